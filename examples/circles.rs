@@ -20,7 +20,8 @@ use limn::widgets::edit_text::TextUpdated;
 use limn::widgets::text::StaticTextStyle;
 use limn::widget::draw::OpacityModifier;
 use limn::input::keyboard::KeyboardInput;
-use limn::input::drag::DragEvent;
+use limn::input::drag::{DragEvent, DragState};
+use limn::history::{History, Command};
 
 
 fn create_slider_control<F: FnMut(&SliderEvent, EventArgs) + 'static>(title: &str, range: Range<f32>, mut on_slider_event: F) -> Widget {
@@ -184,18 +185,73 @@ impl EventHandler<CircleEvent> for CircleHandler {
             }
             CircleEvent::Drag(ref event) => {
                 args.ui.event(AppEvent::Move(self.0, event.change));
+                if event.state == DragState::End {
+                    args.ui.event(AppEvent::EndMove);
+                }
             }
         }
     }
 }
 
+#[derive(Clone)]
 enum Change {
     Create(CircleId, Circle),
     Delete(CircleId),
     Resize(CircleId, f32),
     ChangeOpacity(CircleId, f32),
     Move(CircleId, Vector),
-    None,
+}
+
+impl Command for Change {
+    type Context = Circles;
+    fn apply(self, circles: &mut Circles) -> Self {
+        match self {
+            Change::Create(circle_id, circle) => {
+                let widget_ref = create_circle(circle_id, &circle, &mut circles.circle_canvas_ref);
+                circles.circles.insert(circle_id, circle);
+                circles.circle_widgets.insert(circle_id, widget_ref);
+                circles.update_selected(Some(circle_id));
+                Change::Delete(circle_id)
+            },
+            Change::Delete(circle_id) => {
+                circles.update_selected(None);
+                let mut widget_ref = circles.circle_widgets.remove(&circle_id).unwrap();
+                widget_ref.remove_widget();
+                let circle = circles.circles.remove(&circle_id).unwrap();
+                Change::Create(circle_id, circle)
+            }
+            Change::Resize(circle_id, size_change) => {
+                let circle = circles.circles.get_mut(&circle_id).unwrap();
+                circle.size += size_change;
+                circles.circle_widgets[&circle_id].event(CircleEvent::Update(circle.center, circle.size, circle.alpha));
+                circles.size_slider_ref.event(SetSliderValue(circle.size));
+                Change::Resize(circle_id, -size_change)
+            }
+            Change::ChangeOpacity(circle_id, alpha_change) => {
+                let circle = circles.circles.get_mut(&circle_id).unwrap();
+                circle.alpha += alpha_change;
+                circles.circle_widgets[&circle_id].event(CircleEvent::Update(circle.center, circle.size, circle.alpha));
+                circles.alpha_slider_ref.event(SetSliderValue(circle.alpha));
+                Change::ChangeOpacity(circle_id, -alpha_change)
+            }
+            Change::Move(circle_id, pos_change) => {
+                let circle = circles.circles.get_mut(&circle_id).unwrap();
+                circle.center += pos_change;
+                circles.circle_widgets[&circle_id].event(CircleEvent::Update(circle.center, circle.size, circle.alpha));
+                Change::Move(circle_id, -pos_change)
+            }
+        }
+    }
+    // a drag is undone in one step, the history is sealed when the drag ends
+    fn coalesce(&mut self, newer: &Self) -> bool {
+        match (self, newer) {
+            (&mut Change::Move(id, ref mut change), &Change::Move(newer_id, newer_change)) if id == newer_id => {
+                *change += newer_change;
+                true
+            }
+            _ => false,
+        }
+    }
 }
 
 enum AppEvent {
@@ -208,6 +264,7 @@ enum AppEvent {
     Resize(SliderEvent),
     ChangeOpacity(SliderEvent),
     Move(CircleId, Vector),
+    EndMove,
 }
 
 #[derive(Clone)]
@@ -219,48 +276,18 @@ struct Circle {
 
 named_id!(CircleId);
 
-struct AppEventHandler {
+/// The circles, and the widgets that display them, modified by `Change`s
+struct Circles {
     circle_canvas_ref: Widget,
-    create_ref: Widget,
-    undo_ref: Widget,
-    redo_ref: Widget,
     size_slider_ref: Widget,
     alpha_slider_ref: Widget,
 
-    id_gen: IdGen<CircleId>,
     circle_widgets: HashMap<CircleId, Widget>,
-    create_mode: bool,
     circles: HashMap<CircleId, Circle>,
-    undo_queue: Vec<Change>,
-    redo_queue: Vec<Change>,
     selected: Option<CircleId>,
 }
 
-impl AppEventHandler {
-    fn new(circle_canvas_ref: Widget, control_bar: &ControlBarRefs) -> Self {
-        let mut handler = AppEventHandler {
-            circle_canvas_ref: circle_canvas_ref,
-            create_ref: control_bar.create.clone(),
-            undo_ref: control_bar.undo.clone(),
-            redo_ref: control_bar.redo.clone(),
-            size_slider_ref: control_bar.size_slider.clone(),
-            alpha_slider_ref: control_bar.alpha_slider.clone(),
-
-            id_gen: IdGen::new(),
-            circle_widgets: HashMap::new(),
-            create_mode: true,
-            circles: HashMap::new(),
-            undo_queue: Vec::new(),
-            redo_queue: Vec::new(),
-            selected: None,
-        };
-        handler.create_ref.add_prop(Property::Activated);
-        handler.undo_ref.add_prop(Property::Inactive);
-        handler.redo_ref.add_prop(Property::Inactive);
-        handler.size_slider_ref.add_prop(Property::Inactive);
-        handler.alpha_slider_ref.add_prop(Property::Inactive);
-        handler
-    }
+impl Circles {
     fn update_selected(&mut self, new_selected: Option<CircleId>) {
         if let Some(ref selected) = self.selected {
             self.circle_widgets.get_mut(selected).unwrap().remove_prop(Property::Selected);
@@ -279,70 +306,59 @@ impl AppEventHandler {
             self.alpha_slider_ref.add_prop(Property::Inactive);
         }
     }
-    fn apply_change(&mut self, change: Change) -> Change {
-        match change {
-            Change::Create(circle_id, circle) => {
-                let widget_ref = create_circle(circle_id, &circle, &mut self.circle_canvas_ref);
-                self.circles.insert(circle_id, circle);
-                self.circle_widgets.insert(circle_id, widget_ref);
-                self.update_selected(Some(circle_id));
-                Change::Delete(circle_id)
+}
+
+struct AppEventHandler {
+    create_ref: Widget,
+    undo_ref: Widget,
+    redo_ref: Widget,
+
+    id_gen: IdGen<CircleId>,
+    create_mode: bool,
+    circles: Circles,
+    history: History<Change>,
+}
+
+impl AppEventHandler {
+    fn new(circle_canvas_ref: Widget, control_bar: &ControlBarRefs) -> Self {
+        let mut handler = AppEventHandler {
+            create_ref: control_bar.create.clone(),
+            undo_ref: control_bar.undo.clone(),
+            redo_ref: control_bar.redo.clone(),
+
+            id_gen: IdGen::new(),
+            create_mode: true,
+            circles: Circles {
+                circle_canvas_ref: circle_canvas_ref,
+                size_slider_ref: control_bar.size_slider.clone(),
+                alpha_slider_ref: control_bar.alpha_slider.clone(),
+                circle_widgets: HashMap::new(),
+                circles: HashMap::new(),
+                selected: None,
             },
-            Change::Delete(circle_id) => {
-                self.update_selected(None);
-                let mut widget_ref = self.circle_widgets.remove(&circle_id).unwrap();
-                widget_ref.remove_widget();
-                let circle = self.circles.remove(&circle_id).unwrap();
-                Change::Create(circle_id, circle)
-            }
-            Change::Resize(circle_id, size_change) => {
-                let circle = self.circles.get_mut(&circle_id).unwrap();
-                circle.size += size_change;
-                self.circle_widgets[&circle_id].event(CircleEvent::Update(circle.center, circle.size, circle.alpha));
-                self.size_slider_ref.event(SetSliderValue(circle.size));
-                Change::Resize(circle_id, -size_change)
-            }
-            Change::ChangeOpacity(circle_id, alpha_change) => {
-                let circle = self.circles.get_mut(&circle_id).unwrap();
-                circle.alpha += alpha_change;
-                self.circle_widgets[&circle_id].event(CircleEvent::Update(circle.center, circle.size, circle.alpha));
-                self.alpha_slider_ref.event(SetSliderValue(circle.alpha));
-                Change::ChangeOpacity(circle_id, -alpha_change)
-            }
-            Change::Move(circle_id, pos_change) => {
-                let circle = self.circles.get_mut(&circle_id).unwrap();
-                circle.center += pos_change;
-                self.circle_widgets[&circle_id].event(CircleEvent::Update(circle.center, circle.size, circle.alpha));
-                Change::Move(circle_id, -pos_change)
-            }
-            _ => Change::None
-        }
+            history: History::new(),
+        };
+        handler.create_ref.add_prop(Property::Activated);
+        handler.undo_ref.add_prop(Property::Inactive);
+        handler.redo_ref.add_prop(Property::Inactive);
+        handler.circles.size_slider_ref.add_prop(Property::Inactive);
+        handler.circles.alpha_slider_ref.add_prop(Property::Inactive);
+        handler
     }
     fn new_change(&mut self, change: Change) {
-        let change = self.apply_change(change);
-        self.undo_queue.push(change);
-        self.redo_queue.clear();
-        self.undo_ref.remove_prop(Property::Inactive);
-        self.redo_ref.add_prop(Property::Inactive);
-    }
-    fn undo(&mut self) {
-        if let Some(change) = self.undo_queue.pop() {
-            let change = self.apply_change(change);
-            self.redo_queue.push(change);
-            self.redo_ref.remove_prop(Property::Inactive);
-            if self.undo_queue.is_empty() {
-                self.undo_ref.add_prop(Property::Inactive);
-            }
-        }
+        self.history.apply(change, &mut self.circles);
+        self.update_buttons();
     }
-    fn redo(&mut self) {
-        if let Some(change) = self.redo_queue.pop() {
-            let change = self.apply_change(change);
-            self.undo_queue.push(change);
+    fn update_buttons(&mut self) {
+        if self.history.can_undo() {
             self.undo_ref.remove_prop(Property::Inactive);
-            if self.redo_queue.is_empty() {
-                self.redo_ref.add_prop(Property::Inactive);
-            }
+        } else {
+            self.undo_ref.add_prop(Property::Inactive);
+        }
+        if self.history.can_redo() {
+            self.redo_ref.remove_prop(Property::Inactive);
+        } else {
+            self.redo_ref.add_prop(Property::Inactive);
         }
     }
 }
@@ -358,38 +374,40 @@ impl EventHandler<AppEvent> for AppEventHandler {
                     let circle_id = self.id_gen.next_id();
                     self.new_change(Change::Create(circle_id, circle));
                 } else {
-                    self.update_selected(None);
+                    self.circles.update_selected(None);
                 }
             }
             AppEvent::Undo => {
-                self.undo();
+                self.history.undo(&mut self.circles);
+                self.update_buttons();
             }
             AppEvent::Redo => {
-                self.redo();
+                self.history.redo(&mut self.circles);
+                self.update_buttons();
             }
             AppEvent::Select(new_selected) => {
-                self.update_selected(new_selected);
+                self.circles.update_selected(new_selected);
             }
             AppEvent::Delete => {
-                if let Some(selected) = self.selected.take() {
+                if let Some(selected) = self.circles.selected {
                     self.new_change(Change::Delete(selected));
                 }
             }
             AppEvent::Resize(ref event) => {
-                if let Some(selected) = self.selected {
+                if let Some(selected) = self.circles.selected {
                     if event.dragging {
-                        let circle = &self.circles[&selected];
-                        self.circle_widgets[&selected].event(CircleEvent::Update(circle.center, event.value, circle.alpha));
+                        let circle = &self.circles.circles[&selected];
+                        self.circles.circle_widgets[&selected].event(CircleEvent::Update(circle.center, event.value, circle.alpha));
                     } else {
                         self.new_change(Change::Resize(selected, event.offset));
                     }
                 }
             }
             AppEvent::ChangeOpacity(ref event) => {
-                if let Some(selected) = self.selected {
+                if let Some(selected) = self.circles.selected {
                     if event.dragging {
-                        let circle = &self.circles[&selected];
-                        self.circle_widgets[&selected].event(CircleEvent::Update(circle.center, circle.size, event.value));
+                        let circle = &self.circles.circles[&selected];
+                        self.circles.circle_widgets[&selected].event(CircleEvent::Update(circle.center, circle.size, event.value));
                     } else {
                         self.new_change(Change::ChangeOpacity(selected, event.offset));
                     }
@@ -398,6 +416,9 @@ impl EventHandler<AppEvent> for AppEventHandler {
             AppEvent::Move(widget_ref, change) => {
                 self.new_change(Change::Move(widget_ref, change));
             }
+            AppEvent::EndMove => {
+                self.history.seal();
+            }
         }
     }
 }
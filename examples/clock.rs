@@ -60,23 +60,13 @@ impl Draw for ClockHand {
     fn draw(&mut self, bounds: Rect, _: Rect, renderer: &mut RenderBuilder) {
         let transform = rotation_transform(&bounds.center(),
             self.rotation + Angle::radians(f32::consts::PI));
-        renderer.builder.push_stacking_context(
-            &PrimitiveInfo::new(Rect::zero()),
-            ScrollPolicy::Fixed,
-            Some(PropertyBinding::Value(transform)),
-            TransformStyle::Flat,
-            None,
-            MixBlendMode::Normal,
-            Vec::new(),
-        );
+        renderer.push_stacking_context(None, Some(transform));
         let rect = Rect::new(
             bounds.center() + Size::new(-self.width / 2.0, 0.0),
             Size::new(self.width, self.length)
         );
-        renderer.builder.push_rect(
-            &PrimitiveInfo::new(rect),
-            self.color.into());
-        renderer.builder.pop_stacking_context();
+        renderer.push_rect(&PrimitiveInfo::new(rect), self.color);
+        renderer.pop_stacking_context();
     }
 }
 
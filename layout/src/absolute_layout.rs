@@ -0,0 +1,81 @@
+use cassowary::strength::*;
+use cassowary::WeightedRelation::*;
+use cassowary::Variable;
+
+use super::{Layout, LayoutContainer, VariableEditable};
+use super::stack_layout::Anchor;
+
+/// Placement of a child of an `AbsoluteLayout`, set using `Layout::set_item_props`
+/// before the child is added to the layout.
+/// The child's anchor point is placed at an offset from the same anchor point of the parent,
+/// ie. `Position::new(-10.0, -10.0).anchor(Anchor::BottomRight)` places the bottom right
+/// corner of the child 10 pixels above and to the left of the bottom right corner of the parent.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Position {
+    pub anchor: Anchor,
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Position {
+    pub fn new(x: f32, y: f32) -> Self {
+        Position {
+            anchor: Anchor::TopLeft,
+            x: x,
+            y: y,
+        }
+    }
+    pub fn anchor(mut self, anchor: Anchor) -> Self {
+        self.anchor = anchor;
+        self
+    }
+}
+
+/// The variables for the offset of a child of an `AbsoluteLayout`
+#[derive(Debug, Copy, Clone)]
+struct OffsetVars {
+    x: Variable,
+    y: Variable,
+}
+
+/// Places children at explicit offsets within the layout, without constraining their size.
+/// Children without a `Position` are placed at the top left corner.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct AbsoluteLayout;
+
+impl AbsoluteLayout {
+    pub fn new(_: &mut Layout) -> Self {
+        AbsoluteLayout
+    }
+}
+
+impl LayoutContainer for AbsoluteLayout {
+    fn add_child(&mut self, parent: &mut Layout, child: &mut Layout) {
+        let position = child.item_props::<Position>().cloned().unwrap_or_else(|| Position::new(0.0, 0.0));
+        let offset = OffsetVars {
+            x: Variable::new(),
+            y: Variable::new(),
+        };
+        child.add_associated_var(offset.x, "offset_x");
+        child.add_associated_var(offset.y, "offset_y");
+        let (parent_x, parent_y) = position.anchor.point(&parent.vars, 0.0);
+        let (child_x, child_y) = position.anchor.point(&child.vars, 0.0);
+        child.add(vec![
+            child_x | EQ(REQUIRED) | parent_x + offset.x,
+            child_y | EQ(REQUIRED) | parent_y + offset.y,
+        ]);
+        child.set_item_props(offset);
+        child.set_offset(position.x, position.y);
+    }
+}
+
+impl Layout {
+    /// Moves a child of an `AbsoluteLayout` to a new offset from it's anchor.
+    /// Has no effect if this layout hasn't been added to an `AbsoluteLayout`.
+    pub fn set_offset(&mut self, x: f32, y: f32) {
+        if let Some(offset) = self.item_props::<OffsetVars>().cloned() {
+            VariableEditable::new(self, offset.x).set(x);
+            VariableEditable::new(self, offset.y).set(y);
+        }
+    }
+}
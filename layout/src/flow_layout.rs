@@ -0,0 +1,177 @@
+use std::mem;
+
+use cassowary::strength::*;
+use cassowary::WeightedRelation::*;
+use cassowary::{Variable, Constraint};
+
+use super::{LayoutId, LayoutVars, Layout, LayoutContainer, LimnSolver};
+
+/// Horizontal alignment of the items in each row of a `FlowLayout`
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum FlowAlignment {
+    /// Rows are packed against the left of the layout
+    Start,
+    /// Rows are centered within the layout
+    Center,
+    /// Rows are packed against the right of the layout
+    End,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct FlowLayoutSettings {
+    /// Space between items in a row
+    pub spacing: f32,
+    /// Space between rows
+    pub row_spacing: f32,
+    /// Horizontal alignment of the items in each row
+    pub align: FlowAlignment,
+}
+
+impl FlowLayoutSettings {
+    pub fn new() -> Self {
+        FlowLayoutSettings {
+            spacing: 0.0,
+            row_spacing: 0.0,
+            align: FlowAlignment::Start,
+        }
+    }
+}
+
+impl Default for FlowLayoutSettings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+struct RowVars {
+    top: Variable,
+    bottom: Variable,
+}
+
+/// Lays out items left to right, wrapping onto a new row when an item doesn't fit within
+/// the width of the layout. Each row is as tall as it's tallest item, and the layout is
+/// as tall as it's rows, if it's height isn't constrained otherwise.
+///
+/// Which row each item is in depends on the solved widths of the layout and it's items,
+/// so the rows are recalculated in `reflow`, after the layout has been solved.
+pub struct FlowLayout {
+    settings: FlowLayoutSettings,
+    items: Vec<(LayoutId, LayoutVars)>,
+    /// Index of the first item in each row, after the first
+    row_breaks: Vec<usize>,
+    rows: Vec<RowVars>,
+    constraints: Vec<Constraint>,
+}
+
+impl FlowLayout {
+    pub fn new(_: &mut Layout, settings: FlowLayoutSettings) -> Self {
+        FlowLayout {
+            settings: settings,
+            items: Vec::new(),
+            row_breaks: Vec::new(),
+            rows: Vec::new(),
+            constraints: Vec::new(),
+        }
+    }
+
+    /// Replaces the constraints positioning the items, based on the current row breaks
+    fn update_constraints(&mut self, parent: &mut Layout) {
+        parent.remove_constraints(mem::replace(&mut self.constraints, Vec::new()));
+        if self.items.is_empty() {
+            return;
+        }
+        let mut row_starts = vec![0];
+        row_starts.extend(self.row_breaks.iter().cloned());
+        while self.rows.len() < row_starts.len() {
+            let row = RowVars {
+                top: Variable::new(),
+                bottom: Variable::new(),
+            };
+            parent.add_associated_var(row.top, &format!("flow_row_{}.top", self.rows.len()));
+            parent.add_associated_var(row.bottom, &format!("flow_row_{}.bottom", self.rows.len()));
+            self.rows.push(row);
+        }
+        let mut constraints = Vec::new();
+        for (index, &start) in row_starts.iter().enumerate() {
+            let end = row_starts.get(index + 1).cloned().unwrap_or_else(|| self.items.len());
+            let row = self.rows[index];
+            if index == 0 {
+                constraints.push(row.top | EQ(REQUIRED) | parent.vars.top);
+            } else {
+                let prev_row = self.rows[index - 1];
+                constraints.push(row.top | EQ(REQUIRED) | prev_row.bottom + self.settings.row_spacing);
+            }
+            constraints.push(row.bottom | GE(REQUIRED) | row.top);
+            constraints.push(row.bottom | EQ(WEAK) | row.top);
+
+            let items = &self.items[start..end];
+            for (item_index, &(_, ref item)) in items.iter().enumerate() {
+                constraints.push(item.top | EQ(REQUIRED) | row.top);
+                constraints.push(item.bottom | LE(REQUIRED) | row.bottom);
+                if item_index > 0 {
+                    let prev = items[item_index - 1].1;
+                    constraints.push(item.left | EQ(REQUIRED) | prev.right + self.settings.spacing);
+                }
+            }
+            let (first, last) = (items[0].1, items[items.len() - 1].1);
+            match self.settings.align {
+                FlowAlignment::Start => constraints.push(first.left | EQ(REQUIRED) | parent.vars.left),
+                FlowAlignment::End => constraints.push(last.right | EQ(REQUIRED) | parent.vars.right),
+                FlowAlignment::Center => {
+                    constraints.push(first.left - parent.vars.left | EQ(REQUIRED) | parent.vars.right - last.right);
+                }
+            }
+        }
+        let last_row = self.rows[row_starts.len() - 1];
+        constraints.push(parent.vars.bottom | EQ(STRONG) | last_row.bottom);
+        parent.add(constraints.clone());
+        self.constraints = constraints;
+    }
+}
+
+impl LayoutContainer for FlowLayout {
+    fn add_child(&mut self, parent: &mut Layout, child: &mut Layout) {
+        // new items are added to the last row, until the next reflow
+        self.items.push((child.id, child.vars));
+        self.update_constraints(parent);
+    }
+
+    fn remove_child(&mut self, parent: &mut Layout, child: &mut Layout) {
+        if let Some(index) = self.items.iter().position(|&(id, _)| id == child.id) {
+            self.items.remove(index);
+            let len = self.items.len();
+            self.row_breaks = self.row_breaks.iter()
+                .map(|&row_break| if row_break > index { row_break - 1 } else { row_break })
+                .filter(|&row_break| row_break > 0 && row_break < len)
+                .collect();
+            self.row_breaks.dedup();
+            self.update_constraints(parent);
+        }
+    }
+
+    fn reflow(&mut self, parent: &mut Layout, solver: &LimnSolver) -> bool {
+        let available = solver.get_value(parent.vars.width);
+        let spacing = self.settings.spacing as f64;
+        let mut row_breaks = Vec::new();
+        let mut row_width = 0.0;
+        for (index, &(_, ref item)) in self.items.iter().enumerate() {
+            let width = solver.get_value(item.width);
+            if index > 0 && row_width + spacing + width > available + 0.001 {
+                row_breaks.push(index);
+                row_width = width;
+            } else if index > 0 {
+                row_width += spacing + width;
+            } else {
+                row_width = width;
+            }
+        }
+        if row_breaks != self.row_breaks {
+            self.row_breaks = row_breaks;
+            self.update_constraints(parent);
+            true
+        } else {
+            false
+        }
+    }
+}
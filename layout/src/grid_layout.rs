@@ -1,86 +1,258 @@
+use std::collections::HashMap;
+
 use cassowary::strength::*;
 use cassowary::WeightedRelation::*;
+use cassowary::{Variable, Constraint};
+
+use super::{LayoutId, Layout, LayoutContainer, LayoutError};
 
-use super::{LayoutVars, Layout, Constraint, LayoutContainer};
-use super::constraint::*;
+/// The size of a row or column in a `GridLayout`
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Track {
+    /// A fixed size
+    Fixed(f32),
+    /// A share of the space left after the other tracks, relative to the other fraction tracks
+    Fraction(f32),
+    /// Sized to fit the largest item in the track
+    Auto,
+}
 
 #[derive(Debug, Clone)]
-pub struct GridLayout {
-    num_columns: usize,
-    column: usize,
-    rows: Vec<LayoutVars>,
-    columns: Vec<LayoutVars>,
-    row_end: Option<Constraint>,
+pub struct GridLayoutSettings {
+    pub columns: Vec<Track>,
+    /// Rows that are created up front, rows beyond these are created as items are placed in them
+    pub rows: Vec<Track>,
+    /// The size of rows created as items are placed in them
+    pub implicit_row: Track,
+    /// Space between columns
+    pub column_gap: f32,
+    /// Space between rows
+    pub row_gap: f32,
 }
 
-impl GridLayout {
-    pub fn new(parent: &mut Layout, num_columns: usize) -> Self {
-        assert!(num_columns > 0, "can't create grid layout with no columns");
-        let mut columns = Vec::new();
-        for col in 0..num_columns {
-            let column = LayoutVars::new();
+impl GridLayoutSettings {
+    /// Creates settings for a grid with `num_columns` columns of equal width,
+    /// and rows of equal height
+    pub fn new(num_columns: usize) -> Self {
+        GridLayoutSettings {
+            columns: vec![Track::Fraction(1.0); num_columns],
+            rows: Vec::new(),
+            implicit_row: Track::Fraction(1.0),
+            column_gap: 0.0,
+            row_gap: 0.0,
+        }
+    }
+}
+
+/// Placement of a child of a `GridLayout`, set using `Layout::set_item_props`
+/// before the child is added to the layout.
+/// Children without a `GridCell` are placed in the next free cell, in the order they are added.
+/// A cell outside of the grid's columns, or overlapping another child, is reported as a `LayoutError`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct GridCell {
+    pub row: usize,
+    pub column: usize,
+    pub row_span: usize,
+    pub column_span: usize,
+}
+
+impl GridCell {
+    pub fn new(row: usize, column: usize) -> Self {
+        GridCell {
+            row: row,
+            column: column,
+            row_span: 1,
+            column_span: 1,
+        }
+    }
+    pub fn span(mut self, row_span: usize, column_span: usize) -> Self {
+        self.row_span = row_span;
+        self.column_span = column_span;
+        self
+    }
+    fn contains(&self, row: usize, column: usize) -> bool {
+        row >= self.row && row < self.row + self.row_span &&
+            column >= self.column && column < self.column + self.column_span
+    }
+    fn overlaps(&self, other: &GridCell) -> bool {
+        self.row < other.row + other.row_span && other.row < self.row + self.row_span &&
+            self.column < other.column + other.column_span && other.column < self.column + self.column_span
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+struct TrackVars {
+    start: Variable,
+    end: Variable,
+    size: Variable,
+}
+
+/// The rows or columns of a grid
+#[derive(Debug, Clone)]
+struct Tracks {
+    name: &'static str,
+    tracks: Vec<TrackVars>,
+    sizes: Vec<Track>,
+    implicit: Track,
+    gap: f32,
+    /// Size of one fraction of the remaining space
+    fraction: Variable,
+    parent_start: Variable,
+    parent_end: Variable,
+    end_constraint: Option<Constraint>,
+}
+
+impl Tracks {
+    fn new(parent: &mut Layout, name: &'static str, sizes: Vec<Track>, implicit: Track, gap: f32, parent_start: Variable, parent_end: Variable) -> Self {
+        let fraction = Variable::new();
+        parent.add_associated_var(fraction, &format!("{}_fraction", name));
+        parent.add(fraction | GE(REQUIRED) | 0.0);
+        let count = sizes.len();
+        let mut tracks = Tracks {
+            name: name,
+            tracks: Vec::new(),
+            sizes: sizes,
+            implicit: implicit,
+            gap: gap,
+            fraction: fraction,
+            parent_start: parent_start,
+            parent_end: parent_end,
+            end_constraint: None,
+        };
+        tracks.ensure(parent, count);
+        tracks
+    }
+
+    /// Creates tracks until there are at least `count`
+    fn ensure(&mut self, parent: &mut Layout, count: usize) {
+        while self.tracks.len() < count {
+            let index = self.tracks.len();
+            let track = TrackVars {
+                start: Variable::new(),
+                end: Variable::new(),
+                size: Variable::new(),
+            };
+            parent.add_associated_var(track.start, &format!("{}_{}.start", self.name, index));
+            parent.add_associated_var(track.end, &format!("{}_{}.end", self.name, index));
+            parent.add_associated_var(track.size, &format!("{}_{}.size", self.name, index));
             let mut constraints = vec![
-                column.top | EQ(REQUIRED) | parent.vars.top,
-                column.bottom | EQ(REQUIRED) | parent.vars.bottom,
-                column.right - column.left | EQ(REQUIRED) | column.width,
-                column.bottom - column.top | EQ(REQUIRED) | column.height,
+                track.end - track.start | EQ(REQUIRED) | track.size,
+                track.size | GE(REQUIRED) | 0.0,
             ];
-            if let Some(last_column) = columns.last() {
-                let last_column: &LayoutVars = last_column;
-                constraints.push(column.left | EQ(REQUIRED) | last_column.right);
-                constraints.push(column.width | EQ(REQUIRED) | last_column.width);
+            match self.sizes.get(index).cloned().unwrap_or(self.implicit) {
+                Track::Fixed(size) => constraints.push(track.size | EQ(REQUIRED) | size),
+                Track::Fraction(fraction) => constraints.push(track.size | EQ(STRONG) | self.fraction * fraction),
+                Track::Auto => constraints.push(track.size | EQ(WEAK) | 0.0),
+            }
+            if let Some(prev) = self.tracks.last() {
+                constraints.push(track.start | EQ(REQUIRED) | prev.end + self.gap);
             } else {
-                constraints.push(column.left | EQ(REQUIRED) | parent.vars.left);
+                constraints.push(track.start | EQ(REQUIRED) | self.parent_start);
             }
-            if col == num_columns - 1 {
-                constraints.push(column.right | EQ(REQUIRED) | parent.vars.right);
+            if let Some(end_constraint) = self.end_constraint.take() {
+                parent.remove_constraint(end_constraint);
             }
+            let end_constraint = track.end | EQ(MEDIUM) | self.parent_end;
+            self.end_constraint = Some(end_constraint.clone());
+            constraints.push(end_constraint);
             parent.add(constraints);
-            parent.add_associated_vars(&column, &format!("column_{}", col));
-            columns.push(column);
+            self.tracks.push(track);
         }
+    }
+
+    /// The start of the first track and the end of the last track in a span
+    fn span(&self, start: usize, span: usize) -> (Variable, Variable) {
+        (self.tracks[start].start, self.tracks[start + span - 1].end)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GridLayout {
+    columns: Tracks,
+    rows: Tracks,
+    cells: HashMap<LayoutId, GridCell>,
+}
+
+impl GridLayout {
+    /// Creates a grid with `num_columns` columns of equal width, and rows of equal height
+    pub fn new(parent: &mut Layout, num_columns: usize) -> Self {
+        GridLayout::with_settings(parent, GridLayoutSettings::new(num_columns))
+    }
+
+    pub fn with_settings(parent: &mut Layout, settings: GridLayoutSettings) -> Self {
+        assert!(settings.columns.len() > 0, "can't create grid layout with no columns");
+        let vars = parent.vars;
+        let columns = Tracks::new(parent, "column", settings.columns, Track::Auto, settings.column_gap, vars.left, vars.right);
+        let rows = Tracks::new(parent, "row", settings.rows, settings.implicit_row, settings.row_gap, vars.top, vars.bottom);
         GridLayout {
-            num_columns: num_columns,
-            column: 0,
-            rows: Vec::new(),
             columns: columns,
-            row_end: None,
+            rows: rows,
+            cells: HashMap::new(),
+        }
+    }
+
+    fn is_free(&self, cell: &GridCell) -> bool {
+        !self.cells.values().any(|other| other.overlaps(cell))
+    }
+
+    /// The first free cell, in row major order
+    fn next_free_cell(&self) -> GridCell {
+        let num_columns = self.columns.tracks.len();
+        let mut row = 0;
+        loop {
+            for column in 0..num_columns {
+                if !self.cells.values().any(|cell| cell.contains(row, column)) {
+                    return GridCell::new(row, column);
+                }
+            }
+            row += 1;
         }
     }
 }
+
 impl LayoutContainer for GridLayout {
     fn add_child(&mut self, parent: &mut Layout, child: &mut Layout) {
-        if self.column == self.num_columns || self.rows.len() == 0 {
-            let row = LayoutVars::new();
-            let mut constraints = vec![
-                row.left | EQ(REQUIRED) | parent.vars.left,
-                row.right | EQ(REQUIRED) | parent.vars.right,
-                row.right - row.left | EQ(REQUIRED) | row.width,
-                row.bottom - row.top | EQ(REQUIRED) | row.height,
-            ];
-            if let Some(last_row) = self.rows.last() {
-                constraints.push(row.top | EQ(REQUIRED) | last_row.bottom);
-                constraints.push(row.height | EQ(REQUIRED) | last_row.height);
-            } else {
-                constraints.push(row.top | EQ(REQUIRED) | parent.vars.top);
-            }
-            if let Some(row_end) = self.row_end.take() {
-                parent.remove_constraint(row_end);
+        let cell = match child.item_props::<GridCell>().cloned() {
+            Some(cell) => {
+                let layout = child.name.clone().unwrap_or_else(|| "unknown".to_owned());
+                let valid = cell.row_span > 0 && cell.column_span > 0 &&
+                    cell.column + cell.column_span <= self.columns.tracks.len();
+                if !valid {
+                    child.add_error(LayoutError::Container {
+                        layout: layout,
+                        message: format!("invalid grid cell {:?}, placed in the next free cell instead", cell),
+                    });
+                    self.next_free_cell()
+                } else {
+                    if !self.is_free(&cell) {
+                        child.add_error(LayoutError::Container {
+                            layout: layout,
+                            message: format!("grid cell {:?} overlaps another cell", cell),
+                        });
+                    }
+                    cell
+                }
             }
-            let row_end = row.bottom | EQ(REQUIRED) | parent.vars.bottom;
-            self.row_end = Some(row_end.clone());
-            constraints.push(row_end);
-            parent.add(constraints);
-            parent.add_associated_vars(&row, &format!("row_{}", self.rows.len()));
-            self.rows.push(row);
-            self.column = 0;
-        }
-        let (row, col) = (self.rows.last().unwrap(), self.columns.get(self.column).unwrap());
-        child.add(constraints![
-            bound_by(row),
-            bound_by(col),
-            match_layout(&parent).strength(STRONG),
+            None => self.next_free_cell(),
+        };
+        self.rows.ensure(parent, cell.row + cell.row_span);
+        self.cells.insert(child.id, cell);
+
+        let (left, right) = self.columns.span(cell.column, cell.column_span);
+        let (top, bottom) = self.rows.span(cell.row, cell.row_span);
+        child.add(vec![
+            child.vars.left | GE(REQUIRED) | left,
+            child.vars.right | LE(REQUIRED) | right,
+            child.vars.top | GE(REQUIRED) | top,
+            child.vars.bottom | LE(REQUIRED) | bottom,
+            child.vars.left | EQ(STRONG) | left,
+            child.vars.right | EQ(STRONG) | right,
+            child.vars.top | EQ(STRONG) | top,
+            child.vars.bottom | EQ(STRONG) | bottom,
         ]);
-        self.column += 1;
+    }
+
+    fn remove_child(&mut self, _: &mut Layout, child: &mut Layout) {
+        self.cells.remove(&child.id);
     }
 }
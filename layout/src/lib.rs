@@ -40,7 +40,8 @@ extern crate lazy_static;
 extern crate cassowary;
 extern crate euclid;
 
-use std::collections::HashSet;
+use std::any::{Any, TypeId};
+use std::collections::{HashMap, HashSet};
 use std::ops::Drop;
 use std::mem;
 use std::rc::Rc;
@@ -151,6 +152,13 @@ pub struct Layout {
     pub name: Option<String>,
     pub id: LayoutId,
     container: Option<Rc<RefCell<LayoutContainer>>>,
+    /// Constraints added to this layout by it's container, removed if the container is replaced
+    container_constraints: Vec<Constraint>,
+    /// Constraints added to this layout by the container of it's parent, removed when it's added
+    /// to a container again, if they haven't been added to the solver yet
+    item_constraints: Vec<Constraint>,
+    /// Collects the constraints added while the container is modifying this layout
+    recorded: Option<Vec<Constraint>>,
     parent: Option<LayoutId>,
     children: Vec<LayoutId>,
     edit_vars: Vec<EditVariable>,
@@ -159,7 +167,11 @@ pub struct Layout {
     removed_constraints: Vec<Constraint>,
     removed_children: Vec<LayoutId>,
     associated_vars: Vec<(Variable, String)>,
+    item_props: HashMap<TypeId, Box<Any>>,
+    /// Errors reported by containers, recorded by the solver when the layout is updated
+    errors: Vec<LayoutError>,
     pub hidden: bool,
+    isolated: bool,
 }
 
 impl Layout {
@@ -177,6 +189,9 @@ impl Layout {
             name: name,
             id: id,
             container: Some(Rc::new(RefCell::new(Frame::default()))),
+            container_constraints: Vec::new(),
+            item_constraints: Vec::new(),
+            recorded: None,
             parent: None,
             children: Vec::new(),
             edit_vars: Vec::new(),
@@ -185,7 +200,10 @@ impl Layout {
             removed_constraints: Vec::new(),
             removed_children: Vec::new(),
             associated_vars: Vec::new(),
+            item_props: HashMap::new(),
+            errors: Vec::new(),
             hidden: false,
+            isolated: false,
         }
     }
 
@@ -204,6 +222,23 @@ impl Layout {
     pub fn set_container<T>(&mut self, container: T) where T: LayoutContainer + 'static {
         self.container = Some(Rc::new(RefCell::new(container)));
     }
+    /// Removes the constraints the current container added to this layout, then sets the container
+    /// created by `build`. The constraints `build` adds to this layout are removed if the container
+    /// is replaced again.
+    /// The children of this layout are forgotten, and need to be added again with `add_child`
+    /// to be arranged by the new container, which also removes the constraints the previous
+    /// container added to them.
+    pub fn replace_container<T, F>(&mut self, build: F)
+        where T: LayoutContainer + 'static, F: FnOnce(&mut Layout) -> T
+    {
+        let constraints = mem::replace(&mut self.container_constraints, Vec::new());
+        self.remove_constraints(constraints);
+        self.children.clear();
+        self.recorded = Some(Vec::new());
+        let container = build(self);
+        self.container_constraints = self.recorded.take().unwrap();
+        self.set_container(container);
+    }
     pub fn edit_left(&mut self) -> VariableEditable {
         let var = self.vars.left;
         VariableEditable::new(self, var)
@@ -233,18 +268,24 @@ impl Layout {
     }
     pub fn add<B: ConstraintBuilder>(&mut self, builder: B) {
         let new_constraints = builder.build(&self.vars);
+        if let Some(ref mut recorded) = self.recorded {
+            recorded.extend(new_constraints.iter().cloned());
+        }
         self.new_constraints.extend(new_constraints);
     }
     pub fn remove_constraint(&mut self, constraint: Constraint) {
+        if let Some(ref mut recorded) = self.recorded {
+            recorded.retain(|recorded| *recorded != constraint);
+            self.container_constraints.retain(|added| *added != constraint);
+            self.item_constraints.retain(|added| *added != constraint);
+        }
         if !self.new_constraints.remove(&constraint) {
             self.removed_constraints.push(constraint);
         }
     }
     pub fn remove_constraints(&mut self, constraints: Vec<Constraint>) {
         for constraint in constraints {
-            if !self.new_constraints.remove(&constraint) {
-                self.removed_constraints.push(constraint);
-            }
+            self.remove_constraint(constraint);
         }
     }
     pub fn has_constraint(&mut self, constraints: &Vec<Constraint>) -> bool {
@@ -272,22 +313,61 @@ impl Layout {
     pub fn get_edit_vars(&mut self) -> Vec<EditVariable> {
         mem::replace(&mut self.edit_vars, Vec::new())
     }
+    /// Reports an error in this layout, passed on to the solver when the layout is updated,
+    /// see `LimnSolver::take_errors`
+    pub fn add_error(&mut self, error: LayoutError) {
+        self.errors.push(error);
+    }
+    pub fn get_errors(&mut self) -> Vec<LayoutError> {
+        mem::replace(&mut self.errors, Vec::new())
+    }
     pub fn add_child(&mut self, child: &mut Layout) {
         child.parent = Some(self.id);
         self.children.push(child.id);
+        let item_constraints = mem::replace(&mut child.item_constraints, Vec::new());
+        child.remove_constraints(item_constraints);
         if let Some(container) = self.container.clone() {
+            self.recorded = Some(Vec::new());
+            child.recorded = Some(Vec::new());
             container.borrow_mut().add_child(self, child);
+            let recorded = self.recorded.take().unwrap();
+            self.container_constraints.extend(recorded);
+            // removed through this layout when the container is replaced, since the solver has to
+            // remove them before the constraints of the new container are added
+            let item_constraints = child.recorded.take().unwrap();
+            self.container_constraints.extend(item_constraints.iter().cloned());
+            child.item_constraints = item_constraints;
         }
     }
     pub fn remove_child(&mut self, child: &mut Layout) {
         if let Some(container) = self.container.clone() {
+            self.recorded = Some(Vec::new());
             container.borrow_mut().remove_child(self, child);
+            let recorded = self.recorded.take().unwrap();
+            self.container_constraints.extend(recorded);
         }
+        // constraints involving the child are removed from the solver along with it
+        let child_vars = child.vars.array();
+        self.container_constraints.retain(|constraint| {
+            !constraint.expr().terms.iter().any(|term| child_vars.contains(&term.variable))
+        });
         if let Some(pos) = self.children.iter().position(|id| child.id == *id) {
             self.children.remove(pos);
         }
         self.removed_children.push(child.id);
     }
+    /// Lets the container update it's constraints based on the solved layout, see `LayoutContainer::reflow`
+    pub fn reflow(&mut self, solver: &LimnSolver) -> bool {
+        if let Some(container) = self.container.clone() {
+            self.recorded = Some(Vec::new());
+            let changed = container.borrow_mut().reflow(self, solver);
+            let recorded = self.recorded.take().unwrap();
+            self.container_constraints.extend(recorded);
+            changed
+        } else {
+            false
+        }
+    }
     pub fn get_removed_children(&mut self) -> Vec<LayoutId> {
         mem::replace(&mut self.removed_children, Vec::new())
     }
@@ -306,12 +386,33 @@ impl Layout {
     pub fn get_associated_vars(&mut self) -> Vec<(Variable, String)> {
         mem::replace(&mut self.associated_vars, Vec::new())
     }
+    /// Sets properties that apply to this layout as a child of a particular type of
+    /// `LayoutContainer`, ie. `FlexItem` for a `LinearLayout`.
+    /// Only has an effect if set before this layout is added to it's parent.
+    pub fn set_item_props<T: 'static>(&mut self, props: T) {
+        self.item_props.insert(TypeId::of::<T>(), Box::new(props));
+    }
+    /// The item properties of type `T` set for this layout, if any
+    pub fn item_props<T: 'static>(&self) -> Option<&T> {
+        self.item_props.get(&TypeId::of::<T>()).and_then(|props| props.downcast_ref::<T>())
+    }
     pub fn hide(&mut self) {
         self.hidden = true;
     }
     pub fn show(&mut self) {
         self.hidden = false;
     }
+    /// Solve the descendants of this layout separately from the rest of the layout graph,
+    /// which avoids re-solving large subtrees when unrelated layouts change.
+    /// Only valid if the bounds of this layout don't depend on it's descendants, ie. if they're
+    /// fully determined by this layouts own constraints and it's ancestors, like an `ExactFrame`.
+    /// If a constraint is added between the subtree and a layout outside of it, or the bounds
+    /// of this layout turn out to depend on it's descendants, the subtree is merged back into
+    /// the solver of it's parent.
+    /// Must be called before the layout is first added to the solver, which debug builds assert.
+    pub fn isolate(&mut self) {
+        self.isolated = true;
+    }
 }
 
 pub struct VariableEditable<'a> {
@@ -388,6 +489,12 @@ macro_rules! constraints {
 pub trait LayoutContainer {
     fn add_child(&mut self, parent: &mut Layout, child: &mut Layout);
     fn remove_child(&mut self, _: &mut Layout, _: &mut Layout) {}
+    /// Called after the layout has been solved, for containers whose constraints depend on the solved
+    /// size of the layout or it's children. Returns true if the constraints changed, in which case
+    /// the layout needs to be updated and solved again.
+    fn reflow(&mut self, _: &mut Layout, _: &LimnSolver) -> bool {
+        false
+    }
 }
 
 #[derive(Debug, Default, Copy, Clone)]
@@ -419,8 +526,11 @@ pub mod solver;
 pub mod constraint;
 pub mod linear_layout;
 pub mod grid_layout;
+pub mod flow_layout;
+pub mod stack_layout;
+pub mod absolute_layout;
 
-pub use self::solver::LimnSolver;
+pub use self::solver::{LimnSolver, LayoutError, UnsatisfiableConstraint};
 
 lazy_static! {
     pub static ref LAYOUT: LayoutVars = LayoutVars::new();
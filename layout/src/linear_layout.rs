@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::mem;
 
 use cassowary::strength::*;
 use cassowary::WeightedRelation::*;
@@ -70,6 +71,37 @@ impl LinearLayoutSettings {
     }
 }
 
+/// Flex properties of a child of a `LinearLayout`, set using `Layout::set_item_props`
+/// before the child is added to the layout.
+///
+/// Growing and shrinking are expressed as constraints on the size of each item along the primary axis,
+/// `size = basis + grow * g - shrink * s`, where `g` and `s` are shared by all the items in the layout.
+#[derive(Debug, Copy, Clone)]
+pub struct FlexItem {
+    /// Share of the remaining space the item grows to fill, relative to the other items,
+    /// if any item grows, the items fill the layout
+    pub grow: f32,
+    /// How much the item shrinks relative to the other items, when they don't fit at their basis.
+    /// Items with a shrink of 0 are never smaller than their basis
+    pub shrink: f32,
+    /// The size of the item along the primary axis, before growing or shrinking.
+    /// If not set, the item only has the size it grows to
+    pub basis: Option<f32>,
+    /// Overrides the `item_align` of the layout for this item
+    pub align: Option<ItemAlignment>,
+}
+
+impl Default for FlexItem {
+    fn default() -> Self {
+        FlexItem {
+            grow: 0.0,
+            shrink: 1.0,
+            basis: None,
+            align: None,
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone)]
 pub enum Orientation {
     Horizontal,
@@ -83,6 +115,7 @@ struct WidgetData {
     prev: Option<LayoutId>,
     next: Option<LayoutId>,
     end_constraint: Option<Constraint>,
+    grows: bool,
 }
 
 pub struct LinearLayout {
@@ -91,6 +124,14 @@ pub struct LinearLayout {
     end: Variable,
     space: Variable,
     size: Option<Variable>,
+    /// Space added to growing items, per unit of grow
+    grow: Variable,
+    /// Space removed from shrinking items, per unit of shrink
+    shrink: Variable,
+    /// Number of items that grow
+    growing: usize,
+    /// Constraints that make growing items fill the layout
+    fill_constraints: Vec<Constraint>,
     widgets: HashMap<LayoutId, WidgetData>,
     last_widget: Option<LayoutId>,
 }
@@ -133,20 +174,57 @@ impl LinearLayout {
         } else {
             None
         };
+        let grow = Variable::new();
+        let shrink = Variable::new();
+        parent.add_associated_var(grow, "linear_layout_grow");
+        parent.add_associated_var(shrink, "linear_layout_shrink");
+        parent.add(constraints![
+            grow | GE(REQUIRED) | 0.0,
+            shrink | GE(REQUIRED) | 0.0,
+            grow | EQ(WEAK) | 0.0,
+            shrink | EQ(WEAK) | 0.0,
+        ]);
         LinearLayout {
             settings: settings,
             start: start,
             end: end,
             space: space,
             size: size,
+            grow: grow,
+            shrink: shrink,
+            growing: 0,
+            fill_constraints: Vec::new(),
             widgets: HashMap::new(),
             last_widget: None,
         }
     }
 }
 
+impl LinearLayout {
+    /// If any items grow, constrains the items to fill the layout, with the minimum spacing
+    fn update_fill(&mut self, parent: &mut Layout) {
+        parent.remove_constraints(mem::replace(&mut self.fill_constraints, Vec::new()));
+        if self.growing == 0 {
+            return;
+        }
+        if let Some(last_id) = self.last_widget {
+            let mut first_id = last_id;
+            while let Some(prev) = self.widgets[&first_id].prev {
+                first_id = prev;
+            }
+            self.fill_constraints = vec![
+                self.widgets[&first_id].start | EQ(MEDIUM) | self.start,
+                self.widgets[&last_id].end | EQ(MEDIUM) | self.end,
+                self.space | EQ(MEDIUM) | self.settings.padding,
+            ];
+            parent.add(self.fill_constraints.clone());
+        }
+    }
+}
+
 impl LayoutContainer for LinearLayout {
     fn add_child(&mut self, parent: &mut Layout, child: &mut Layout) {
+        let flex = child.item_props::<FlexItem>().cloned();
 
         let child_start = beginning(self.settings.orientation, &child.vars);
         let child_end = ending(self.settings.orientation, &child.vars);
@@ -182,16 +260,33 @@ impl LayoutContainer for LinearLayout {
             prev: self.last_widget,
             next: None,
             end_constraint: end_constraint,
+            grows: flex.map_or(false, |flex| flex.grow > 0.0),
         });
         self.last_widget = Some(child.id);
 
+        let child_size = axis_length(self.settings.orientation, &child.vars);
         if self.settings.fill_equal {
-            let child_size = axis_length(self.settings.orientation, &child.vars);
             parent.add(child_size | EQ(REQUIRED) | self.size.unwrap());
         }
+        if let Some(flex) = flex {
+            if let Some(basis) = flex.basis {
+                parent.add(child_size | EQ(STRONG) | basis + self.grow * flex.grow - self.shrink * flex.shrink);
+                if flex.shrink == 0.0 {
+                    parent.add(child_size | GE(REQUIRED) | basis);
+                }
+            } else if flex.grow > 0.0 {
+                parent.add(child_size | EQ(STRONG) | self.grow * flex.grow);
+            }
+            if flex.grow > 0.0 {
+                self.growing += 1;
+            }
+        }
+        self.update_fill(parent);
+
+        let item_align = flex.and_then(|flex| flex.align).unwrap_or(self.settings.item_align);
         match self.settings.orientation {
             Orientation::Horizontal => {
-                match self.settings.item_align {
+                match item_align {
                     ItemAlignment::Fill => {
                         child.add(constraints![
                             align_top(parent),
@@ -227,7 +322,7 @@ impl LayoutContainer for LinearLayout {
                 }
             },
             Orientation::Vertical => {
-                match self.settings.item_align {
+                match item_align {
                     ItemAlignment::Fill => {
                         child.add(constraints![
                             align_left(parent),
@@ -295,6 +390,10 @@ impl LayoutContainer for LinearLayout {
                     self.last_widget = widget_data.prev;
                 }
             }
+            if widget_data.grows {
+                self.growing -= 1;
+            }
+            self.update_fill(parent);
         }
     }
 }
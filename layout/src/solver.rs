@@ -1,17 +1,107 @@
 use std::collections::{HashMap, HashSet, VecDeque};
+use std::error::Error;
+use std::fmt;
 use std::fmt::Write;
+use std::mem;
 
 use cassowary;
 use cassowary::strength::*;
-use cassowary::{Variable, Constraint, Expression};
+use cassowary::{Variable, Constraint, Expression, AddConstraintError};
 use cassowary::WeightedRelation::*;
 
 use super::{LayoutId, Layout, VarType, LayoutVars, EditVariable, Rect, Point, Size};
 
+/// A constraint involved in a `LayoutError`
+#[derive(Debug, Clone)]
+pub struct ConstraintInfo {
+    pub constraint: Constraint,
+    /// The constraint, with it's variables named after the layouts they belong to
+    pub description: String,
+    /// The names of the layouts the constraint belongs to
+    pub layouts: Vec<String>,
+}
+
+impl fmt::Display for ConstraintInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.description)
+    }
+}
+
+/// A constraint that couldn't be added to the solver, because it can't be satisfied
+/// together with the required constraints already in the solver
+#[derive(Debug, Clone)]
+pub struct UnsatisfiableConstraint {
+    pub constraint: ConstraintInfo,
+    /// The smallest set of required constraints in the solver that conflict with `constraint`,
+    /// removing any one of them would allow `constraint` to be added
+    pub conflicts: Vec<ConstraintInfo>,
+}
+
+impl fmt::Display for UnsatisfiableConstraint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unsatisfiable constraint {}", self.constraint)?;
+        for conflict in &self.conflicts {
+            write!(f, "\n    conflicts with {}", conflict)?;
+        }
+        Ok(())
+    }
+}
+
+/// An error in the layout, recorded by the solver, see `LimnSolver::take_errors`
+#[derive(Debug, Clone)]
+pub enum LayoutError {
+    /// A constraint couldn't be added to the solver
+    Unsatisfiable(UnsatisfiableConstraint),
+    /// A layout container can't place a child as requested, ie. a grid cell outside of the grid,
+    /// reported with `Layout::add_error`
+    Container {
+        /// The name of the layout that's misplaced
+        layout: String,
+        message: String,
+    },
+}
+
+impl fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LayoutError::Unsatisfiable(ref error) => write!(f, "{}", error),
+            LayoutError::Container { ref layout, ref message } => write!(f, "{}: {}", layout, message),
+        }
+    }
+}
+
+impl Error for LayoutError {
+    fn description(&self) -> &str {
+        match *self {
+            LayoutError::Unsatisfiable(_) => "unsatisfiable constraint",
+            LayoutError::Container { .. } => "layout container error",
+        }
+    }
+}
+
+/// Strength of the bounds of an isolated layout, as suggested to the solver of it's partition
+const BOUNDARY: f64 = REQUIRED - 1.0;
+
+/// The descendants of an isolated layout, solved with their own solver.
+/// The bounds of the isolated layout are solved in the parent partition,
+/// and suggested to this solver before it's changes are fetched.
+struct Partition {
+    solver: cassowary::Solver,
+    /// The partition the isolated layout belongs to, `None` for the main solver
+    parent: Option<LayoutId>,
+    depth: usize,
+}
+
 pub struct LimnSolver {
     pub solver: cassowary::Solver,
+    /// Panic on a layout error, ie. when a constraint can't be added, instead of recording a `LayoutError`
     pub strict: bool,
     layouts: LayoutManager,
+    errors: Vec<LayoutError>,
+    /// Partitions of isolated layouts, keyed by the id of the isolated layout
+    partitions: HashMap<LayoutId, Partition>,
+    /// The partition each constraint was added to, `None` for the main solver
+    constraint_partitions: HashMap<Constraint, Option<LayoutId>>,
 }
 
 impl LimnSolver {
@@ -20,14 +110,20 @@ impl LimnSolver {
             solver: cassowary::Solver::new(),
             strict: false,
             layouts: LayoutManager::new(),
+            errors: Vec::new(),
+            partitions: HashMap::new(),
+            constraint_partitions: HashMap::new(),
         }
     }
 
     pub fn update_layout(&mut self, layout: &mut Layout) {
 
         let registered = self.layouts.layouts.contains_key(&layout.id);
+        debug_assert!(!registered || !layout.isolated || self.layouts.layouts[&layout.id].isolated,
+                      "isolate() called on layout {} after it was added to the solver", self.layouts.layout_name(layout.id));
         if !registered {
             self.layouts.register_layout(layout);
+            self.register_partition(layout);
         }
         self.layouts.update_layout(layout);
 
@@ -58,15 +154,19 @@ impl LimnSolver {
             self.update_edit_var(&edit_var);
             self.layouts.update_edit_var(layout.id, edit_var);
         }
+        for error in layout.get_errors() {
+            self.add_error(error);
+        }
     }
     fn update_edit_var(&mut self, edit_var: &EditVariable) {
         let &EditVariable { var, val, strength } = edit_var;
-        if !self.solver.has_edit_variable(&var) {
+        let partition = self.var_partition(var);
+        if !self.solver_ref(partition).has_edit_variable(&var) {
             debug!("add edit_var {}", self.layouts.fmt_variable(var));
-            self.solver.add_edit_variable(var, strength).unwrap();
+            self.solver_mut(partition).add_edit_variable(var, strength).unwrap();
         }
         if val.is_finite() {
-            self.solver.suggest_value(var, val).unwrap();
+            self.solver_mut(partition).suggest_value(var, val).unwrap();
             debug!("suggest edit_var {} {}", self.layouts.fmt_variable(var), val);
         } else {
             debug!("invalid edit_var {} {}", self.layouts.fmt_variable(var), val);
@@ -74,23 +174,243 @@ impl LimnSolver {
     }
     fn add_constraint(&mut self, constraint: Constraint) {
         debug!("adding constraint {}", self.layouts.fmt_constraint(&constraint));
-        if self.solver.add_constraint(constraint.clone()).is_err() {
-            eprintln!("Failed to add constraint {}", self.layouts.fmt_constraint(&constraint));
-            self.debug_associated_constraints(&constraint);
-            if self.strict {
-                panic!("Solver unsatisfiable");
+        let partition = self.constraint_partition(&constraint);
+        self.add_to_partition(partition, constraint);
+    }
+    fn add_to_partition(&mut self, partition: Option<LayoutId>, constraint: Constraint) {
+        let result = self.solver_mut(partition).add_constraint(constraint.clone());
+        match result {
+            Ok(()) => {
+                self.constraint_partitions.insert(constraint, partition);
+            }
+            Err(AddConstraintError::UnsatisfiableConstraint) => {
+                let conflicts = self.find_conflicts(&constraint);
+                let error = UnsatisfiableConstraint {
+                    constraint: self.constraint_info(&constraint),
+                    conflicts: conflicts.iter().map(|conflict| self.constraint_info(conflict)).collect(),
+                };
+                self.add_error(LayoutError::Unsatisfiable(error));
+            }
+            Err(err) => panic!("Failed to add constraint {}: {:?}", self.layouts.fmt_constraint(&constraint), err),
+        }
+    }
+
+    /// Finds the required constraints that conflict with `constraint`, by removing each candidate
+    /// in turn, keeping those without which `constraint` can be satisfied.
+    /// A failed `add_constraint` can leave a cassowary solver in an inconsistent state,
+    /// so each attempt is made with a new solver.
+    fn find_conflicts(&self, constraint: &Constraint) -> Vec<Constraint> {
+        let mut candidates: Vec<Constraint> = self.associated_constraints(constraint).into_iter()
+            .filter(|candidate| candidate != constraint)
+            .collect();
+        candidates.sort_by_key(|candidate| self.layouts.fmt_constraint(candidate));
+        let mut index = 0;
+        while index < candidates.len() {
+            let satisfiable = {
+                let mut solver = cassowary::Solver::new();
+                let others = candidates.iter().enumerate().filter(|&(other, _)| other != index);
+                for (_, other) in others {
+                    solver.add_constraint(other.clone()).unwrap();
+                }
+                solver.add_constraint(constraint.clone()).is_ok()
+            };
+            if satisfiable {
+                // the candidate is part of the conflict
+                index += 1;
+            } else {
+                candidates.remove(index);
             }
         }
+        candidates
+    }
+
+    fn constraint_info(&self, constraint: &Constraint) -> ConstraintInfo {
+        let mut layouts: Vec<String> = Vec::new();
+        for id in self.layouts.dependent_layouts(constraint) {
+            let name = self.layouts.layout_name(id);
+            if !layouts.contains(&name) {
+                layouts.push(name);
+            }
+        }
+        ConstraintInfo {
+            constraint: constraint.clone(),
+            description: self.layouts.fmt_constraint(constraint),
+            layouts: layouts,
+        }
+    }
+
+    fn add_error(&mut self, error: LayoutError) {
+        if self.strict {
+            panic!("{}", error);
+        }
+        self.errors.push(error);
+    }
+
+    /// The errors recorded since the last call, from constraints that couldn't be added,
+    /// and errors reported by layout containers
+    pub fn take_errors(&mut self) -> Vec<LayoutError> {
+        mem::replace(&mut self.errors, Vec::new())
     }
 
     fn remove_constraint(&mut self, constraint: &Constraint) {
         debug!("removing constraint {}", self.layouts.fmt_constraint(constraint));
-        if self.solver.has_constraint(constraint) {
-            self.solver.remove_constraint(constraint).unwrap();
+        let partition = self.constraint_partitions.remove(constraint).unwrap_or(None);
+        let solver = self.solver_mut(partition);
+        if solver.has_constraint(constraint) {
+            solver.remove_constraint(constraint).unwrap();
+        }
+    }
+
+    /// Creates a partition for a newly registered layout if it's isolated,
+    /// and assigns it to the partition of it's parent
+    fn register_partition(&mut self, layout: &Layout) {
+        let partition = match layout.parent {
+            Some(parent) if self.partitions.contains_key(&parent) => Some(parent),
+            Some(parent) => self.layouts.layouts.get(&parent).and_then(|parent| parent.partition),
+            None => None,
+        };
+        self.layouts.layouts.get_mut(&layout.id).unwrap().partition = partition;
+        if layout.isolated {
+            let mut solver = cassowary::Solver::new();
+            for var in layout.vars.array().iter() {
+                solver.add_edit_variable(*var, BOUNDARY).unwrap();
+            }
+            let partition = Partition {
+                solver: solver,
+                parent: partition,
+                depth: self.partition_depth(partition) + 1,
+            };
+            self.partitions.insert(layout.id, partition);
+        }
+    }
+
+    fn partition_depth(&self, partition: Option<LayoutId>) -> usize {
+        match partition {
+            Some(id) => self.partitions[&id].depth,
+            None => 0,
+        }
+    }
+
+    fn solver_ref(&self, partition: Option<LayoutId>) -> &cassowary::Solver {
+        match partition {
+            Some(id) => &self.partitions[&id].solver,
+            None => &self.solver,
+        }
+    }
+
+    fn solver_mut(&mut self, partition: Option<LayoutId>) -> &mut cassowary::Solver {
+        match partition {
+            Some(id) => &mut self.partitions.get_mut(&id).unwrap().solver,
+            None => &mut self.solver,
+        }
+    }
+
+    /// The partition `var` is solved in.
+    /// The bounds of an isolated layout belong to the parent partition, it's associated variables
+    /// to the partition of the isolated layout.
+    fn var_partition(&self, var: Variable) -> Option<LayoutId> {
+        match self.layouts.var_ids.get(&var) {
+            Some(&id) => {
+                let layout = &self.layouts.layouts[&id];
+                if self.partitions.contains_key(&id) && layout.associated_vars.contains_key(&var) {
+                    Some(id)
+                } else {
+                    layout.partition
+                }
+            }
+            None => None,
+        }
+    }
+
+    /// The partitions a constraint on `var` can be added to.
+    /// The bounds of an isolated layout are also known to the partition of the isolated layout.
+    fn var_partitions(&self, var: Variable) -> Vec<Option<LayoutId>> {
+        let partition = self.var_partition(var);
+        match self.layouts.var_ids.get(&var) {
+            Some(&id) if partition != Some(id) && self.partitions.contains_key(&id) => vec![partition, Some(id)],
+            _ => vec![partition],
+        }
+    }
+
+    /// The partition `constraint` is added to, the outermost partition that knows all of it's variables.
+    /// If there is none, the constraint crosses the boundary of an isolated layout, and the innermost
+    /// partition involved is merged into it's parent, until there is one.
+    fn constraint_partition(&mut self, constraint: &Constraint) -> Option<LayoutId> {
+        loop {
+            let mut common: Option<Vec<Option<LayoutId>>> = None;
+            let mut involved = Vec::new();
+            for var in constraint_vars(constraint) {
+                let partitions = self.var_partitions(var);
+                involved.extend(partitions.iter().filter_map(|partition| *partition));
+                common = Some(match common {
+                    Some(common) => common.into_iter().filter(|partition| partitions.contains(partition)).collect(),
+                    None => partitions,
+                });
+            }
+            let common = common.unwrap_or_else(|| vec![None]);
+            if let Some(partition) = common.into_iter().min_by_key(|partition| self.partition_depth(*partition)) {
+                return partition;
+            }
+            let innermost = involved.into_iter().max_by_key(|id| self.partitions[id].depth).unwrap();
+            self.merge_partition(innermost);
+        }
+    }
+
+    /// Removes the partition of the isolated layout `id`, assigning it's layouts and
+    /// nested partitions to it's parent partition. Returns the removed partition.
+    fn remove_partition(&mut self, id: LayoutId) -> Partition {
+        let partition = self.partitions.remove(&id).unwrap();
+        for layout in self.layouts.layouts.values_mut() {
+            if layout.partition == Some(id) {
+                layout.partition = partition.parent;
+            }
+        }
+        for nested in self.partitions.values_mut() {
+            if nested.parent == Some(id) {
+                nested.parent = partition.parent;
+            }
+        }
+        let ids: Vec<LayoutId> = self.partitions.keys().cloned().collect();
+        for nested in ids {
+            let mut depth = 1;
+            let mut ancestor = self.partitions[&nested].parent;
+            while let Some(id) = ancestor {
+                depth += 1;
+                ancestor = self.partitions[&id].parent;
+            }
+            self.partitions.get_mut(&nested).unwrap().depth = depth;
+        }
+        partition
+    }
+
+    /// Merges the partition of the isolated layout `id` into it's parent partition,
+    /// moving it's constraints and edit variables to the parent solver
+    fn merge_partition(&mut self, id: LayoutId) {
+        debug!("merging isolated layout {}", self.layouts.layout_name(id));
+        let partition = self.remove_partition(id);
+        let constraints: Vec<Constraint> = self.constraint_partitions.iter()
+            .filter(|&(_, constraint_partition)| *constraint_partition == Some(id))
+            .map(|(constraint, _)| constraint.clone())
+            .collect();
+        let edit_vars: Vec<EditVariable> = self.layouts.layouts.values()
+            .flat_map(|layout| layout.edit_vars.values())
+            .filter(|edit_var| partition.solver.has_edit_variable(&edit_var.var))
+            .cloned()
+            .collect();
+        for edit_var in edit_vars {
+            self.update_edit_var(&edit_var);
+        }
+        for constraint in constraints {
+            self.add_to_partition(partition.parent, constraint);
         }
     }
 
     pub fn remove_layout(&mut self, id: LayoutId) {
+        if self.partitions.contains_key(&id) {
+            // the subtree of an isolated layout is removed along with it
+            self.remove_partition(id);
+            self.constraint_partitions.retain(|_, partition| *partition != Some(id));
+        }
         if let Some(layout) = self.layouts.layouts.remove(&id) {
             for constraint in layout.constraints {
                 self.remove_constraint(&constraint);
@@ -132,7 +452,7 @@ impl LimnSolver {
                 self.remove_constraint(&constraint);
             }
             for constraint in self.layouts.layouts[&id].constraints.clone() {
-                if !self.solver.has_constraint(&constraint) {
+                if !self.has_constraint(&constraint) {
                     let mut hidden = false;
                     for layout_id in self.layouts.dependent_layouts(&constraint) {
                         if layout_id != id && self.layouts.layout_hidden(layout_id) {
@@ -160,22 +480,96 @@ impl LimnSolver {
     }
 
     pub fn has_edit_variable(&mut self, v: &Variable) -> bool {
-        self.solver.has_edit_variable(v)
+        let partition = self.var_partition(*v);
+        self.solver_ref(partition).has_edit_variable(v)
     }
     pub fn has_constraint(&self, constraint: &Constraint) -> bool {
-        self.solver.has_constraint(constraint)
+        let partition = self.constraint_partitions.get(constraint).cloned().unwrap_or(None);
+        self.solver_ref(partition).has_constraint(constraint)
+    }
+
+    /// The current solved value of `var`
+    pub fn get_value(&self, var: Variable) -> f64 {
+        self.solver_ref(self.var_partition(var)).get_value(var)
     }
 
     pub fn fetch_changes(&mut self) -> Vec<(LayoutId, VarType, f64)> {
         let mut changes = Vec::new();
-        for &(var, val) in self.solver.fetch_changes() {
+        let mut bounds = HashMap::new();
+        self.fetch_partition_changes(None, &mut changes, &mut bounds);
+        'partitions: loop {
+            // outer partitions first, so the bounds of nested isolated layouts are known
+            let mut partitions: Vec<(usize, LayoutId)> = self.partitions.iter()
+                .map(|(id, partition)| (partition.depth, *id))
+                .collect();
+            partitions.sort();
+            for (_, id) in partitions {
+                if let Some(values) = bounds.remove(&id) {
+                    let solver = &mut self.partitions.get_mut(&id).unwrap().solver;
+                    for (var, val) in values {
+                        solver.suggest_value(var, val).unwrap();
+                    }
+                }
+                if self.partition_moves_bounds(id) {
+                    // the isolated layout depends on it's descendants, ie. it's sized to fit them,
+                    // so it's solved with it's parent, and the remaining partitions are checked again
+                    debug!("isolated layout {} depends on it's descendants", self.layouts.layout_name(id));
+                    let parent = self.partitions[&id].parent;
+                    let vars: Vec<(Variable, LayoutId)> = self.layouts.var_ids.iter()
+                        .filter(|&(_, layout_id)| self.layouts.layouts.get(layout_id).map_or(false, |layout| layout.partition == Some(id)))
+                        .map(|(var, layout_id)| (*var, *layout_id))
+                        .collect();
+                    self.merge_partition(id);
+                    self.fetch_partition_changes(parent, &mut changes, &mut bounds);
+                    // the values of the merged layouts may not have changed in the parent solver
+                    for (var, layout_id) in vars {
+                        let var_type = self.layouts.layouts[&layout_id].vars.var_type(var);
+                        changes.push((layout_id, var_type, self.solver_ref(parent).get_value(var)));
+                    }
+                    continue 'partitions;
+                }
+                self.fetch_partition_changes(Some(id), &mut changes, &mut bounds);
+            }
+            return changes;
+        }
+    }
+
+    /// Returns true if the constraints in the partition of the isolated layout `id` move it's bounds
+    /// away from where they were solved in the parent partition
+    fn partition_moves_bounds(&self, id: LayoutId) -> bool {
+        let partition = &self.partitions[&id];
+        let parent = self.solver_ref(partition.parent);
+        self.layouts.layouts[&id].vars.array().iter().any(|var| {
+            (partition.solver.get_value(*var) - parent.get_value(*var)).abs() > 0.001
+        })
+    }
+
+    /// Adds the changes of the solver of `partition` to `changes`, and the changed bounds
+    /// of isolated layouts to `bounds`
+    fn fetch_partition_changes(&mut self,
+                               partition: Option<LayoutId>,
+                               changes: &mut Vec<(LayoutId, VarType, f64)>,
+                               bounds: &mut HashMap<LayoutId, Vec<(Variable, f64)>>)
+    {
+        let solver_changes = self.solver_mut(partition).fetch_changes().to_vec();
+        for (var, val) in solver_changes {
             debug!("solver {} = {}", self.layouts.fmt_variable(var), val);
-            if let Some(layout_id) = self.layouts.var_ids.get(&var) {
+            if let Some(&layout_id) = self.layouts.var_ids.get(&var) {
                 let var_type = self.layouts.layouts[&layout_id].vars.var_type(var);
-                changes.push((*layout_id, var_type, val));
+                let is_bound = match var_type {
+                    VarType::Other => false,
+                    _ => true,
+                };
+                if is_bound && partition == Some(layout_id) {
+                    // already fetched from the parent partition
+                    continue;
+                }
+                if is_bound && self.partitions.contains_key(&layout_id) {
+                    bounds.entry(layout_id).or_insert_with(Vec::new).push((var, val));
+                }
+                changes.push((layout_id, var_type, val));
             }
         }
-        changes
     }
 
     pub fn debug_variables(&self) {
@@ -193,7 +587,7 @@ impl LimnSolver {
         while let Some(layout) = layouts.pop_front() {
             println!("{}", self.layouts.layout_name(layout).to_uppercase());
             for constraint in &self.layouts.layouts[&layout].constraints {
-                if !shown_constraints.contains(constraint) && self.solver.has_constraint(constraint) {
+                if !shown_constraints.contains(constraint) && self.has_constraint(constraint) {
                     self.debug_constraint(constraint);
                     shown_constraints.insert(constraint.clone());
                 }
@@ -210,7 +604,9 @@ impl LimnSolver {
         println!("{}", self.layouts.fmt_constraint(constraint));
     }
 
-    pub fn debug_associated_constraints(&self, constraint: &Constraint) {
+    /// The required constraints in the solver that share variables with `constraint`,
+    /// directly or through other required constraints
+    fn associated_constraints(&self, constraint: &Constraint) -> HashSet<Constraint> {
         let mut visited_constraints = HashSet::new();
         let mut new_constraints = HashSet::new();
         new_constraints.insert(constraint.clone());
@@ -225,7 +621,7 @@ impl LimnSolver {
                     for constraint in self.layouts.constraints_for(var) {
                         if constraint.strength() >= REQUIRED &&
                             !visited_constraints.contains(constraint) &&
-                                self.solver.has_constraint(&constraint) {
+                                self.has_constraint(&constraint) {
                             newer_constraints.insert(constraint.clone());
                         }
                     }
@@ -234,9 +630,87 @@ impl LimnSolver {
             }
             new_constraints = newer_constraints;
         }
-        for constraint in visited_constraints {
-            self.debug_constraint(&constraint);
-        }
+        visited_constraints
+    }
+
+    /// Descriptions of the constraints in the solver that involve the layout `id`
+    pub fn layout_constraints(&self, id: LayoutId) -> Vec<String> {
+        let mut constraints: Vec<String> = match self.layouts.layouts.get(&id) {
+            Some(layout) => layout.constraints.iter()
+                .filter(|constraint| self.has_constraint(constraint))
+                .map(|constraint| self.layouts.fmt_constraint(constraint))
+                .collect(),
+            None => Vec::new(),
+        };
+        constraints.sort();
+        constraints
+    }
+
+    /// Descriptions of the edit variables of the layout `id`, and their suggested values
+    pub fn layout_edit_vars(&self, id: LayoutId) -> Vec<String> {
+        let mut edit_vars: Vec<String> = match self.layouts.layouts.get(&id) {
+            Some(layout) => layout.edit_vars.values()
+                .map(|edit_var| self.layouts.fmt_edit_variable(edit_var))
+                .collect(),
+            None => Vec::new(),
+        };
+        edit_vars.sort();
+        edit_vars
+    }
+
+    /// Serializes the whole layout graph as JSON, for analysis outside of the app.
+    /// Contains every layout with it's solved bounds, children, associated variables
+    /// and edit variables, and every constraint with the layouts it involves.
+    pub fn layout_graph_json(&self) -> String {
+        let mut ids: Vec<LayoutId> = self.layouts.layouts.keys().cloned().collect();
+        ids.sort();
+        let mut constraints = HashSet::new();
+        let mut out = String::new();
+        write!(out, "{{\"root\":{},\"layouts\":[", self.layouts.root).unwrap();
+        for (index, id) in ids.iter().enumerate() {
+            let layout = &self.layouts.layouts[id];
+            constraints.extend(layout.constraints.iter().cloned());
+            if index > 0 {
+                out.push(',');
+            }
+            let get_val = |var| json_number(self.get_value(var));
+            write!(out, "{{\"id\":{},\"name\":{},\"hidden\":{},", id, json_string(&self.layouts.layout_name(*id)), layout.hidden).unwrap();
+            write!(out, "\"bounds\":{{\"left\":{},\"top\":{},\"width\":{},\"height\":{}}},",
+                get_val(layout.vars.left), get_val(layout.vars.top), get_val(layout.vars.width), get_val(layout.vars.height)).unwrap();
+            let children: Vec<String> = layout.children.iter().map(|child| child.to_string()).collect();
+            write!(out, "\"children\":[{}],", children.join(",")).unwrap();
+            let mut associated_vars: Vec<(&Variable, &String)> = layout.associated_vars.iter().collect();
+            associated_vars.sort_by_key(|&(_, name)| name.clone());
+            let associated_vars: Vec<String> = associated_vars.iter()
+                .map(|&(var, name)| format!("{{\"name\":{},\"value\":{}}}", json_string(name), get_val(*var)))
+                .collect();
+            write!(out, "\"associated_vars\":[{}],", associated_vars.join(",")).unwrap();
+            let mut edit_vars: Vec<&EditVariable> = layout.edit_vars.values().collect();
+            edit_vars.sort_by_key(|edit_var| self.layouts.fmt_variable(edit_var.var));
+            let edit_vars: Vec<String> = edit_vars.iter()
+                .map(|edit_var| format!("{{\"var\":{},\"value\":{},\"strength\":{}}}",
+                    json_string(&self.layouts.fmt_variable(edit_var.var)), json_number(edit_var.val), edit_var.strength))
+                .collect();
+            write!(out, "\"edit_vars\":[{}]}}", edit_vars.join(",")).unwrap();
+        }
+        out.push_str("],\"constraints\":[");
+        let mut constraints: Vec<(String, Constraint)> = constraints.into_iter()
+            .map(|constraint| (self.layouts.fmt_constraint(&constraint), constraint))
+            .collect();
+        constraints.sort_by(|a, b| a.0.cmp(&b.0));
+        for (index, &(ref description, ref constraint)) in constraints.iter().enumerate() {
+            if index > 0 {
+                out.push(',');
+            }
+            let mut layouts = self.layouts.dependent_layouts(constraint);
+            layouts.sort();
+            layouts.dedup();
+            let layouts: Vec<String> = layouts.iter().map(|id| id.to_string()).collect();
+            write!(out, "{{\"description\":{},\"strength\":{},\"active\":{},\"layouts\":[{}]}}",
+                json_string(description), constraint.strength(), self.has_constraint(constraint), layouts.join(",")).unwrap();
+        }
+        out.push_str("]}");
+        out
     }
 
     pub fn debug_layouts(&self) {
@@ -251,7 +725,7 @@ impl LimnSolver {
 
     pub fn debug_layout(&self, id: LayoutId) {
         let bounds = {
-            let get_val = |var| self.solver.get_value(var) as f32;
+            let get_val = |var| self.get_value(var) as f32;
             let vars = &self.layouts.layouts[&id].vars;
             let origin = Point::new(get_val(vars.left), get_val(vars.top));
             let size = Size::new(get_val(vars.width), get_val(vars.height));
@@ -274,6 +748,10 @@ struct LayoutInternal {
     children: Vec<LayoutId>,
     hidden: bool,
     hidden_constraints: Vec<Constraint>,
+    /// `Layout::isolate` was called before the layout was registered
+    isolated: bool,
+    /// The isolated layout whose partition this layout is solved in, `None` for the main solver
+    partition: Option<LayoutId>,
 }
 pub struct LayoutManager {
     root: LayoutId,
@@ -313,6 +791,8 @@ impl LayoutManager {
             children: layout.children.clone(),
             hidden: false,
             hidden_constraints: Vec::new(),
+            isolated: layout.isolated,
+            partition: None,
         };
         self.layouts.insert(id, layout);
     }
@@ -473,6 +953,35 @@ impl LayoutManager {
     }
 }
 
+/// Quotes and escapes `string` as a JSON string
+fn json_string(string: &str) -> String {
+    let mut out = String::with_capacity(string.len() + 2);
+    out.push('"');
+    for char in string.chars() {
+        match char {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            char if (char as u32) < 0x20 => write!(out, "\\u{:04x}", char as u32).unwrap(),
+            char => out.push(char),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Formats `number` as a JSON number, or null if it's not finite
+fn json_number(number: f64) -> String {
+    if number.is_finite() {
+        // avoids formatting negative zero as -0
+        (number + 0.0).to_string()
+    } else {
+        "null".to_owned()
+    }
+}
+
 /// Creates a printable string value for a given strength
 fn strength_desc(strength: f64) -> &'static str {
     if strength < WEAK { "WEAK-" }
@@ -0,0 +1,94 @@
+use cassowary::strength::*;
+use cassowary::WeightedRelation::*;
+use cassowary::Expression;
+
+use super::{LayoutVars, Layout, LayoutContainer};
+use super::constraint::*;
+
+/// A point on the bounds of a layout, used to align a child with the same point on it's parent
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Anchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl Anchor {
+    /// The x and y coordinates of the anchor point on `vars`, with the bounds inset by `inset`
+    pub fn point(&self, vars: &LayoutVars, inset: f32) -> (Expression, Expression) {
+        let x = match *self {
+            Anchor::TopLeft | Anchor::Left | Anchor::BottomLeft => vars.left + inset,
+            Anchor::Top | Anchor::Center | Anchor::Bottom => vars.left + vars.width * 0.5,
+            Anchor::TopRight | Anchor::Right | Anchor::BottomRight => vars.right - inset,
+        };
+        let y = match *self {
+            Anchor::TopLeft | Anchor::Top | Anchor::TopRight => vars.top + inset,
+            Anchor::Left | Anchor::Center | Anchor::Right => vars.top + vars.height * 0.5,
+            Anchor::BottomLeft | Anchor::Bottom | Anchor::BottomRight => vars.bottom - inset,
+        };
+        (x, y)
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct StackLayoutSettings {
+    /// Where children are placed, if they are smaller than the layout.
+    /// Can be overridden for a child by setting an `Anchor` with `Layout::set_item_props`
+    pub anchor: Anchor,
+    /// Space between the edges of the layout and it's children
+    pub padding: f32,
+}
+
+impl StackLayoutSettings {
+    pub fn new() -> Self {
+        StackLayoutSettings {
+            anchor: Anchor::Center,
+            padding: 0.0,
+        }
+    }
+}
+
+impl Default for StackLayoutSettings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Layers children on top of each other, each child is bound by the layout,
+/// and fills it unless it's size is constrained, in which case it's aligned to it's anchor.
+/// Children added later are drawn on top of earlier children, unless their z-index says otherwise.
+#[derive(Debug, Copy, Clone)]
+pub struct StackLayout {
+    settings: StackLayoutSettings,
+}
+
+impl StackLayout {
+    pub fn new(_: &mut Layout, settings: StackLayoutSettings) -> Self {
+        StackLayout {
+            settings: settings,
+        }
+    }
+}
+
+impl LayoutContainer for StackLayout {
+    fn add_child(&mut self, parent: &mut Layout, child: &mut Layout) {
+        let anchor = child.item_props::<Anchor>().cloned().unwrap_or(self.settings.anchor);
+        let padding = self.settings.padding;
+        let (parent_x, parent_y) = anchor.point(&parent.vars, padding);
+        let (child_x, child_y) = anchor.point(&child.vars, 0.0);
+        child.add(constraints![
+            bound_by(&parent).padding(padding),
+            match_layout(&parent).padding(padding).strength(WEAK),
+        ]);
+        child.add(vec![
+            child_x | EQ(REQUIRED) | parent_x,
+            child_y | EQ(REQUIRED) | parent_y,
+        ]);
+    }
+}
@@ -0,0 +1,98 @@
+//! Benchmarks comparing layouts solved with a single solver to layouts with isolated subtrees.
+//! Ignored by default, run with `cargo test --release --test benchmarks -- --ignored --nocapture`
+
+extern crate cassowary;
+#[macro_use]
+extern crate limn_layout as layout;
+
+mod common;
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use layout::{LayoutId, Rect};
+use layout::constraint::*;
+use layout::linear_layout::{LinearLayout, LinearLayoutSettings, Orientation, ItemAlignment, Spacing};
+
+use common::{TestLayout, SharedLayout};
+
+const ROWS: usize = 150;
+const CELLS: usize = 4;
+const EDITS: usize = 30;
+
+struct ListTimes {
+    build: Duration,
+    edits: Duration,
+    rects: HashMap<LayoutId, Rect>,
+}
+
+fn linear_layout(layout: &mut SharedLayout, orientation: Orientation) {
+    let mut settings = LinearLayoutSettings::new(orientation);
+    settings.item_align = ItemAlignment::Fill;
+    settings.spacing = Spacing::Start;
+    let linear_layout = LinearLayout::new(&mut *layout, settings);
+    layout.set_container(linear_layout);
+}
+
+/// Builds a list of rows of cells, then resizes a cell in some of the rows, one at a time
+fn list(isolated: bool) -> ListTimes {
+    let mut layout = TestLayout::new();
+    let start = Instant::now();
+
+    let mut list = layout.new_widget("list");
+    list.add(constraints![
+        top_left(layout::Point::new(0.0, 0.0)),
+        width(400.0),
+    ]);
+    linear_layout(&mut list, Orientation::Vertical);
+    let mut cells = Vec::new();
+    for index in 0..ROWS {
+        let mut row = layout.new_widget(&format!("row_{}", index));
+        if isolated {
+            row.isolate();
+        }
+        row.add(height(30.0));
+        linear_layout(&mut row, Orientation::Horizontal);
+        list.add_child(&mut *row);
+        for cell_index in 0..CELLS {
+            let mut cell = layout.new_widget(&format!("cell_{}_{}", index, cell_index));
+            cell.edit_width().set(40.0);
+            row.add_child(&mut *cell);
+            if cell_index == 0 {
+                cells.push(cell);
+            }
+        }
+    }
+    layout.add_root(list);
+    layout.update();
+    let build = start.elapsed();
+
+    let start = Instant::now();
+    for edit in 0..EDITS {
+        cells[edit * ROWS / EDITS].edit_width().set(60.0);
+        layout.update();
+    }
+    let edits = start.elapsed();
+
+    ListTimes {
+        build: build,
+        edits: edits,
+        rects: layout.layout_rects,
+    }
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs() as f64 * 1000.0 + duration.subsec_nanos() as f64 / 1_000_000.0
+}
+
+#[test]
+#[ignore]
+fn isolated_list() {
+    let shared = list(false);
+    let isolated = list(true);
+    assert!(shared.rects == isolated.rects);
+
+    println!("{} rows of {} cells, {} edits", ROWS, CELLS, EDITS);
+    println!("shared solver:  build {:.1}ms, edits {:.1}ms", millis(shared.build), millis(shared.edits));
+    println!("isolated rows:  build {:.1}ms, edits {:.1}ms", millis(isolated.build), millis(isolated.edits));
+}
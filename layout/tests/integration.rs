@@ -4,17 +4,18 @@ extern crate limn_layout as layout;
 #[macro_use]
 extern crate maplit;
 
-use std::rc::Rc;
-use std::cell::RefCell;
-use std::collections::HashMap;
-use std::ops::{Deref, DerefMut};
+mod common;
+
+use std::ops::DerefMut;
 
 use cassowary::strength::*;
 
-use layout::{LimnSolver, LayoutId, Layout, VarType, LayoutRef, LayoutVars};
-use layout::{Size, Point, Rect};
+use layout::LayoutRef;
+use layout::{Size, Point, Rect, LayoutError};
 use layout::constraint::*;
 
+use common::{TestLayout, SharedLayout};
+
 #[test]
 fn one_widget() {
     let mut layout = TestLayout::new();
@@ -120,6 +121,100 @@ fn grid_layout() {
     });
 }
 
+#[test]
+fn grid_layout_tracks() {
+    use layout::grid_layout::{GridLayout, GridLayoutSettings, GridCell, Track};
+    let mut layout = TestLayout::new();
+
+    let mut grid = layout.new_widget("grid");
+    grid.add(constraints![
+        top_left(Point::new(0.0, 0.0)),
+        size(Size::new(300.0, 200.0)),
+    ]);
+    let grid_layout = GridLayout::with_settings(&mut grid, GridLayoutSettings {
+        columns: vec![Track::Fixed(50.0), Track::Fraction(1.0), Track::Fraction(2.0)],
+        rows: vec![Track::Auto],
+        implicit_row: Track::Fraction(1.0),
+        column_gap: 10.0,
+        row_gap: 10.0,
+    });
+    grid.set_container(grid_layout);
+
+    let mut header = layout.new_widget("header");
+    header.add(height(30.0));
+    header.set_item_props(GridCell::new(0, 0).span(1, 3));
+    grid.add_child(header.deref_mut());
+    let mut label = layout.new_widget("label");
+    grid.add_child(label.deref_mut());
+    let mut field = layout.new_widget("field");
+    field.set_item_props(GridCell::new(1, 1).span(2, 2));
+    grid.add_child(field.deref_mut());
+    let mut footer = layout.new_widget("footer");
+    grid.add_child(footer.deref_mut());
+
+    layout.add_root(grid.clone());
+    layout.update();
+
+    assert!(layout.layout_rects == hashmap!{
+        grid.id => Rect::new(Point::new(0.0, 0.0), Size::new(300.0, 200.0)),
+        header.id => Rect::new(Point::new(0.0, 0.0), Size::new(300.0, 30.0)),
+        label.id => Rect::new(Point::new(0.0, 40.0), Size::new(50.0, 75.0)),
+        field.id => Rect::new(Point::new(60.0, 40.0), Size::new(240.0, 160.0)),
+        footer.id => Rect::new(Point::new(0.0, 125.0), Size::new(50.0, 75.0)),
+    });
+}
+
+#[test]
+fn grid_cell_errors() {
+    use layout::grid_layout::{GridLayout, GridCell};
+    let mut layout = TestLayout::new();
+    layout.solver.strict = false;
+
+    let mut grid = layout.new_widget("grid");
+    grid.add(constraints![
+        top_left(Point::new(0.0, 0.0)),
+        size(Size::new(200.0, 200.0)),
+    ]);
+    let grid_layout = GridLayout::new(&mut grid, 2);
+    grid.set_container(grid_layout);
+
+    let mut first = layout.new_widget("first");
+    first.set_item_props(GridCell::new(0, 0));
+    grid.add_child(first.deref_mut());
+    let mut overlapping = layout.new_widget("overlapping");
+    overlapping.set_item_props(GridCell::new(0, 0).span(1, 2));
+    grid.add_child(overlapping.deref_mut());
+    let mut outside = layout.new_widget("outside");
+    outside.set_item_props(GridCell::new(0, 2));
+    grid.add_child(outside.deref_mut());
+    let mut empty = layout.new_widget("empty");
+    empty.set_item_props(GridCell::new(2, 0).span(0, 1));
+    grid.add_child(empty.deref_mut());
+
+    layout.add_root(grid.clone());
+    layout.update();
+
+    let mut errors: Vec<String> = layout.solver.take_errors().iter().map(|error| match *error {
+        LayoutError::Container { .. } => error.to_string(),
+        ref error => panic!("unexpected layout error {}", error),
+    }).collect();
+    errors.sort();
+    assert_eq!(errors, vec![
+        "empty: invalid grid cell GridCell { row: 2, column: 0, row_span: 0, column_span: 1 }, placed in the next free cell instead",
+        "outside: invalid grid cell GridCell { row: 0, column: 2, row_span: 1, column_span: 1 }, placed in the next free cell instead",
+        "overlapping: grid cell GridCell { row: 0, column: 0, row_span: 1, column_span: 2 } overlaps another cell",
+    ]);
+
+    // invalid cells are placed in the next free cell
+    assert!(layout.layout_rects == hashmap!{
+        grid.id => Rect::new(Point::new(0.0, 0.0), Size::new(200.0, 200.0)),
+        first.id => Rect::new(Point::new(0.0, 0.0), Size::new(100.0, 100.0)),
+        overlapping.id => Rect::new(Point::new(0.0, 0.0), Size::new(200.0, 100.0)),
+        outside.id => Rect::new(Point::new(0.0, 100.0), Size::new(100.0, 100.0)),
+        empty.id => Rect::new(Point::new(100.0, 100.0), Size::new(100.0, 100.0)),
+    });
+}
+
 #[test]
 fn edit_var() {
     let mut layout = TestLayout::new();
@@ -381,117 +476,473 @@ fn linear_layout_remove() {
     }));
 }
 
-#[derive(Clone)]
-struct SharedLayout(Rc<RefCell<Layout>>);
-impl SharedLayout {
-    fn new(layout: Layout) -> Self {
-        SharedLayout(Rc::new(RefCell::new(layout)))
-    }
-}
-impl LayoutRef for SharedLayout {
-    fn layout_ref(&self) -> LayoutVars {
-        self.0.borrow().vars.clone()
-    }
+#[test]
+fn replace_container() {
+    use layout::linear_layout::{LinearLayout, LinearLayoutSettings, Orientation, ItemAlignment};
+
+    let mut layout = TestLayout::new();
+
+    let mut root = layout.new_widget("root");
+    let mut item_1 = layout.new_widget("item_1");
+    let mut item_2 = layout.new_widget("item_2");
+
+    root.add(constraints![
+        top_left(Point::new(0.0, 0.0)),
+        size(Size::new(100.0, 100.0))
+    ]);
+    item_1.add(size(Size::new(20.0, 10.0)));
+    item_2.add(size(Size::new(20.0, 10.0)));
+    let linear_layout = |orientation| {
+        let mut settings = LinearLayoutSettings::new(orientation);
+        settings.item_align = match orientation {
+            Orientation::Horizontal => ItemAlignment::Top,
+            Orientation::Vertical => ItemAlignment::Left,
+        };
+        settings.padding = 10.0;
+        settings
+    };
+    root.replace_container(|root| LinearLayout::new(root, linear_layout(Orientation::Horizontal)));
+    root.add_child(&mut *item_1);
+    root.add_child(&mut *item_2);
+
+    layout.add_root(root.clone());
+    layout.update();
+    assert!(layout.match_layouts(hashmap!{
+        item_1.id => Rect::new(Point::new(0.0, 0.0), Size::new(20.0, 10.0)),
+        item_2.id => Rect::new(Point::new(30.0, 0.0), Size::new(20.0, 10.0)),
+    }));
+
+    // the constraints of the horizontal layout are removed, and the children arranged vertically
+    root.replace_container(|root| LinearLayout::new(root, linear_layout(Orientation::Vertical)));
+    root.add_child(&mut *item_1);
+    root.add_child(&mut *item_2);
+    layout.update();
+    assert!(layout.match_layouts(hashmap!{
+        item_1.id => Rect::new(Point::new(0.0, 0.0), Size::new(20.0, 10.0)),
+        item_2.id => Rect::new(Point::new(0.0, 20.0), Size::new(20.0, 10.0)),
+    }));
 }
-impl <'a> Deref for SharedLayout {
-    type Target = Layout;
-    #[inline]
-    fn deref(&self) -> &Layout {
-        unsafe {self.0.as_ptr().as_ref().unwrap()}
-    }
+
+#[test]
+fn linear_layout_flex_grow() {
+    use layout::linear_layout::{LinearLayout, LinearLayoutSettings, Orientation, ItemAlignment, FlexItem};
+
+    let mut layout = TestLayout::new();
+
+    let mut root = layout.new_widget("root");
+    let mut item_1 = layout.new_widget("item_1");
+    let mut item_2 = layout.new_widget("item_2");
+    let mut item_3 = layout.new_widget("item_3");
+
+    root.add(constraints![
+        top_left(Point::new(0.0, 0.0)),
+        size(Size::new(300.0, 10.0))
+    ]);
+    item_1.set_item_props(FlexItem { grow: 1.0, ..FlexItem::default() });
+    item_2.set_item_props(FlexItem { grow: 2.0, ..FlexItem::default() });
+    item_3.add(size(Size::new(30.0, 5.0)));
+    item_3.set_item_props(FlexItem { align: Some(ItemAlignment::Bottom), ..FlexItem::default() });
+    let mut settings = LinearLayoutSettings::new(Orientation::Horizontal);
+    settings.item_align = ItemAlignment::Fill;
+    let linear_layout = LinearLayout::new(&mut *root, settings);
+    root.set_container(linear_layout);
+
+    root.add_child(&mut *item_1);
+    root.add_child(&mut *item_2);
+    root.add_child(&mut *item_3);
+
+    layout.add_root(root.clone());
+    layout.update();
+    assert!(layout.match_layouts(hashmap!{
+        root.id => Rect::new(Point::new(0.0, 0.0), Size::new(300.0, 10.0)),
+        item_1.id => Rect::new(Point::new(0.0, 0.0), Size::new(90.0, 10.0)),
+        item_2.id => Rect::new(Point::new(90.0, 0.0), Size::new(180.0, 10.0)),
+        item_3.id => Rect::new(Point::new(270.0, 5.0), Size::new(30.0, 5.0)),
+    }));
 }
-impl <'a> DerefMut for SharedLayout
-{   #[inline]
-    fn deref_mut(&mut self) -> &mut Layout {
-        unsafe {self.0.as_ptr().as_mut().unwrap()}
-    }
+
+#[test]
+fn linear_layout_flex_basis() {
+    use layout::linear_layout::{LinearLayout, LinearLayoutSettings, Orientation, ItemAlignment, FlexItem};
+
+    let mut layout = TestLayout::new();
+
+    let mut root = layout.new_widget("root");
+    let mut item_1 = layout.new_widget("item_1");
+    let mut item_2 = layout.new_widget("item_2");
+
+    root.add(constraints![
+        top_left(Point::new(0.0, 0.0)),
+        size(Size::new(200.0, 10.0))
+    ]);
+    item_1.set_item_props(FlexItem { grow: 1.0, basis: Some(50.0), ..FlexItem::default() });
+    item_2.set_item_props(FlexItem { basis: Some(30.0), ..FlexItem::default() });
+    let mut settings = LinearLayoutSettings::new(Orientation::Horizontal);
+    settings.item_align = ItemAlignment::Fill;
+    settings.padding = 10.0;
+    let linear_layout = LinearLayout::new(&mut *root, settings);
+    root.set_container(linear_layout);
+
+    root.add_child(&mut *item_1);
+    root.add_child(&mut *item_2);
+
+    layout.add_root(root.clone());
+    layout.update();
+    assert!(layout.match_layouts(hashmap!{
+        root.id => Rect::new(Point::new(0.0, 0.0), Size::new(200.0, 10.0)),
+        item_1.id => Rect::new(Point::new(0.0, 0.0), Size::new(160.0, 10.0)),
+        item_2.id => Rect::new(Point::new(170.0, 0.0), Size::new(30.0, 10.0)),
+    }));
 }
 
-// code below is used to create a test harness for creating layouts outside of the widget graph
-struct TestLayout {
-    id_gen: IdGen,
-    solver: LimnSolver,
-    layout_rects: HashMap<LayoutId, Rect>,
-    layouts: HashMap<LayoutId, SharedLayout>,
-    roots: Vec<SharedLayout>,
+#[test]
+fn linear_layout_flex_shrink() {
+    use layout::linear_layout::{LinearLayout, LinearLayoutSettings, Orientation, ItemAlignment, FlexItem};
+
+    let mut layout = TestLayout::new();
+
+    let mut root = layout.new_widget("root");
+    let mut item_1 = layout.new_widget("item_1");
+    let mut item_2 = layout.new_widget("item_2");
+    let mut item_3 = layout.new_widget("item_3");
+
+    root.add(constraints![
+        top_left(Point::new(0.0, 0.0)),
+        size(Size::new(10.0, 100.0))
+    ]);
+    item_1.set_item_props(FlexItem { shrink: 0.0, basis: Some(50.0), ..FlexItem::default() });
+    item_2.set_item_props(FlexItem { basis: Some(50.0), ..FlexItem::default() });
+    item_3.set_item_props(FlexItem { basis: Some(50.0), ..FlexItem::default() });
+    let mut settings = LinearLayoutSettings::new(Orientation::Vertical);
+    settings.item_align = ItemAlignment::Fill;
+    let linear_layout = LinearLayout::new(&mut *root, settings);
+    root.set_container(linear_layout);
+
+    root.add_child(&mut *item_1);
+    root.add_child(&mut *item_2);
+    root.add_child(&mut *item_3);
+
+    layout.add_root(root.clone());
+    layout.update();
+    assert!(layout.match_layouts(hashmap!{
+        root.id => Rect::new(Point::new(0.0, 0.0), Size::new(10.0, 100.0)),
+        item_1.id => Rect::new(Point::new(0.0, 0.0), Size::new(10.0, 50.0)),
+        item_2.id => Rect::new(Point::new(0.0, 50.0), Size::new(10.0, 25.0)),
+        item_3.id => Rect::new(Point::new(0.0, 75.0), Size::new(10.0, 25.0)),
+    }));
 }
-impl TestLayout {
-    fn new() -> Self {
-        let mut solver = LimnSolver::new();
-        solver.strict = true;
-        TestLayout {
-            id_gen: IdGen::new(),
-            solver: solver,
-            layout_rects: HashMap::new(),
-            layouts: HashMap::new(),
-            roots: Vec::new(),
-        }
-    }
-    fn new_widget(&mut self, name: &str) -> SharedLayout {
-        let id = self.id_gen.next();
-        let layout = Layout::new(id, Some(name.to_owned()));
-        let layout = SharedLayout::new(layout);
-        self.layouts.insert(id, layout.clone());
-        layout
-    }
-    fn add_root(&mut self, layout: SharedLayout) {
-        self.roots.push(layout);
-    }
-    fn update_layout(&mut self, mut layout: SharedLayout) {
-        self.solver.update_layout(layout.deref_mut());
-        for child in layout.get_children() {
-            let layout = self.layouts[child].clone();
-            self.update_layout(layout);
-        }
-    }
-    fn update(&mut self) {
-        for layout in self.roots.clone() {
-            self.update_layout(layout);
-        }
-        for (id, var, value) in self.solver.fetch_changes() {
-            let rect = self.layout_rects.entry(id).or_insert(Rect::zero());
-            match var {
-                VarType::Left => rect.origin.x = value as f32,
-                VarType::Top => rect.origin.y = value as f32,
-                VarType::Width => rect.size.width = value as f32,
-                VarType::Height => rect.size.height = value as f32,
-                _ => (),
-            }
+
+#[test]
+fn flow_layout() {
+    use layout::flow_layout::{FlowLayout, FlowLayoutSettings};
+
+    let mut layout = TestLayout::new();
+
+    let mut root = layout.new_widget("root");
+    let mut items: Vec<SharedLayout> = (0..5).map(|index| layout.new_widget(&format!("item_{}", index))).collect();
+
+    root.add(top_left(Point::new(0.0, 0.0)));
+    root.edit_width().set(100.0);
+    for (index, item) in items.iter_mut().enumerate() {
+        if index == 3 {
+            item.add(size(Size::new(60.0, 30.0)));
+        } else {
+            item.add(size(Size::new(30.0, 20.0)));
         }
     }
-    fn match_layouts(&self, layouts: HashMap<LayoutId, Rect>) -> bool {
-        for (match_layout_id, match_layout_rect) in layouts {
-            let layout_rect = self.layout_rects[&match_layout_id];
-            if layout_rect != match_layout_rect {
-                self.solver.debug_layouts();
-                return false;
-            }
-        }
-        true
+    let mut settings = FlowLayoutSettings::new();
+    settings.spacing = 5.0;
+    settings.row_spacing = 10.0;
+    let flow_layout = FlowLayout::new(&mut *root, settings);
+    root.set_container(flow_layout);
+    for item in &mut items {
+        root.add_child(&mut *item);
     }
+
+    layout.add_root(root.clone());
+    layout.update();
+    assert!(layout.match_layouts(hashmap!{
+        root.id => Rect::new(Point::new(0.0, 0.0), Size::new(100.0, 60.0)),
+        items[0].id => Rect::new(Point::new(0.0, 0.0), Size::new(30.0, 20.0)),
+        items[1].id => Rect::new(Point::new(35.0, 0.0), Size::new(30.0, 20.0)),
+        items[2].id => Rect::new(Point::new(70.0, 0.0), Size::new(30.0, 20.0)),
+        items[3].id => Rect::new(Point::new(0.0, 30.0), Size::new(60.0, 30.0)),
+        items[4].id => Rect::new(Point::new(65.0, 30.0), Size::new(30.0, 20.0)),
+    }));
+
+    // narrowing the layout reflows the items
+    root.edit_width().set(70.0);
+    layout.update();
+    assert!(layout.match_layouts(hashmap!{
+        root.id => Rect::new(Point::new(0.0, 0.0), Size::new(70.0, 120.0)),
+        items[0].id => Rect::new(Point::new(0.0, 0.0), Size::new(30.0, 20.0)),
+        items[1].id => Rect::new(Point::new(35.0, 0.0), Size::new(30.0, 20.0)),
+        items[2].id => Rect::new(Point::new(0.0, 30.0), Size::new(30.0, 20.0)),
+        items[3].id => Rect::new(Point::new(0.0, 60.0), Size::new(60.0, 30.0)),
+        items[4].id => Rect::new(Point::new(0.0, 100.0), Size::new(30.0, 20.0)),
+    }));
+}
+
+#[test]
+fn stack_layout() {
+    use layout::stack_layout::{StackLayout, StackLayoutSettings, Anchor};
+
+    let mut layout = TestLayout::new();
+
+    let mut root = layout.new_widget("root");
+    root.add(constraints![
+        top_left(Point::new(0.0, 0.0)),
+        size(Size::new(100.0, 100.0)),
+    ]);
+    let mut settings = StackLayoutSettings::new();
+    settings.padding = 10.0;
+    let stack_layout = StackLayout::new(&mut *root, settings);
+    root.set_container(stack_layout);
+
+    let mut background = layout.new_widget("background");
+    root.add_child(&mut *background);
+    let mut overlay = layout.new_widget("overlay");
+    overlay.add(size(Size::new(20.0, 20.0)));
+    root.add_child(&mut *overlay);
+    let mut badge = layout.new_widget("badge");
+    badge.add(size(Size::new(10.0, 10.0)));
+    badge.set_item_props(Anchor::TopRight);
+    root.add_child(&mut *badge);
+
+    layout.add_root(root.clone());
+    layout.update();
+    assert!(layout.match_layouts(hashmap!{
+        root.id => Rect::new(Point::new(0.0, 0.0), Size::new(100.0, 100.0)),
+        background.id => Rect::new(Point::new(10.0, 10.0), Size::new(80.0, 80.0)),
+        overlay.id => Rect::new(Point::new(40.0, 40.0), Size::new(20.0, 20.0)),
+        badge.id => Rect::new(Point::new(80.0, 10.0), Size::new(10.0, 10.0)),
+    }));
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
-struct IdGen {
-    id: usize,
+#[test]
+fn absolute_layout() {
+    use layout::stack_layout::Anchor;
+    use layout::absolute_layout::{AbsoluteLayout, Position};
+
+    let mut layout = TestLayout::new();
+
+    let mut root = layout.new_widget("root");
+    root.add(constraints![
+        top_left(Point::new(0.0, 0.0)),
+        size(Size::new(200.0, 100.0)),
+    ]);
+    let absolute_layout = AbsoluteLayout::new(&mut *root);
+    root.set_container(absolute_layout);
+
+    let mut node = layout.new_widget("node");
+    node.add(size(Size::new(30.0, 20.0)));
+    node.set_item_props(Position::new(50.0, 25.0));
+    root.add_child(&mut *node);
+    let mut corner = layout.new_widget("corner");
+    corner.add(size(Size::new(20.0, 20.0)));
+    corner.set_item_props(Position::new(-5.0, -5.0).anchor(Anchor::BottomRight));
+    root.add_child(&mut *corner);
+
+    layout.add_root(root.clone());
+    layout.update();
+    assert!(layout.match_layouts(hashmap!{
+        node.id => Rect::new(Point::new(50.0, 25.0), Size::new(30.0, 20.0)),
+        corner.id => Rect::new(Point::new(175.0, 75.0), Size::new(20.0, 20.0)),
+    }));
+
+    // children can be moved after they're added
+    node.set_offset(120.0, 10.0);
+    layout.update();
+    assert!(layout.match_layouts(hashmap!{
+        node.id => Rect::new(Point::new(120.0, 10.0), Size::new(30.0, 20.0)),
+        corner.id => Rect::new(Point::new(175.0, 75.0), Size::new(20.0, 20.0)),
+    }));
 }
 
-impl Default for IdGen {
-    fn default() -> Self {
-        IdGen {
-            id: 0,
+#[test]
+fn layout_error() {
+    let mut layout = TestLayout::new();
+    layout.solver.strict = false;
+
+    let mut root = layout.new_widget("root");
+    root.add(constraints![
+        top_left(Point::new(0.0, 0.0)),
+        size(Size::new(100.0, 100.0)),
+    ]);
+    let mut child = layout.new_widget("child");
+    child.add(width(200.0));
+    root.add_child(&mut *child);
+    layout.add_root(root.clone());
+    layout.update();
+
+    // which constraint fails depends on the order they're added in, but the same constraints are involved
+    let errors = layout.solver.take_errors();
+    let error = match errors[0] {
+        LayoutError::Unsatisfiable(ref error) => error,
+        ref error => panic!("unexpected layout error {}", error),
+    };
+    let mut involved: Vec<String> = error.conflicts.iter().map(|info| info.description.clone()).collect();
+    involved.push(error.constraint.description.clone());
+    involved.sort();
+    assert_eq!(involved, vec![
+        "REQD  child.left >= root.left",
+        "REQD  child.right == child.left + child.width",
+        "REQD  child.width == 200",
+        "REQD  root.right == root.left + root.width",
+        "REQD  root.right >= child.right",
+        "REQD  root.width == 100",
+    ]);
+    assert!(error.to_string().starts_with(&format!("unsatisfiable constraint {}\n    conflicts with ", error.constraint.description)));
+    assert!(layout.solver.take_errors().is_empty());
+}
+
+#[test]
+fn layout_graph_json() {
+    let mut layout = TestLayout::new();
+
+    let mut root = layout.new_widget("root");
+    root.add(constraints![
+        top_left(Point::new(0.0, 0.0)),
+        size(Size::new(100.0, 50.0)),
+    ]);
+    let mut child = layout.new_widget("child \"quoted\"");
+    child.edit_width().set(20.0);
+    root.add_child(&mut *child);
+    layout.add_root(root.clone());
+    layout.update();
+
+    assert_eq!(layout.solver.layout_edit_vars(child.id), vec!["STR   child \"quoted\".width == 20"]);
+    assert!(layout.solver.layout_constraints(root.id).contains(&"REQD  root.width == 100".to_owned()));
+
+    let json = layout.solver.layout_graph_json();
+    assert!(json.starts_with("{\"root\":0,\"layouts\":["));
+    assert!(json.contains(&format!("{{\"id\":{},\"name\":\"root\",\"hidden\":false,\"bounds\":{{\"left\":0,\"top\":0,\"width\":100,\"height\":50}},\"children\":[{}]", root.id, child.id)));
+    assert!(json.contains("\"name\":\"child \\\"quoted\\\"\""));
+    assert!(json.contains("{\"var\":\"child \\\"quoted\\\".width\",\"value\":20,\"strength\":1000000}"));
+    assert!(json.contains(&format!("{{\"description\":\"REQD  root.width == 100\",\"strength\":1001001000,\"active\":true,\"layouts\":[{}]}}", root.id)));
+}
+
+#[test]
+fn isolated_layout() {
+    let mut layout = TestLayout::new();
+
+    let mut root = layout.new_widget("root");
+    root.add(top_left(Point::new(0.0, 0.0)));
+    root.edit_width().set(200.0);
+    root.edit_height().set(100.0);
+    let mut rows: Vec<SharedLayout> = Vec::new();
+    let mut labels = Vec::new();
+    let mut fields = Vec::new();
+    for index in 0..2 {
+        let mut row = layout.new_widget(&format!("row_{}", index));
+        row.isolate();
+        row.add(constraints![
+            align_left(&root),
+            align_right(&root),
+            height(50.0),
+        ]);
+        match rows.last() {
+            Some(prev) => row.add(below(prev)),
+            None => row.add(align_top(&root)),
         }
+        root.add_child(&mut *row);
+        let mut label = layout.new_widget(&format!("label_{}", index));
+        label.add(width(50.0));
+        row.add_child(&mut *label);
+        let mut field = layout.new_widget(&format!("field_{}", index));
+        field.add(constraints![
+            to_right_of(&label).padding(10.0),
+            align_right(&row),
+        ]);
+        row.add_child(&mut *field);
+        rows.push(row);
+        labels.push(label);
+        fields.push(field);
     }
+    layout.add_root(root.clone());
+    layout.update();
+    assert!(layout.match_layouts(hashmap!{
+        rows[1].id => Rect::new(Point::new(0.0, 50.0), Size::new(200.0, 50.0)),
+        labels[0].id => Rect::new(Point::new(0.0, 0.0), Size::new(50.0, 50.0)),
+        fields[0].id => Rect::new(Point::new(60.0, 0.0), Size::new(140.0, 50.0)),
+        labels[1].id => Rect::new(Point::new(0.0, 50.0), Size::new(50.0, 50.0)),
+        fields[1].id => Rect::new(Point::new(60.0, 50.0), Size::new(140.0, 50.0)),
+    }));
+
+    // changes to the bounds of isolated layouts are passed on to their descendants
+    root.edit_width().set(300.0);
+    layout.update();
+    assert!(layout.match_layouts(hashmap!{
+        rows[1].id => Rect::new(Point::new(0.0, 50.0), Size::new(300.0, 50.0)),
+        fields[0].id => Rect::new(Point::new(60.0, 0.0), Size::new(240.0, 50.0)),
+        fields[1].id => Rect::new(Point::new(60.0, 50.0), Size::new(240.0, 50.0)),
+    }));
+
+    // a constraint crossing the boundary of an isolated layout merges it into the parent solver
+    let mut badge = layout.new_widget("badge");
+    badge.add(constraints![
+        size(Size::new(10.0, 10.0)),
+        align_top(&fields[1]),
+        align_right(&fields[1]),
+    ]);
+    root.add_child(&mut *badge);
+    root.edit_width().set(200.0);
+    layout.update();
+    assert!(layout.match_layouts(hashmap!{
+        badge.id => Rect::new(Point::new(190.0, 50.0), Size::new(10.0, 10.0)),
+        fields[0].id => Rect::new(Point::new(60.0, 0.0), Size::new(140.0, 50.0)),
+        fields[1].id => Rect::new(Point::new(60.0, 50.0), Size::new(140.0, 50.0)),
+    }));
+
+    root.remove_child(&mut *rows[0]);
+    root.edit_width().set(100.0);
+    layout.update();
+    assert!(layout.match_layouts(hashmap!{
+        fields[1].id => Rect::new(Point::new(60.0, 50.0), Size::new(40.0, 50.0)),
+        badge.id => Rect::new(Point::new(90.0, 50.0), Size::new(10.0, 10.0)),
+    }));
 }
-impl IdGen {
 
-    fn new() -> Self {
-        Self::default()
-    }
+#[test]
+fn isolated_layout_sized_to_children() {
+    let mut layout = TestLayout::new();
 
-    fn next(&mut self) -> LayoutId {
-        let next = self.id;
-        self.id += 1;
-        next
-    }
+    let mut root = layout.new_widget("root");
+    root.add(constraints![
+        top_left(Point::new(0.0, 0.0)),
+        size(Size::new(200.0, 200.0)),
+    ]);
+    // the bounds of the panel depend on it's child, so it can't be solved on it's own
+    let mut panel = layout.new_widget("panel");
+    panel.isolate();
+    panel.add(top_left(Point::new(10.0, 10.0)));
+    root.add_child(&mut *panel);
+    let mut child = layout.new_widget("child");
+    child.add(constraints![
+        size(Size::new(80.0, 30.0)),
+        match_layout(&panel),
+    ]);
+    panel.add_child(&mut *child);
+    layout.add_root(root.clone());
+    layout.update();
+    assert!(layout.match_layouts(hashmap!{
+        panel.id => Rect::new(Point::new(10.0, 10.0), Size::new(80.0, 30.0)),
+        child.id => Rect::new(Point::new(10.0, 10.0), Size::new(80.0, 30.0)),
+    }));
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "after it was added to the solver")]
+fn isolate_after_added() {
+    let mut layout = TestLayout::new();
+    let mut root = layout.new_widget("root");
+    root.add(constraints![
+        top_left(Point::new(0.0, 0.0)),
+        size(Size::new(200.0, 200.0)),
+    ]);
+    layout.add_root(root.clone());
+    layout.update();
+    root.isolate();
+    layout.update();
 }
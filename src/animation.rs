@@ -0,0 +1,224 @@
+//! Animated layout transitions.
+//!
+//! By default, widgets jump to their new bounds as soon as the layout changes. A widget with a
+//! `Transition`, set with `Widget::set_transition`, instead moves from it's current bounds to the
+//! newly solved bounds over the duration of the transition, following an easing curve.
+//! The transition also applies to the widget's descendants, unless they have one of their own,
+//! so the contents of an expanding panel move along with it.
+//!
+//! Widgets that appear, when first laid out or shown, grow from their top left corner, and
+//! widgets that are hidden shrink towards it.
+//!
+//! Transitions are advanced on every `FrameEvent`, a headless `App` can advance them with
+//! `App::advance_animations`.
+
+use std::time::{Duration, Instant};
+
+use event::{EventHandler, EventArgs};
+use widget::Widget;
+use layout::VarType;
+use geometry::{Rect, Point, Size};
+use ui::Ui;
+use app::{App, FrameEvent};
+
+/// Maps the linear progress of a transition, from 0.0 to 1.0, to the progress of the animation
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Easing {
+    Linear,
+    /// Starts slowly, then accelerates
+    EaseIn,
+    /// Starts quickly, then decelerates
+    EaseOut,
+    /// Accelerates, then decelerates
+    EaseInOut,
+}
+
+impl Easing {
+    pub fn apply(&self, progress: f32) -> f32 {
+        let t = progress.max(0.0).min(1.0);
+        match *self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => {
+                let t = 1.0 - t;
+                1.0 - t * t * t
+            }
+            Easing::EaseInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    let t = 2.0 - 2.0 * t;
+                    1.0 - t * t * t / 2.0
+                }
+            }
+        }
+    }
+}
+
+/// How a widget moves to new bounds when it's layout changes
+#[derive(Debug, Copy, Clone)]
+pub struct Transition {
+    pub duration: Duration,
+    pub easing: Easing,
+}
+
+impl Transition {
+    /// Creates a `Transition` with the given duration, that eases in and out
+    pub fn new(duration: Duration) -> Self {
+        Transition {
+            duration: duration,
+            easing: Easing::EaseInOut,
+        }
+    }
+    pub fn easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+}
+
+/// A widget moving from one set of bounds to another
+pub(crate) struct BoundsAnimation {
+    widget: Widget,
+    transition: Transition,
+    from: Rect,
+    to: Rect,
+    elapsed: Duration,
+}
+
+impl BoundsAnimation {
+    fn progress(&self) -> f32 {
+        let duration = seconds(self.transition.duration);
+        if duration > 0.0 {
+            (seconds(self.elapsed) / duration).min(1.0)
+        } else {
+            1.0
+        }
+    }
+
+    /// The bounds of the widget at the current point in the animation
+    fn bounds(&self) -> Rect {
+        let progress = self.progress();
+        if progress >= 1.0 {
+            return self.to;
+        }
+        let (mut from, mut to) = (self.from, self.to);
+        if is_empty(from) {
+            from = Rect::new(to.origin, Size::zero());
+        }
+        if is_empty(to) {
+            to = Rect::new(from.origin, Size::zero());
+        }
+        let t = self.transition.easing.apply(progress);
+        let lerp = |from: f32, to: f32| from + (to - from) * t;
+        Rect::new(
+            Point::new(lerp(from.origin.x, to.origin.x), lerp(from.origin.y, to.origin.y)),
+            Size::new(lerp(from.size.width, to.size.width), lerp(from.size.height, to.size.height)))
+    }
+}
+
+fn is_empty(rect: Rect) -> bool {
+    rect.size.width == 0.0 && rect.size.height == 0.0
+}
+
+fn seconds(duration: Duration) -> f32 {
+    duration.as_secs() as f32 + duration.subsec_nanos() as f32 / 1_000_000_000.0
+}
+
+impl Widget {
+    /// Animates changes to the bounds of this widget and it's descendants
+    pub fn set_transition(&mut self, transition: Transition) -> &mut Self {
+        self.widget_mut().transition = Some(transition);
+        self
+    }
+    /// Changes to the bounds of this widget take effect immediately,
+    /// unless an ancestor has a transition
+    pub fn remove_transition(&mut self) -> &mut Self {
+        self.widget_mut().transition = None;
+        self
+    }
+    /// The transition of this widget, or it's closest ancestor with a transition
+    pub fn transition(&self) -> Option<Transition> {
+        let transition = self.widget().transition;
+        transition.or_else(|| self.parent().and_then(|parent| parent.transition()))
+    }
+}
+
+impl Ui {
+    /// Starts, or retargets, the animation of `widget` towards it's newly solved bounds
+    pub(crate) fn transition_bounds(&mut self, widget: Widget, transition: Transition, var: VarType, value: f32) {
+        let id = widget.id();
+        let from = widget.bounds();
+        let mut to = match self.animations.get(&id) {
+            Some(animation) => animation.to,
+            None => from,
+        };
+        match var {
+            VarType::Left => to.origin.x = value,
+            VarType::Top => to.origin.y = value,
+            VarType::Width => to.size.width = value,
+            VarType::Height => to.size.height = value,
+            _ => return,
+        }
+        self.animations.insert(id, BoundsAnimation {
+            widget: widget,
+            transition: transition,
+            from: from,
+            to: to,
+            elapsed: Duration::from_secs(0),
+        });
+    }
+
+    /// Advances every running animation by `elapsed`
+    pub fn advance_animations(&mut self, elapsed: Duration) {
+        if self.animations.is_empty() {
+            return;
+        }
+        let mut finished = Vec::new();
+        for (id, animation) in &mut self.animations {
+            animation.elapsed += elapsed;
+            let bounds = animation.bounds();
+            animation.widget.set_bounds(bounds);
+            if animation.progress() >= 1.0 {
+                finished.push(*id);
+            }
+        }
+        for id in finished {
+            self.animations.remove(&id);
+        }
+        self.redraw();
+    }
+
+    /// Returns true if any widgets are moving to new bounds
+    pub fn is_animating(&self) -> bool {
+        !self.animations.is_empty()
+    }
+}
+
+/// Advances animations by the time since the last frame
+#[derive(Default)]
+struct FrameClock {
+    last_frame: Option<Instant>,
+}
+
+impl EventHandler<FrameEvent> for FrameClock {
+    fn handle(&mut self, _: &FrameEvent, args: EventArgs) {
+        if !args.ui.is_animating() {
+            // animations started after an idle period shouldn't skip ahead
+            self.last_frame = None;
+            return;
+        }
+        let now = Instant::now();
+        let elapsed = match self.last_frame {
+            Some(last_frame) => now - last_frame,
+            None => Duration::from_secs(0),
+        };
+        self.last_frame = Some(now);
+        args.ui.advance_animations(elapsed);
+    }
+}
+
+impl App {
+    pub fn add_animation_handlers(&mut self) {
+        self.add_handler(FrameClock::default());
+    }
+}
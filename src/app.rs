@@ -24,11 +24,11 @@ use geometry::Size;
 /// There should be only one `App` per Window.
 pub struct App {
     /// The UI currently visible in the window.
-    ui: Ui,
+    pub(crate) ui: Ui,
     /// Minimum time until the next frame is drawn, caps the UI to 60 FPS.
     next_frame_time: Instant,
-    /// Source of `glutin` input events.
-    events_loop: Rc<RefCell<glutin::EventsLoop>>,
+    /// Source of `glutin` input events, `None` if the `App` is headless.
+    events_loop: Option<Rc<RefCell<glutin::EventsLoop>>>,
     /// Used to ignore resize events before ui has been measured
     window_initialized: bool,
 }
@@ -39,20 +39,39 @@ impl App {
     /// desktop app:
     ///
     /// `ui_handlers`, `layout_handlers`, `input_handlers`,
-    /// `mouse_handlers`, `keyboard_handlers` and `drag_handlers`
+    /// `mouse_handlers`, `keyboard_handlers`, `drag_handlers` and `clipboard_handlers`
     pub fn new(window: Window, events_loop: glutin::EventsLoop) -> Self {
         event::queue_set_events_loop(&events_loop);
         let ui = Ui::new(window, &events_loop);
         let mut app = App {
             ui: ui,
             next_frame_time: Instant::now(),
-            events_loop: Rc::new(RefCell::new(events_loop)),
+            events_loop: Some(Rc::new(RefCell::new(events_loop))),
             window_initialized: false,
         };
         app.initialize_handlers();
         app
     }
 
+    /// Creates a new `App` without a window or renderer, with a virtual window of the given size.
+    /// Nothing is drawn, but events and layout are handled normally, input can be simulated
+    /// using the methods in the [`headless`](../headless/index.html) module.
+    ///
+    /// Intended for testing, the thread the `App` is created on becomes the UI thread.
+    pub fn new_headless(window_size: Size) -> Self {
+        event::queue_init_thread();
+        let mut app = App {
+            ui: Ui::new_headless(),
+            next_frame_time: Instant::now(),
+            events_loop: None,
+            window_initialized: true,
+        };
+        app.initialize_handlers();
+        app.ui.window_resized(window_size);
+        app.handle_events();
+        app
+    }
+
     /// Initialize the handlers that are used in a typical desktop app.
     /// The handlers that make up the event flow in an application are configurable
     fn initialize_handlers(&mut self) {
@@ -63,6 +82,14 @@ impl App {
         self.add_mouse_handlers();
         self.add_keyboard_handlers();
         self.add_drag_handlers();
+        self.add_clipboard_handlers();
+        self.add_inspector_handlers();
+        self.add_animation_handlers();
+        self.add_responsive_handlers();
+        // before popups, so escape closes a popup within a dialog without also cancelling the dialog
+        self.add_dialog_handlers();
+        self.add_popup_handlers();
+        self.add_tooltip_handlers();
     }
 
     fn handle_window_event(&mut self, event: glutin::Event) {
@@ -82,17 +109,17 @@ impl App {
     /// Updates the UI and redraws the window (the applications main loop)
     pub fn main_loop(mut self, root: Widget) {
         self.ui.root.add_child(root);
-        let events_loop = Rc::clone(&self.events_loop);
+        let events_loop = Rc::clone(self.events_loop.as_ref().expect("Can't run the main loop of a headless App"));
         let mut events_loop = events_loop.borrow_mut();
 
         // Handle set up events to allow layout to 'settle' and initialize
         // the window size to the initial layout size
         self.handle_events();
         self.ui.resize_window_to_fit();
-        self.ui.window.borrow_mut().show();
+        self.window().show();
         self.window_initialized = true;
         loop {
-            if !self.ui.needs_redraw() && !self.ui.render.frame_ready() {
+            if !self.ui.needs_redraw() && !self.ui.frame_ready() {
                 events_loop.run_forever(|event| {
                     self.handle_window_event(event);
                     glutin::ControlFlow::Break
@@ -103,11 +130,12 @@ impl App {
             });
             self.handle_events();
             if self.ui.should_close() {
-                self.ui.render.deinit();
+                self.ui.deinit();
                 return;
             }
             let now = Instant::now();
-            if now > self.next_frame_time {
+            let wakeup_due = self.ui.wakeup_due(now);
+            if now > self.next_frame_time || wakeup_due {
                 let frame_length = Duration::new(0, 1_000_000_000 / 60);
                 if self.next_frame_time + frame_length > now {
                     self.next_frame_time = now + frame_length;
@@ -123,9 +151,16 @@ impl App {
     }
 
     /// Handle all the pending events in the event queue
-    fn handle_events(&mut self) {
-        while let Some((event_address, type_id, data)) = event::queue_next() {
-            self.ui.handle_event(event_address, type_id, data.as_ref());
+    pub fn handle_events(&mut self) {
+        loop {
+            while let Some((event_address, type_id, data)) = event::queue_next() {
+                self.ui.handle_event(event_address, type_id, data.as_ref());
+            }
+            // layouts changed by the handled events are updated together,
+            // which can send more events
+            if !self.ui.update_layouts() {
+                break;
+            }
         }
     }
 
@@ -139,8 +174,9 @@ impl App {
         self.ui.get_root()
     }
 
+    /// Get the window the `App` is drawn to, panics if the `App` is headless
     pub fn window(&self) -> ::std::cell::Ref<Window> {
-        self.ui.window.borrow()
+        self.ui.window.as_ref().expect("Headless App has no window").borrow()
     }
 }
 
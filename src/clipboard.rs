@@ -0,0 +1,76 @@
+//! Clipboard access for text widgets and apps.
+//!
+//! The clipboard used by the `Ui` is pluggable, so apps can provide an implementation backed by
+//! the system clipboard. By default the `Ui` uses a `MemoryClipboard`, which is only shared within
+//! the app, and is what headless apps and tests use.
+//!
+//! Handlers can access the clipboard directly with `Ui::clipboard`, or by sending a `ClipboardEvent`
+//! to the root widget.
+
+use event::EventArgs;
+use widget::Widget;
+use app::App;
+
+/// A backend that can store and retrieve text
+pub trait Clipboard {
+    /// The current contents of the clipboard, if it contains any text
+    fn get_contents(&mut self) -> Option<String>;
+    /// Replaces the contents of the clipboard
+    fn set_contents(&mut self, contents: String);
+}
+
+/// A clipboard that stores it's contents in memory
+#[derive(Debug, Default)]
+pub struct MemoryClipboard {
+    contents: Option<String>,
+}
+
+impl MemoryClipboard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Clipboard for MemoryClipboard {
+    fn get_contents(&mut self) -> Option<String> {
+        self.contents.clone()
+    }
+    fn set_contents(&mut self, contents: String) {
+        self.contents = Some(contents);
+    }
+}
+
+/// Reads or writes the clipboard, should be sent to the root widget
+#[derive(Debug, Clone)]
+pub enum ClipboardEvent {
+    /// Replace the contents of the clipboard
+    Set(String),
+    /// Request the contents of the clipboard, they are sent to the widget as a `ClipboardContents` event
+    Get(Widget),
+}
+
+/// The contents of the clipboard, sent in response to a `ClipboardEvent::Get`
+#[derive(Debug, Clone)]
+pub struct ClipboardContents(pub Option<String>);
+
+impl App {
+    /// Add handler for `ClipboardEvent`s to the root widget
+    pub fn add_clipboard_handlers(&mut self) {
+        self.add_handler(|event: &ClipboardEvent, args: EventArgs| {
+            match *event {
+                ClipboardEvent::Set(ref contents) => {
+                    args.ui.clipboard().set_contents(contents.clone());
+                }
+                ClipboardEvent::Get(ref widget) => {
+                    let contents = args.ui.clipboard().get_contents();
+                    widget.event(ClipboardContents(contents));
+                }
+            }
+        });
+    }
+
+    /// Replace the clipboard used by the app, by default a `MemoryClipboard` is used
+    pub fn set_clipboard<C: Clipboard + 'static>(&mut self, clipboard: C) {
+        self.ui.set_clipboard(clipboard);
+    }
+}
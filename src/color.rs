@@ -26,6 +26,13 @@ impl Into<ColorF> for Color {
     }
 }
 
+impl From<ColorF> for Color {
+    fn from(color: ColorF) -> Self {
+        let write = |value: f32, offset: u32| { ((value * 255.0).round() as u32 & 0xFF) << offset };
+        Color(write(color.r, 24) | write(color.g, 16) | write(color.b, 8) | write(color.a, 0))
+    }
+}
+
 pub const TRANSPARENT: Color = Color(0x00000000);
 pub const BLACK: Color = Color(0x000000FF);
 pub const WHITE: Color = Color(0xFFFFFFFF);
@@ -0,0 +1,175 @@
+//! Modal dialogs, that block interaction with the rest of the UI until they're closed.
+//!
+//! A dialog is shown over a backdrop that dims the widget tree and takes the mouse input meant for
+//! everything outside of the dialog. Keyboard focus is trapped within the dialog, and only
+//! shortcuts scoped to widgets within it are active.
+//! A dialog is closed with a `DialogResponse`, by sending `CloseDialog` from within the dialog,
+//! ie. from it's buttons, or with `Ui::close_dialog`. Pressing escape cancels the most recently
+//! opened dialog. The response is sent to the dialog, and the widget that opened it, as `DialogClosed`.
+//!
+//! `Ui::alert` and `Ui::confirm` open a `MessageBox`, a dialog with a message and a row of buttons:
+//!
+//! ```no_run
+//! # extern crate limn;
+//! # use limn::prelude::*;
+//! use limn::dialog::{DialogClosed, DialogResponse};
+//! # fn main() {
+//! # let mut button = Widget::new("button");
+//! button.add_handler(|_: &ClickEvent, args: EventArgs| {
+//!     args.ui.confirm(&args.widget, "Discard unsaved changes?");
+//! });
+//! button.add_handler(|event: &DialogClosed, _: EventArgs| {
+//!     if event.response == DialogResponse::Ok {
+//!         println!("discarding");
+//!     }
+//! });
+//! # }
+//! ```
+
+use glutin;
+
+use event::EventArgs;
+use widget::Widget;
+use input::keyboard::KeyboardInput;
+use layout::constraint::*;
+use widgets::message_box::MessageBoxStyle;
+use draw::rect::RectStyle;
+use color::Color;
+use style::DrawStyle;
+use ui::{Ui, DIALOG_Z_INDEX};
+use app::App;
+
+/// How a dialog was closed, ie. which of it's buttons was pressed
+#[derive(Debug, Clone, PartialEq)]
+pub enum DialogResponse {
+    Ok,
+    Cancel,
+    Custom(String),
+}
+
+impl DialogResponse {
+    /// The text of a button that closes a dialog with this response
+    pub fn label(&self) -> &str {
+        match *self {
+            DialogResponse::Ok => "OK",
+            DialogResponse::Cancel => "Cancel",
+            DialogResponse::Custom(ref label) => label,
+        }
+    }
+}
+
+/// Closes the dialog that contains the widget it's sent to, with the given response,
+/// should be sent with `event_bubble_up`
+#[derive(Debug, Clone)]
+pub struct CloseDialog(pub DialogResponse);
+
+/// Sent to a dialog, and it's owner, when the dialog is closed
+#[derive(Debug, Clone)]
+pub struct DialogClosed {
+    pub dialog: Widget,
+    pub response: DialogResponse,
+}
+
+pub(crate) struct Dialog {
+    widget: Widget,
+    owner: Widget,
+    /// The backdrop the dialog is centered in
+    layer: Widget,
+}
+
+impl Ui {
+    /// Opens `dialog` on behalf of `owner`, centered in the window, over a backdrop that blocks
+    /// input to the rest of the UI. Any open popups are closed.
+    pub fn open_dialog(&mut self, owner: &Widget, mut dialog: Widget) {
+        self.close_popups();
+        let mut layer = Widget::new("dialog_layer");
+        let mut draw_style = DrawStyle::from(style!(RectStyle {
+            background_color: Color(0x00000066),
+        }));
+        draw_style.set_class("dialog_backdrop");
+        layer
+            .set_draw_style(draw_style)
+            .set_z_index(DIALOG_Z_INDEX);
+        layer.layout().no_container();
+        dialog.layout().add(constraints![
+            bound_by(&layer),
+            center(&layer),
+        ]);
+        dialog.add_handler(|event: &CloseDialog, args: EventArgs| {
+            args.ui.close_dialog(&args.widget, event.0.clone());
+            *args.handled = true;
+        });
+        layer.add_child(dialog.clone());
+        self.root.add_child(layer.clone());
+        dialog.push_focus_scope();
+        self.dialogs.push(Dialog {
+            widget: dialog,
+            owner: owner.clone(),
+            layer: layer,
+        });
+    }
+
+    /// Closes `dialog`, sending `DialogClosed` with `response`, and restores the focus from before
+    /// it was opened
+    pub fn close_dialog(&mut self, dialog: &Widget, response: DialogResponse) {
+        if let Some(index) = self.dialogs.iter().position(|open| open.widget == *dialog) {
+            // popups opened from within the dialog
+            self.close_popups();
+            let Dialog { widget, owner, mut layer } = self.dialogs.remove(index);
+            widget.pop_focus_scope();
+            layer.remove_widget();
+            let event = DialogClosed {
+                dialog: widget.clone(),
+                response: response,
+            };
+            widget.event(event.clone());
+            if owner != widget {
+                owner.event(event);
+            }
+        }
+    }
+
+    /// Returns true if `dialog` is open
+    pub fn is_dialog_open(&self, dialog: &Widget) -> bool {
+        self.dialogs.iter().any(|open| open.widget == *dialog)
+    }
+
+    /// The most recently opened dialog, the only one that receives input
+    pub fn modal_dialog(&self) -> Option<Widget> {
+        self.dialogs.last().map(|open| open.widget.clone())
+    }
+
+    /// Opens a message box with `message` and an OK button, returns the dialog
+    pub fn alert(&mut self, owner: &Widget, message: &str) -> Widget {
+        self.open_message_box(owner, MessageBoxStyle::alert(message))
+    }
+
+    /// Opens a message box with `message`, and OK and Cancel buttons, returns the dialog
+    pub fn confirm(&mut self, owner: &Widget, message: &str) -> Widget {
+        self.open_message_box(owner, MessageBoxStyle::confirm(message))
+    }
+
+    /// Opens a message box with a title, message and buttons from `style`, returns the dialog
+    pub fn open_message_box(&mut self, owner: &Widget, style: MessageBoxStyle) -> Widget {
+        let dialog = Widget::from_modifier_style(style);
+        self.open_dialog(owner, dialog.clone());
+        dialog
+    }
+}
+
+impl App {
+    pub fn add_dialog_handlers(&mut self) {
+        self.add_handler(|event: &KeyboardInput, args: EventArgs| {
+            let &KeyboardInput(input) = event;
+            if input.state == glutin::ElementState::Pressed && input.virtual_keycode == Some(glutin::VirtualKeyCode::Escape) {
+                // escape closes popups first
+                if !args.ui.popups.is_empty() {
+                    return;
+                }
+                if let Some(dialog) = args.ui.modal_dialog() {
+                    args.ui.close_dialog(&dialog, DialogResponse::Cancel);
+                }
+            }
+        });
+    }
+}
@@ -38,7 +38,7 @@ fn clip_ellipse(rect: Rect) -> LocalClip {
 fn push_ellipse(renderer: &mut RenderBuilder, rect: Rect, clip_rect: Rect, color: Color) {
     let clip = clip_ellipse(clip_rect);
     let info = PrimitiveInfo::with_clip(rect, clip);
-    renderer.builder.push_rect(&info, color.into());
+    renderer.push_rect(&info, color);
 }
 
 fn point_inside_ellipse(point: Point, center: Point, radius: Size) -> bool {
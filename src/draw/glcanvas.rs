@@ -1,7 +1,7 @@
 use webrender::api::*;
 
 use render::RenderBuilder;
-use widget::draw::Draw;
+use widget::draw::{Draw, Measure, SizeHint};
 use resources::resources;
 use resources::image::ImageInfo;
 use geometry::{Rect, RectExt, Size};
@@ -40,7 +40,17 @@ impl GLCanvasState {
     }
 }
 
+/// The canvas prefers the size of it's texture, which is resized to fit the canvas when drawn
+impl Measure for GLCanvasState {
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::new(Size::zero(), self.measure(), None)
+    }
+}
+
 impl Draw for GLCanvasState {
+    fn as_measure(&self) -> Option<&Measure> {
+        Some(self)
+    }
     fn draw(&mut self, bounds: Rect, _: Rect, renderer: &mut RenderBuilder) {
         let descriptor = self.image_info.descriptor;
         let (bounds_width, bounds_height) = (bounds.width() as u32, bounds.height() as u32);
@@ -49,13 +59,7 @@ impl Draw for GLCanvasState {
             resources().image_loader.update_texture(self.image_info.key, descriptor, self.data);
             self.image_info.descriptor = descriptor;
         }
-        renderer.builder.push_image(
-            &PrimitiveInfo::new(bounds),
-            bounds.size,
-            LayoutSize::zero(),
-            ImageRendering::Auto,
-            AlphaType::Alpha,
-            self.image_info.key,
-        );
+        let ExternalImageId(texture_id) = self.data.id;
+        renderer.push_image(&PrimitiveInfo::new(bounds), self.image_info.key, &format!("texture {}", texture_id));
     }
 }
@@ -1,7 +1,7 @@
 use webrender::api::*;
 
 use render::RenderBuilder;
-use widget::draw::Draw;
+use widget::draw::{Draw, Measure, SizeHint};
 use resources::resources;
 use resources::image::ImageSource;
 use geometry::{Rect, Size};
@@ -35,17 +35,19 @@ impl ImageState {
     }
 }
 
+impl Measure for ImageState {
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::exact(self.measure())
+    }
+}
+
 impl Draw for ImageState {
+    fn as_measure(&self) -> Option<&Measure> {
+        Some(self)
+    }
     fn draw(&mut self, bounds: Rect, _: Rect, renderer: &mut RenderBuilder) {
         let key = resources().image_loader.get_image(&self.image).unwrap().key;
         let info = PrimitiveInfo::new(bounds);
-        renderer.builder.push_image(
-            &info,
-            bounds.size,
-            LayoutSize::zero(),
-            ImageRendering::Auto,
-            AlphaType::Alpha,
-            key,
-        );
+        renderer.push_image(&info, key, &format!("{:?}", self.image));
     }
 }
@@ -37,5 +37,5 @@ fn push_rect(renderer: &mut RenderBuilder, rect: Rect, color: Color, clip_rect:
     } else {
         PrimitiveInfo::new(rect)
     };
-    renderer.builder.push_rect(&info, color.into());
+    renderer.push_rect(&info, color);
 }
@@ -1,16 +1,23 @@
-use webrender::api::{LayoutPoint, GlyphInstance, PrimitiveInfo, FontInstanceKey};
+use std::ops::Range;
+
+use webrender::api::{LayoutPoint, GlyphInstance, PrimitiveInfo};
 use rusttype::{Scale, GlyphId, VMetrics};
 
 use render::RenderBuilder;
 use text_layout::{self, Wrap, Align};
+use text_layout::cursor::{self, Index};
+use text_layout::glyph;
+use text_layout::line::{LineInfo, LineInfos, LineRects, SelectedLineRects};
 use resources::resources;
-use resources::font::FontDescriptor;
-use geometry::{Size, Rect, RectExt, Vector};
+use resources::font::{Font, FontDescriptor};
+use geometry::{Size, Rect, RectExt, Point, Vector};
 use render;
-use widget::draw::Draw;
+use widget::draw::{Draw, Measure, SizeHint};
 use color::*;
 
 const DEBUG_LINE_BOUNDS: bool = false;
+const CURSOR_WIDTH: f32 = 1.0;
+const UNDERLINE_WIDTH: f32 = 1.0;
 
 component_style!{pub struct TextState<name="text", style=TextStyle> {
     text: String = String::from(""),
@@ -20,6 +27,14 @@ component_style!{pub struct TextState<name="text", style=TextStyle> {
     background_color: Color = TRANSPARENT,
     wrap: Wrap = Wrap::Whitespace,
     align: Align = Align::Start,
+    // Char index of the cursor, the cursor is only drawn if set
+    cursor: Option<usize> = None,
+    // Range of selected chars, drawn behind the text
+    selection: Option<Range<usize>> = None,
+    // Range of chars drawn underlined, ie. text being composed
+    underline: Option<Range<usize>> = None,
+    cursor_color: Color = BLACK,
+    selection_color: Color = GRAY_80,
 }}
 
 impl TextStyle {
@@ -49,6 +64,19 @@ impl TextState {
     pub fn line_height(&self) -> f32 {
         self.font_size + self.v_metrics().line_gap
     }
+    /// The height of the text when wrapped to `width`
+    pub fn text_height(&self, width: f32) -> f32 {
+        let line_height = self.line_height();
+        let mut resources = resources();
+        let font = resources.font_loader.get_font(&self.font).unwrap();
+        text_layout::get_text_height(
+            &self.text,
+            &font.info,
+            self.font_size,
+            line_height,
+            self.wrap,
+            width)
+    }
     pub fn text_fits(&self, text: &str, bounds: Rect) -> bool {
         let line_height = self.line_height();
         let mut resources = resources();
@@ -62,6 +90,87 @@ impl TextState {
             bounds.width());
         height <= bounds.height()
     }
+    /// The rect of the cursor placed before the char at `char_index`, for text drawn in `bounds`
+    pub fn cursor_rect(&self, bounds: Rect, char_index: usize) -> Rect {
+        let line_height = self.line_height();
+        let mut resources = resources();
+        let font = resources.font_loader.get_font(&self.font).unwrap();
+        let line_infos = self.line_infos(&font.info, bounds.width());
+        let index = self.char_to_index(&line_infos, char_index);
+        let xys_per_line = cursor::xys_per_line_from_text(
+            &self.text,
+            &line_infos,
+            &font.info,
+            self.font_size,
+            self.align,
+            line_height,
+            bounds.to_untyped());
+        match cursor::xy_at(xys_per_line, index) {
+            Some((x, y)) => Rect::new(Point::new(x, y.start), Size::new(CURSOR_WIDTH, y.end - y.start)),
+            None => Rect::new(bounds.origin, Size::new(CURSOR_WIDTH, line_height)),
+        }
+    }
+    /// The char index of the cursor position closest to `point`, for text drawn in `bounds`
+    pub fn closest_char_index(&self, bounds: Rect, point: Point) -> usize {
+        let line_height = self.line_height();
+        let mut resources = resources();
+        let font = resources.font_loader.get_font(&self.font).unwrap();
+        let line_infos = self.line_infos(&font.info, bounds.width());
+        let xys_per_line = cursor::xys_per_line_from_text(
+            &self.text,
+            &line_infos,
+            &font.info,
+            self.font_size,
+            self.align,
+            line_height,
+            bounds.to_untyped());
+        cursor::closest_cursor_index_and_xy(point.to_untyped(), xys_per_line)
+            .and_then(|(index, _)| glyph::index_after_cursor(line_infos.iter().cloned(), index))
+            .unwrap_or(0)
+    }
+    /// The rects covering the chars in `selection`, one per line, for text drawn in `bounds`
+    pub fn selection_rects(&self, bounds: Rect, selection: Range<usize>) -> Vec<Rect> {
+        let line_height = self.line_height();
+        let mut resources = resources();
+        let font = resources.font_loader.get_font(&self.font).unwrap();
+        let line_infos = self.line_infos(&font.info, bounds.width());
+        let start = self.char_to_index(&line_infos, selection.start);
+        let end = self.char_to_index(&line_infos, selection.end);
+        let line_rects = LineRects::new(line_infos.iter().cloned(), self.font_size, bounds.to_untyped(), self.align, line_height);
+        let line_texts = line_infos.iter().map(|info| &self.text[info.byte_range()]);
+        SelectedLineRects::new(line_texts.zip(line_rects), &font.info, self.font_size, start, end)
+            .map(|rect| Rect::from_untyped(&rect))
+            .collect()
+    }
+    /// The char index of the start of the word before `char_index`, or the start of the text
+    pub fn previous_word_start(&self, bounds: Rect, char_index: usize) -> usize {
+        let mut resources = resources();
+        let font = resources.font_loader.get_font(&self.font).unwrap();
+        let line_infos = self.line_infos(&font.info, bounds.width());
+        self.char_to_index(&line_infos, char_index)
+            .previous_word_start(&self.text, line_infos.iter().cloned())
+            .and_then(|index| glyph::index_after_cursor(line_infos.iter().cloned(), index))
+            .unwrap_or(0)
+    }
+    /// The char index of the end of the word after `char_index`, or the end of the text
+    pub fn next_word_end(&self, bounds: Rect, char_index: usize) -> usize {
+        let mut resources = resources();
+        let font = resources.font_loader.get_font(&self.font).unwrap();
+        let line_infos = self.line_infos(&font.info, bounds.width());
+        self.char_to_index(&line_infos, char_index)
+            .next_word_end(&self.text, line_infos.iter().cloned())
+            .and_then(|index| glyph::index_after_cursor(line_infos.iter().cloned(), index))
+            .unwrap_or_else(|| self.text.chars().count())
+    }
+    fn line_infos(&self, font: &Font, width: f32) -> Vec<LineInfo> {
+        LineInfos::new(&self.text, font, self.font_size, self.wrap, width).collect()
+    }
+    /// Converts a char index into a line and position within that line, clamped to the end of the text
+    fn char_to_index(&self, line_infos: &[LineInfo], char_index: usize) -> Index {
+        cursor::index_before_char(line_infos.iter().cloned(), char_index).unwrap_or_else(|| {
+            Index { line: line_infos.len(), char: 0 }.clamp_to_lines(line_infos.iter().cloned())
+        })
+    }
     fn get_line_rects(&self, bounds: Rect) -> Vec<Rect> {
         let line_height = self.line_height();
         let mut resources = resources();
@@ -95,9 +204,6 @@ impl TextState {
                 }
             }).collect()
     }
-    fn font_instance_key(&self) -> FontInstanceKey {
-        *resources().font_loader.get_font_instance(&self.font, self.font_size).unwrap()
-    }
     fn v_metrics(&self) -> VMetrics {
         let mut resources = resources();
         let font = resources.font_loader.get_font(&self.font).unwrap();
@@ -105,7 +211,28 @@ impl TextState {
     }
 }
 
+/// Text that doesn't wrap is exactly it's measured size, wrapped text can be narrowed
+/// to any width, and is as tall as the text wrapped to that width.
+impl Measure for TextState {
+    fn size_hint(&self) -> SizeHint {
+        let size = self.measure();
+        match self.wrap {
+            Wrap::NoWrap => SizeHint::exact(size),
+            _ => SizeHint::new(Size::new(0.0, self.min_height()), size, None),
+        }
+    }
+    fn height_for_width(&self, width: f32) -> Option<f32> {
+        match self.wrap {
+            Wrap::NoWrap => None,
+            _ => Some(self.text_height(width)),
+        }
+    }
+}
+
 impl Draw for TextState {
+    fn as_measure(&self) -> Option<&Measure> {
+        Some(self)
+    }
     fn draw(&mut self, bounds: Rect, _: Rect, renderer: &mut RenderBuilder) {
         let glyphs = self.position_glyphs(bounds);
         if DEBUG_LINE_BOUNDS {
@@ -130,16 +257,25 @@ impl Draw for TextState {
             }
         }
         if self.background_color != TRANSPARENT {
-            renderer.builder.push_rect(&PrimitiveInfo::new(bounds.clone()), self.background_color.into());
+            renderer.push_rect(&PrimitiveInfo::new(bounds.clone()), self.background_color);
+        }
+        if let Some(ref selection) = self.selection {
+            for rect in self.selection_rects(bounds, selection.clone()) {
+                renderer.push_rect(&PrimitiveInfo::new(rect), self.selection_color);
+            }
         }
-        let key = self.font_instance_key();
         let info = PrimitiveInfo::new(bounds);
-        renderer.builder.push_text(
-            &info,
-            &glyphs,
-            key,
-            self.text_color.into(),
-            None,
-        );
+        renderer.push_text(&info, &glyphs, &self.font, self.font_size, self.text_color);
+        if let Some(ref underline) = self.underline {
+            for mut rect in self.selection_rects(bounds, underline.clone()) {
+                rect.origin.y = rect.bottom() - UNDERLINE_WIDTH;
+                rect.size.height = UNDERLINE_WIDTH;
+                renderer.push_rect(&PrimitiveInfo::new(rect), self.text_color);
+            }
+        }
+        if let Some(cursor) = self.cursor {
+            let rect = self.cursor_rect(bounds, cursor);
+            renderer.push_rect(&PrimitiveInfo::new(rect), self.cursor_color);
+        }
     }
 }
@@ -39,8 +39,10 @@
 
 use std::any::{Any, TypeId};
 use std::cell::{Cell, RefCell};
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex, Condvar};
 use std::collections::VecDeque;
+use std::thread;
+use std::time::Instant;
 
 use glutin::{EventsLoop, EventsLoopProxy};
 
@@ -159,6 +161,7 @@ impl EventHandlerWrapper {
 lazy_static! {
     static ref FIRST_THREAD: Mutex<Cell<bool>> = Mutex::new(Cell::new(true));
     static ref GLOBAL_QUEUE: Mutex<GlobalQueue> = Mutex::new(GlobalQueue::new());
+    static ref WAKEUP_TIMER: WakeupTimer = WakeupTimer::new();
 }
 
 thread_local! {
@@ -183,6 +186,18 @@ pub(super) fn queue_next() -> Option<(Target, TypeId, Box<Any>)> {
     }
 }
 
+/// Makes the current thread the UI thread and discards any events left in it's queue.
+/// Used by headless apps, which may be created on a different thread each time, ie. in tests.
+pub(super) fn queue_init_thread() {
+    FIRST_THREAD.lock().unwrap().set(true);
+    LOCAL_QUEUE.with(|queue| {
+        if let Some(queue) = queue.as_ref() {
+            queue.borrow_mut().queue.clear();
+        }
+    });
+    FIRST_THREAD.lock().unwrap().set(false);
+}
+
 pub(super) fn queue_set_events_loop(events_loop: &EventsLoop) {
     GLOBAL_QUEUE.lock().unwrap().set_events_loop(events_loop.create_proxy());
     LOCAL_QUEUE.with(|queue| queue.as_ref().unwrap().borrow_mut().set_events_loop(events_loop.create_proxy()));
@@ -236,6 +251,53 @@ impl Iterator for GlobalQueue {
     }
 }
 
+/// Wakes the window up at the earliest deadline it's been given, without sending any events.
+/// A single thread is shared by all the timers, see `Ui::wake_at`.
+struct WakeupTimer {
+    deadline: Arc<(Mutex<Option<Instant>>, Condvar)>,
+}
+
+impl WakeupTimer {
+    fn new() -> Self {
+        let deadline = Arc::new((Mutex::new(None), Condvar::new()));
+        let timer_deadline = Arc::clone(&deadline);
+        thread::spawn(move || {
+            let &(ref lock, ref condvar) = &*timer_deadline;
+            let mut deadline = lock.lock().unwrap();
+            loop {
+                let now = Instant::now();
+                let next: Option<Instant> = *deadline;
+                match next {
+                    None => deadline = condvar.wait(deadline).unwrap(),
+                    Some(next) if next > now => deadline = condvar.wait_timeout(deadline, next - now).unwrap().0,
+                    Some(_) => {
+                        *deadline = None;
+                        if let Some(ref events_loop_proxy) = GLOBAL_QUEUE.lock().unwrap().events_loop_proxy {
+                            let _ = events_loop_proxy.wakeup();
+                        }
+                    }
+                }
+            }
+        });
+        WakeupTimer {
+            deadline: deadline,
+        }
+    }
+    fn wake_at(&self, at: Instant) {
+        let &(ref lock, ref condvar) = &*self.deadline;
+        let mut deadline = lock.lock().unwrap();
+        if deadline.map_or(true, |deadline| at < deadline) {
+            *deadline = Some(at);
+            condvar.notify_one();
+        }
+    }
+}
+
+/// Wakes the window up at `deadline`, if it's waiting for events
+pub(super) fn wakeup_at(deadline: Instant) {
+    WAKEUP_TIMER.wake_at(deadline);
+}
+
 /// Simplifies setting up an EventHandler that can receive multiple events. Generates an event enum and matches each event to a method on the handler.
 /// Also creates an associated method on the handler, `add_adapters` that should be called when the handler is added to a widget, to add the "adapter"
 /// handlers that redirect each event to the main event handler.
@@ -0,0 +1,154 @@
+//! Helpers for driving a headless `App`, without a window or GPU.
+//!
+//! A headless `App` handles events and layout exactly like a normal `App`, but nothing is drawn,
+//! and input has to be simulated using the methods below. Each of the input methods sends the
+//! same events the input handlers would send for real input, then handles all pending events,
+//! so the state of the UI can be inspected immediately afterwards.
+//!
+//! ```no_run
+//! # use limn::prelude::*;
+//! let mut app = App::new_headless(Size::new(300.0, 300.0));
+//! let mut root = Widget::new("root");
+//! root.layout().add(size(Size::new(100.0, 100.0)));
+//! app.set_root(root);
+//! app.click(Point::new(50.0, 50.0));
+//! assert_eq!(app.find_widget("root").unwrap().bounds().size, Size::new(100.0, 100.0));
+//! ```
+
+use std::time::Duration;
+
+use glutin;
+use glutin::{ElementState, VirtualKeyCode, ModifiersState};
+
+use app::App;
+use widget::Widget;
+use geometry::{Point, Size};
+use record::DisplayList;
+use input::mouse::{MouseMoved, MouseButton, MouseWheel, CursorLeftWindow};
+use input::keyboard::{KeyboardInput, ReceivedCharacter};
+use input::compose::Composition;
+use widgets::tooltip::AdvanceTooltips;
+
+impl App {
+    /// Attaches `root` to the window and handles the resulting events, allowing the layout to settle.
+    /// The equivalent of `main_loop` for a headless `App`.
+    pub fn set_root(&mut self, root: Widget) {
+        self.ui.root.add_child(root);
+        self.handle_events();
+    }
+
+    /// Resizes the virtual window of a headless `App`
+    pub fn resize_window(&mut self, size: Size) {
+        self.ui.window_resized(size);
+        self.handle_events();
+    }
+
+    /// Advances running transitions by `elapsed`, instead of waiting for frames to be drawn
+    pub fn advance_animations(&mut self, elapsed: Duration) {
+        self.ui.advance_animations(elapsed);
+        self.handle_events();
+    }
+
+    /// Advances the hover delay of tooltips by `elapsed`, instead of waiting for it to elapse
+    pub fn advance_tooltips(&mut self, elapsed: Duration) {
+        self.ui.get_root().event(AdvanceTooltips(elapsed));
+        self.handle_events();
+    }
+
+    /// Find the first widget with the given name, see `Ui::find_widget`
+    pub fn find_widget(&self, name: &str) -> Option<Widget> {
+        self.ui.find_widget(name)
+    }
+
+    /// Returns true if any widget has changed since the last frame was drawn
+    pub fn needs_redraw(&self) -> bool {
+        self.ui.needs_redraw()
+    }
+
+    /// Draws the UI, returning the recorded display list, which can be compared against
+    /// a snapshot using `record::assert_snapshot`
+    pub fn display_list(&mut self) -> DisplayList {
+        self.ui.record_display_list()
+    }
+
+    /// Simulates the mouse moving to `point`, in window coordinates
+    pub fn mouse_moved(&mut self, point: Point) {
+        self.ui.event(MouseMoved(point));
+        self.handle_events();
+    }
+
+    /// Simulates the mouse leaving the window
+    pub fn mouse_left(&mut self) {
+        self.ui.event(CursorLeftWindow);
+        self.handle_events();
+    }
+
+    /// Simulates a mouse button being pressed or released at the current mouse position
+    pub fn mouse_button(&mut self, state: ElementState, button: glutin::MouseButton) {
+        self.ui.event(MouseButton(state, button));
+        self.handle_events();
+    }
+
+    /// Simulates scrolling the mouse wheel at the current mouse position
+    pub fn mouse_wheel(&mut self, delta: glutin::MouseScrollDelta) {
+        self.ui.event(MouseWheel(delta));
+        self.handle_events();
+    }
+
+    /// Simulates moving the mouse to `point`, then pressing and releasing the left mouse button
+    pub fn click(&mut self, point: Point) {
+        self.mouse_moved(point);
+        self.mouse_button(ElementState::Pressed, glutin::MouseButton::Left);
+        self.mouse_button(ElementState::Released, glutin::MouseButton::Left);
+    }
+
+    /// Simulates dragging with the left mouse button from `start` to `end`
+    pub fn drag(&mut self, start: Point, end: Point) {
+        self.mouse_moved(start);
+        self.mouse_button(ElementState::Pressed, glutin::MouseButton::Left);
+        self.mouse_moved(end);
+        self.mouse_button(ElementState::Released, glutin::MouseButton::Left);
+    }
+
+    /// Simulates a single character being received, ie. after a key press
+    pub fn received_character(&mut self, char: char) {
+        self.ui.event(ReceivedCharacter(char));
+        self.handle_events();
+    }
+
+    /// Simulates typing `text`, one character at a time
+    pub fn type_text(&mut self, text: &str) {
+        for char in text.chars() {
+            self.received_character(char);
+        }
+    }
+
+    /// Simulates input from an input method, see `input::compose`
+    pub fn composition(&mut self, composition: Composition) {
+        self.ui.event(composition);
+        self.handle_events();
+    }
+
+    /// Simulates raw keyboard input, without any corresponding `ReceivedCharacter`
+    pub fn keyboard_input(&mut self, state: ElementState, key: VirtualKeyCode, modifiers: ModifiersState) {
+        let input = glutin::KeyboardInput {
+            scancode: 0,
+            state: state,
+            virtual_keycode: Some(key),
+            modifiers: modifiers,
+        };
+        self.ui.event(KeyboardInput(input));
+        self.handle_events();
+    }
+
+    /// Simulates pressing and releasing `key` with no modifiers held
+    pub fn key_press(&mut self, key: VirtualKeyCode) {
+        self.key_press_with(key, ModifiersState::default());
+    }
+
+    /// Simulates pressing and releasing `key` with the given modifiers held
+    pub fn key_press_with(&mut self, key: VirtualKeyCode, modifiers: ModifiersState) {
+        self.keyboard_input(ElementState::Pressed, key, modifiers);
+        self.keyboard_input(ElementState::Released, key, modifiers);
+    }
+}
@@ -0,0 +1,133 @@
+//! A generic undo/redo stack.
+//!
+//! Changes are represented by a `Command`, which when applied to some context returns the command
+//! that reverts it. `History` stores the inverse of each change made, so they can be undone,
+//! and the inverse of each undone change, so they can be redone, until a new change is made.
+//!
+//! Consecutive changes can be merged into a single undo step by implementing `Command::coalesce`,
+//! for example to undo text typed a word at a time, or a drag in a single step.
+//! `History::seal` ends the current step, so the next change is never merged into it.
+
+use event::{EventHandler, EventArgs};
+use ui::Ui;
+
+/// A reversible change to some `Context`
+pub trait Command: Sized {
+    type Context;
+    /// Applies the command, returning the command that reverts it
+    fn apply(self, context: &mut Self::Context) -> Self;
+    /// Called on the inverse of the last change, with the inverse of a newer change.
+    /// Returns true if `self` was updated to revert both changes, in which case `newer` is discarded.
+    fn coalesce(&mut self, _newer: &Self) -> bool {
+        false
+    }
+}
+
+/// Stack of changes that can be undone, and undone changes that can be redone
+pub struct History<C: Command> {
+    undo: Vec<C>,
+    redo: Vec<C>,
+    sealed: bool,
+}
+
+impl<C: Command> Default for History<C> {
+    fn default() -> Self {
+        History {
+            undo: Vec::new(),
+            redo: Vec::new(),
+            sealed: false,
+        }
+    }
+}
+
+impl<C: Command> History<C> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Applies `command` and records it, so it can be undone
+    pub fn apply(&mut self, command: C, context: &mut C::Context) {
+        let inverse = command.apply(context);
+        self.record(inverse);
+    }
+    /// Records a change that has already been made, given the command that reverts it.
+    /// Discards any changes that could be redone.
+    pub fn record(&mut self, inverse: C) {
+        self.redo.clear();
+        let coalesced = !self.sealed && match self.undo.last_mut() {
+            Some(last) => last.coalesce(&inverse),
+            None => false,
+        };
+        if !coalesced {
+            self.undo.push(inverse);
+        }
+        self.sealed = false;
+    }
+    /// Reverts the last change, returns false if there was nothing to undo
+    pub fn undo(&mut self, context: &mut C::Context) -> bool {
+        if let Some(command) = self.undo.pop() {
+            self.redo.push(command.apply(context));
+            self.sealed = true;
+            true
+        } else {
+            false
+        }
+    }
+    /// Reapplies the last undone change, returns false if there was nothing to redo
+    pub fn redo(&mut self, context: &mut C::Context) -> bool {
+        if let Some(command) = self.redo.pop() {
+            self.undo.push(command.apply(context));
+            self.sealed = true;
+            true
+        } else {
+            false
+        }
+    }
+    /// Prevents the next change from being coalesced with the last one
+    pub fn seal(&mut self) {
+        self.sealed = true;
+    }
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.sealed = false;
+    }
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+}
+
+/// Events handled by a `History` added to a widget, for commands that act on the `Ui`
+#[derive(Debug, Clone)]
+pub enum HistoryEvent<C> {
+    Apply(C),
+    Undo,
+    Redo,
+    Seal,
+    Clear,
+}
+
+/// Sent to the widget holding a `History` after it handles a `HistoryEvent`
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct HistoryUpdated {
+    pub can_undo: bool,
+    pub can_redo: bool,
+}
+
+impl<C: Command<Context = Ui> + Clone> EventHandler<HistoryEvent<C>> for History<C> {
+    fn handle(&mut self, event: &HistoryEvent<C>, args: EventArgs) {
+        match *event {
+            HistoryEvent::Apply(ref command) => self.apply(command.clone(), args.ui),
+            HistoryEvent::Undo => { self.undo(args.ui); }
+            HistoryEvent::Redo => { self.redo(args.ui); }
+            HistoryEvent::Seal => self.seal(),
+            HistoryEvent::Clear => self.clear(),
+        }
+        args.widget.event(HistoryUpdated {
+            can_undo: self.can_undo(),
+            can_redo: self.can_redo(),
+        });
+    }
+}
@@ -0,0 +1,115 @@
+//! Text composition, for input that takes more than one key press to produce, ie. CJK text
+//! entered using an input method, or accented chars entered using dead keys.
+//!
+//! While composing, the focused widget receives the pre-edit text in `Composition::Update` events,
+//! which it should display, but not treat as part of its value, until it receives a `Composition::Commit`.
+//! `glutin` doesn't expose input method events yet, so for now compositions are only produced by the
+//! `DeadKeyComposer`, enabled with `App::enable_dead_keys`, or sent directly as `Composition` events.
+
+use input::keyboard::KeyboardInputEvent;
+use app::App;
+
+/// A change to the text being composed, sent to the root widget to be forwarded to the focused widget.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Composition {
+    /// Composition has started, there is no pre-edit text yet
+    Start,
+    /// The pre-edit text has changed
+    Update(String),
+    /// Composition has finished, replace the pre-edit text with the committed text,
+    /// an empty string cancels the composition
+    Commit(String),
+}
+
+/// A `Composition` sent to the focused widget
+#[derive(Debug, Clone, PartialEq)]
+pub struct WidgetComposition(pub Composition);
+
+const BACKSPACE: char = '\u{8}';
+
+/// Composes accented chars from a dead key followed by a letter, like the US International keyboard layout,
+/// for systems that don't compose them already.
+///
+/// The dead keys are '`', '\'', '^', '~' and '"'. A dead key followed by a space or itself produces the dead key.
+#[derive(Debug, Default)]
+pub struct DeadKeyComposer {
+    dead_key: Option<char>,
+}
+
+impl DeadKeyComposer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns true if a dead key has been pressed, and is waiting for the next char
+    pub fn is_composing(&self) -> bool {
+        self.dead_key.is_some()
+    }
+
+    /// Feeds the next received char to the composer, returning the compositions it results in,
+    /// and the char, if it should still be handled as normal input
+    pub fn compose(&mut self, char: char) -> (Vec<Composition>, Option<char>) {
+        match self.dead_key.take() {
+            None => {
+                if is_dead_key(char) {
+                    self.dead_key = Some(char);
+                    (vec![Composition::Start, Composition::Update(char.to_string())], None)
+                } else {
+                    (Vec::new(), Some(char))
+                }
+            }
+            Some(dead_key) => {
+                if char == BACKSPACE {
+                    (vec![Composition::Commit(String::new())], None)
+                } else if char == ' ' || char == dead_key {
+                    (vec![Composition::Commit(dead_key.to_string())], None)
+                } else if let Some(composed) = combine(dead_key, char) {
+                    (vec![Composition::Commit(composed.to_string())], None)
+                } else if char.is_control() {
+                    (vec![Composition::Commit(dead_key.to_string())], Some(char))
+                } else {
+                    (vec![Composition::Commit(format!("{}{}", dead_key, char))], None)
+                }
+            }
+        }
+    }
+
+    /// Abandons any composition in progress, returning true if there was one
+    pub fn cancel(&mut self) -> bool {
+        self.dead_key.take().is_some()
+    }
+}
+
+fn is_dead_key(char: char) -> bool {
+    match char {
+        '`' | '\'' | '^' | '~' | '"' => true,
+        _ => false,
+    }
+}
+
+fn combine(dead_key: char, char: char) -> Option<char> {
+    let composed = match (dead_key, char) {
+        ('`', 'a') => 'à', ('`', 'e') => 'è', ('`', 'i') => 'ì', ('`', 'o') => 'ò', ('`', 'u') => 'ù',
+        ('`', 'A') => 'À', ('`', 'E') => 'È', ('`', 'I') => 'Ì', ('`', 'O') => 'Ò', ('`', 'U') => 'Ù',
+        ('\'', 'a') => 'á', ('\'', 'e') => 'é', ('\'', 'i') => 'í', ('\'', 'o') => 'ó', ('\'', 'u') => 'ú',
+        ('\'', 'y') => 'ý', ('\'', 'c') => 'ç',
+        ('\'', 'A') => 'Á', ('\'', 'E') => 'É', ('\'', 'I') => 'Í', ('\'', 'O') => 'Ó', ('\'', 'U') => 'Ú',
+        ('\'', 'Y') => 'Ý', ('\'', 'C') => 'Ç',
+        ('^', 'a') => 'â', ('^', 'e') => 'ê', ('^', 'i') => 'î', ('^', 'o') => 'ô', ('^', 'u') => 'û',
+        ('^', 'A') => 'Â', ('^', 'E') => 'Ê', ('^', 'I') => 'Î', ('^', 'O') => 'Ô', ('^', 'U') => 'Û',
+        ('~', 'a') => 'ã', ('~', 'o') => 'õ', ('~', 'n') => 'ñ',
+        ('~', 'A') => 'Ã', ('~', 'O') => 'Õ', ('~', 'N') => 'Ñ',
+        ('"', 'a') => 'ä', ('"', 'e') => 'ë', ('"', 'i') => 'ï', ('"', 'o') => 'ö', ('"', 'u') => 'ü',
+        ('"', 'y') => 'ÿ',
+        ('"', 'A') => 'Ä', ('"', 'E') => 'Ë', ('"', 'I') => 'Ï', ('"', 'O') => 'Ö', ('"', 'U') => 'Ü',
+        _ => return None,
+    };
+    Some(composed)
+}
+
+impl App {
+    /// Compose accented chars from dead keys typed as separate chars, see `DeadKeyComposer`
+    pub fn enable_dead_keys(&mut self) {
+        self.get_root().event(KeyboardInputEvent::EnableDeadKeys(true));
+    }
+}
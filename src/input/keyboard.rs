@@ -2,13 +2,13 @@
 
 use std::collections::HashMap;
 
-use stable_bst::map::TreeMap;
-use stable_bst::Bound::{Excluded, Unbounded};
-
 use widget::Widget;
 use widget::property::Property;
 use input::mouse::ClickEvent;
-use event::{EventHandler, EventArgs};
+use input::compose::{Composition, WidgetComposition, DeadKeyComposer};
+use input::shortcuts::{self, KeyChord};
+use event::{self, EventHandler, EventArgs, Target};
+use ui::{WidgetAttachedEvent, WidgetDetachedEvent};
 use app::App;
 
 use glutin;
@@ -23,93 +23,240 @@ pub struct WidgetKeyboardInput(pub glutin::KeyboardInput);
 pub struct WidgetReceivedCharacter(pub char);
 
 
+/// Sent to a widget when it receives keyboard focus
+#[derive(Debug, Copy, Clone)]
+pub struct FocusGained;
+/// Sent to a widget when it loses keyboard focus
+#[derive(Debug, Copy, Clone)]
+pub struct FocusLost;
+
 /// Note on focus:
-/// The tab key iterates through the widgets that have registered as focusable.
-/// Currently the order of this iteration is just based on the order the widgets
-/// are registered as focusable.
-/// Later on maybe it should be based on the relative positioning of widgets (could get
-/// ugly updating the treemap as widgets change position), or some user defined ordering.
+/// Tab and Shift-Tab iterate through the widgets that have registered as focusable,
+/// ordered by their tab index, then the order they were registered as focusable.
+/// Widgets with a negative tab index can only be focused by clicking them.
+/// Widgets that are `Inactive`, or hidden, can't be focused.
+///
+/// While a focus scope is active, ie. for a modal dialog, focus is trapped within the
+/// subtree of the widget that started the scope.
+///
+/// Key presses are matched against the `Ui`'s keyboard shortcuts before being sent to the
+/// focused widget, see `input::shortcuts`. The character received for a key press consumed by a
+/// shortcut isn't sent to the focused widget either.
 #[derive(Default)]
 pub struct FocusHandler {
-    focusable_map: HashMap<Widget, usize>,
-    // can replace TreeMap with std BTreeMap once the range API or similar is stable
-    focusable: TreeMap<usize, Widget>,
+    /// Focusable widgets in the order they were registered
+    focusable: Vec<Widget>,
+    tab_indices: HashMap<Widget, i32>,
     focused: Option<Widget>,
-    focus_index_max: usize,
+    scopes: Vec<FocusScope>,
+    composer: Option<DeadKeyComposer>,
+    /// The last key press was consumed by a shortcut, the character it produces is dropped
+    suppress_char: bool,
+}
+
+struct FocusScope {
+    widget: Widget,
+    /// Focus before the scope started, restored when it ends
+    previous_focus: Option<Widget>,
 }
+
 impl FocusHandler {
     pub fn new() -> Self {
         Self::default()
     }
     fn set_focus(&mut self, new_focus: Option<Widget>) {
         if new_focus != self.focused {
+            let composing = self.composer.as_mut().map_or(false, |composer| composer.cancel());
             if let Some(ref mut focused) = self.focused {
+                if composing {
+                    focused.event_subtree(WidgetComposition(Composition::Commit(String::new())));
+                }
                 focused.remove_prop(Property::Focused);
+                focused.event(FocusLost);
             }
             self.focused = new_focus;
             if let Some(ref mut focused) = self.focused {
                 focused.add_prop(Property::Focused);
+                focused.event(FocusGained);
             }
         }
     }
+    /// Returns true if `widget` can receive focus, in the current focus scope
+    fn can_focus(&self, widget: &Widget) -> bool {
+        let in_scope = self.scopes.last().map_or(true, |scope| is_descendant(widget, &scope.widget));
+        in_scope && !widget.props().contains(&Property::Inactive) && !widget.is_hidden()
+    }
+    /// The widgets that can be reached with the tab key, in order
+    fn tab_order(&self) -> Vec<Widget> {
+        let mut widgets: Vec<(i32, usize, Widget)> = self.focusable.iter().enumerate()
+            .map(|(order, widget)| (self.tab_index(widget), order, widget.clone()))
+            .filter(|&(tab_index, _, ref widget)| tab_index >= 0 && self.can_focus(widget))
+            .collect();
+        widgets.sort_by_key(|&(tab_index, order, _)| (tab_index, order));
+        widgets.into_iter().map(|(_, _, widget)| widget).collect()
+    }
+    fn tab_index(&self, widget: &Widget) -> i32 {
+        self.tab_indices.get(widget).cloned().unwrap_or(0)
+    }
+    /// Moves focus to the next widget in the tab order, or the previous if `reverse` is set, wrapping around
+    fn move_focus(&mut self, reverse: bool) {
+        let order = self.tab_order();
+        if order.is_empty() {
+            return;
+        }
+        let len = order.len();
+        let position = self.focused.as_ref().and_then(|focused| order.iter().position(|widget| widget == focused));
+        let next = match position {
+            Some(index) if reverse => (index + len - 1) % len,
+            Some(index) => (index + 1) % len,
+            None if reverse => len - 1,
+            None => 0,
+        };
+        self.set_focus(Some(order[next].clone()));
+    }
 }
 impl EventHandler<KeyboardInputEvent> for FocusHandler {
-    fn handle(&mut self, event: &KeyboardInputEvent, _: EventArgs) {
+    fn handle(&mut self, event: &KeyboardInputEvent, args: EventArgs) {
         match *event {
-            KeyboardInputEvent::AddFocusable(ref widget_id) => {
-                self.focusable.insert(self.focus_index_max, widget_id.clone());
-                self.focusable_map.insert(widget_id.clone(), self.focus_index_max);
-                self.focus_index_max += 1;
-                if self.focused.is_none() {
-                    self.set_focus(Some(widget_id.clone()));
+            KeyboardInputEvent::AddFocusable(ref widget) => {
+                if !self.focusable.contains(widget) {
+                    self.focusable.push(widget.clone());
+                }
+                if self.focused.is_none() && self.can_focus(widget) {
+                    self.set_focus(Some(widget.clone()));
                 }
             }
-            KeyboardInputEvent::RemoveFocusable(ref widget_id) => {
-                if let Some(focused) = self.focused.clone() {
-                    if focused == *widget_id {
-                        self.set_focus(None);
-                    }
+            KeyboardInputEvent::RemoveFocusable(ref widget) => {
+                if self.focused.as_ref() == Some(widget) {
+                    self.set_focus(None);
                 }
-                let index = self.focusable_map.remove(widget_id).unwrap();
-                self.focusable.remove(&index);
+                self.focusable.retain(|focusable| focusable != widget);
+                self.tab_indices.remove(widget);
             }
             KeyboardInputEvent::FocusChange(ref new_focus) => {
-                self.set_focus(new_focus.clone());
+                let allowed = new_focus.as_ref().map_or(true, |widget| self.can_focus(widget));
+                if allowed {
+                    self.set_focus(new_focus.clone());
+                }
+            }
+            KeyboardInputEvent::SetTabIndex(ref widget, tab_index) => {
+                self.tab_indices.insert(widget.clone(), tab_index);
+            }
+            KeyboardInputEvent::PushFocusScope(ref widget) => {
+                self.scopes.push(FocusScope {
+                    widget: widget.clone(),
+                    previous_focus: self.focused.clone(),
+                });
+                let focus_in_scope = self.focused.as_ref().map_or(false, |focused| self.can_focus(focused));
+                if !focus_in_scope {
+                    let new_focus = self.tab_order().into_iter().next();
+                    self.set_focus(new_focus);
+                }
+            }
+            KeyboardInputEvent::PopFocusScope(ref widget) => {
+                if let Some(index) = self.scopes.iter().position(|scope| scope.widget == *widget) {
+                    let scope = self.scopes.remove(index);
+                    if index == self.scopes.len() {
+                        let previous_focus = scope.previous_focus.and_then(|previous| {
+                            if self.focusable.contains(&previous) && self.can_focus(&previous) {
+                                Some(previous)
+                            } else {
+                                None
+                            }
+                        });
+                        self.set_focus(previous_focus);
+                    }
+                }
             }
             KeyboardInputEvent::KeyboardInput(ref key_input) => {
-                if let Some(ref focused) = self.focused {
-                    let &KeyboardInput(input) = key_input;
+                let &KeyboardInput(input) = key_input;
+                if let (glutin::ElementState::Pressed, Some(key)) = (input.state, input.virtual_keycode) {
+                    if !shortcuts::is_modifier_key(key) {
+                        let chord = KeyChord {
+                            key: key,
+                            modifiers: input.modifiers.into(),
+                        };
+                        let root = args.ui.get_root();
+                        let modal = args.ui.modal_dialog();
+                        self.suppress_char = args.ui.shortcuts().key_pressed(chord, self.focused.as_ref(), &root, modal.as_ref());
+                        if self.suppress_char {
+                            return;
+                        }
+                    }
+                }
+                if input.virtual_keycode == Some(glutin::VirtualKeyCode::Tab) && !input.modifiers.ctrl && !input.modifiers.alt {
+                    if input.state == glutin::ElementState::Pressed {
+                        self.move_focus(input.modifiers.shift);
+                    }
+                } else if let Some(ref focused) = self.focused {
                     let event = WidgetKeyboardInput(input);
                     focused.event_subtree(event);
                 }
             }
             KeyboardInputEvent::ReceivedCharacter(ref received_char) => {
                 let &ReceivedCharacter(char) = received_char;
-                if char == '\t' {
-                    let mut new_focus = self.focused.clone().and_then(|focused| {
-                        let index = &self.focusable_map[&focused];
-                        self.focusable.range(Excluded(index), Unbounded).next().map(|(_, v)| v.clone())
-                    });
-                    if new_focus.is_none() {
-                        // focus on first, if any
-                        new_focus = self.focusable.iter().next().map(|(_, v)| v.clone());
+                if self.suppress_char {
+                    self.suppress_char = false;
+                    return;
+                }
+                let char = match self.composer {
+                    Some(ref mut composer) => {
+                        let (compositions, char) = composer.compose(char);
+                        if let Some(ref focused) = self.focused {
+                            for composition in compositions {
+                                focused.event_subtree(WidgetComposition(composition));
+                            }
+                        }
+                        match char {
+                            Some(char) => char,
+                            None => return,
+                        }
                     }
-                    self.set_focus(new_focus);
-                } else if let Some(ref focused) = self.focused {
-                    let event = WidgetReceivedCharacter(char);
-                    focused.event_subtree(event);
+                    None => char,
+                };
+                // tab is handled as keyboard input
+                if char != '\t' {
+                    if let Some(ref focused) = self.focused {
+                        let event = WidgetReceivedCharacter(char);
+                        focused.event_subtree(event);
+                    }
+                }
+            }
+            KeyboardInputEvent::Composition(ref composition) => {
+                if let Some(ref focused) = self.focused {
+                    focused.event_subtree(WidgetComposition(composition.clone()));
                 }
             }
+            KeyboardInputEvent::EnableDeadKeys(enable) => {
+                self.composer = if enable { Some(DeadKeyComposer::new()) } else { None };
+            }
+        }
+    }
+}
+
+/// Returns true if `widget` is `ancestor`, or one of it's descendants
+pub(crate) fn is_descendant(widget: &Widget, ancestor: &Widget) -> bool {
+    let mut widget = Some(widget.clone());
+    while let Some(current) = widget {
+        if current == *ancestor {
+            return true;
         }
+        widget = current.parent();
     }
+    false
 }
 
 pub enum KeyboardInputEvent {
     AddFocusable(Widget),
     RemoveFocusable(Widget),
     FocusChange(Option<Widget>),
+    SetTabIndex(Widget, i32),
+    PushFocusScope(Widget),
+    PopFocusScope(Widget),
     KeyboardInput(KeyboardInput),
     ReceivedCharacter(ReceivedCharacter),
+    Composition(Composition),
+    EnableDeadKeys(bool),
 }
 
 impl Widget {
@@ -118,6 +265,31 @@ impl Widget {
             args.ui.get_root().event(KeyboardInputEvent::FocusChange(Some(args.widget)));
         })
     }
+    /// Adds the widget to the tab order while it's attached, and lets it be focused by clicking it
+    pub(crate) fn enable_focus(&mut self) -> &mut Self {
+        self.add_handler(|_: &WidgetAttachedEvent, args: EventArgs| {
+            args.ui.event(KeyboardInputEvent::AddFocusable(args.widget));
+        })
+        .add_handler(|_: &WidgetDetachedEvent, args: EventArgs| {
+            args.ui.event(KeyboardInputEvent::RemoveFocusable(args.widget));
+        })
+        .make_focusable()
+    }
+    /// Sets the position of the widget in the tab order, lower indices are focused first,
+    /// a negative index removes the widget from the tab order, the default is 0
+    pub fn set_tab_index(&mut self, tab_index: i32) -> &mut Self {
+        event::event(Target::Root, KeyboardInputEvent::SetTabIndex(self.clone(), tab_index));
+        self
+    }
+    /// Traps focus within this widget's subtree, until `pop_focus_scope` is called, moving focus
+    /// to the first focusable widget in the subtree if focus is outside it
+    pub fn push_focus_scope(&self) {
+        event::event(Target::Root, KeyboardInputEvent::PushFocusScope(self.clone()));
+    }
+    /// Ends the focus scope started by `push_focus_scope`, restoring the previous focus
+    pub fn pop_focus_scope(&self) {
+        event::event(Target::Root, KeyboardInputEvent::PopFocusScope(self.clone()));
+    }
 }
 
 impl App {
@@ -128,6 +300,9 @@ impl App {
         self.add_handler(|event: &ReceivedCharacter, args: EventArgs| {
             args.widget.event(KeyboardInputEvent::ReceivedCharacter(*event));
         });
+        self.add_handler(|event: &Composition, args: EventArgs| {
+            args.widget.event(KeyboardInputEvent::Composition(event.clone()));
+        });
         self.add_handler(FocusHandler::new());
     }
 }
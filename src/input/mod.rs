@@ -3,6 +3,8 @@
 
 pub mod mouse;
 pub mod keyboard;
+pub mod compose;
+pub mod shortcuts;
 pub mod drag;
 
 use glutin;
@@ -13,6 +15,7 @@ use event::{EventHandler, EventArgs};
 use input::mouse::{MouseMoved, MouseButton, MouseWheel, CursorLeftWindow};
 use input::keyboard::{KeyboardInput, ReceivedCharacter};
 use geometry::Point;
+use inspector::ToggleInspector;
 use app::App;
 
 #[derive(Clone)]
@@ -97,8 +100,19 @@ impl EventHandler<KeyboardInput> for DebugSettingsHandler {
                 Some(glutin::VirtualKeyCode::F2) => ui.solver.debug_constraints(),
                 Some(glutin::VirtualKeyCode::F3) => ui.debug_widget_positions(),
                 Some(glutin::VirtualKeyCode::F4) => ui.solver.debug_variables(),
-                Some(glutin::VirtualKeyCode::F5) => ui.render.toggle_flags(webrender::DebugFlags::PROFILER_DBG),
+                Some(glutin::VirtualKeyCode::F5) => {
+                    if let Some(ref mut render) = ui.render {
+                        render.toggle_flags(webrender::DebugFlags::PROFILER_DBG);
+                    }
+                },
                 Some(glutin::VirtualKeyCode::F6) => ui.print_widgets(),
+                Some(glutin::VirtualKeyCode::F7) => ui.event(ToggleInspector),
+                Some(glutin::VirtualKeyCode::F8) => {
+                    match ui.write_layout_graph("layout_graph.json") {
+                        Ok(()) => println!("Wrote layout graph to layout_graph.json"),
+                        Err(err) => println!("Failed to write layout graph: {}", err),
+                    }
+                },
                 _ => {}
             }
         }
@@ -32,6 +32,8 @@ pub struct ClickEvent {
 struct MouseController {
     pub mouse: Option<Point>,
     pub widget_under_mouse: Option<Widget>,
+    /// A button whose press closed popups, it's release is ignored too
+    dismissed_button: Option<glutin::MouseButton>,
 }
 
 impl MouseController {
@@ -55,16 +57,28 @@ impl MouseController {
     fn mouse_moved(&mut self, event: &MouseMoved, args: EventArgs) {
         let &MouseMoved(mouse) = event;
         self.mouse = Some(mouse);
+        args.ui.cursor_position = Some(mouse);
         self.check_widget_under_cursor(args);
     }
 
     fn mouse_left(&mut self, _: &CursorLeftWindow, args: EventArgs) {
         self.mouse = None;
+        args.ui.cursor_position = None;
         self.check_widget_under_cursor(args);
     }
 
-    fn mouse_button(&mut self, event: &MouseButton, _: EventArgs) {
+    fn mouse_button(&mut self, event: &MouseButton, args: EventArgs) {
         let &MouseButton(state, button) = event;
+        // a press outside of the open popups closes them, instead of reaching the widget under the mouse
+        if state == glutin::ElementState::Pressed {
+            if args.ui.dismiss_popups(self.widget_under_mouse.as_ref()) {
+                self.dismissed_button = Some(button);
+                return;
+            }
+        } else if self.dismissed_button == Some(button) {
+            self.dismissed_button = None;
+            return;
+        }
         if let Some(ref widget_under) = self.widget_under_mouse {
             widget_under.event_bubble_up(WidgetMouseButton(state, button));
             if (state == glutin::ElementState::Released) && (button == glutin::MouseButton::Left) && self.mouse.is_some() {
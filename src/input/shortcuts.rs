@@ -0,0 +1,295 @@
+//! Keyboard shortcuts, mapping key chords, or sequences of chords, to events.
+//!
+//! Shortcuts are registered with the `Shortcuts` registry of the `Ui`, see `App::shortcuts`,
+//! and are matched by the `FocusHandler` before keyboard input is sent to the focused widget.
+//! A shortcut is either global, sent to the root widget, or scoped to a widget, in which case it's
+//! only active while that widget or one of it's descendants has focus, and is sent to that widget.
+//! While a modal dialog is open, only shortcuts scoped to widgets within the dialog are active.
+//!
+//! ```no_run
+//! # extern crate limn;
+//! # use limn::prelude::*;
+//! # use limn::glutin::VirtualKeyCode;
+//! use limn::input::shortcuts::{KeyChord, ShortcutScope};
+//! # fn main() {
+//! #[derive(Clone)]
+//! struct Save;
+//! let mut app = App::new_headless(Size::new(300.0, 300.0));
+//! app.shortcuts().register(KeyChord::new(VirtualKeyCode::S).ctrl(), ShortcutScope::Global, Save).unwrap();
+//! app.shortcuts().register(vec![
+//!     KeyChord::new(VirtualKeyCode::K).ctrl(),
+//!     KeyChord::new(VirtualKeyCode::S).ctrl(),
+//! ], ShortcutScope::Global, Save).unwrap();
+//! app.add_handler(|_: &Save, _: EventArgs| println!("saving"));
+//! # }
+//! ```
+
+use std::fmt;
+
+use glutin::{ModifiersState, VirtualKeyCode};
+
+use widget::Widget;
+use input::keyboard::is_descendant;
+use app::App;
+
+/// Modifier keys that are held as part of a `KeyChord`
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Modifiers {
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+    pub logo: bool,
+}
+
+impl From<ModifiersState> for Modifiers {
+    fn from(modifiers: ModifiersState) -> Self {
+        Modifiers {
+            ctrl: modifiers.ctrl,
+            shift: modifiers.shift,
+            alt: modifiers.alt,
+            logo: modifiers.logo,
+        }
+    }
+}
+
+/// A key pressed with a set of modifiers held, ie. Ctrl+S
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    pub key: VirtualKeyCode,
+    pub modifiers: Modifiers,
+}
+
+impl KeyChord {
+    pub fn new(key: VirtualKeyCode) -> Self {
+        KeyChord {
+            key: key,
+            modifiers: Modifiers::default(),
+        }
+    }
+    pub fn ctrl(mut self) -> Self {
+        self.modifiers.ctrl = true;
+        self
+    }
+    pub fn shift(mut self) -> Self {
+        self.modifiers.shift = true;
+        self
+    }
+    pub fn alt(mut self) -> Self {
+        self.modifiers.alt = true;
+        self
+    }
+    pub fn logo(mut self) -> Self {
+        self.modifiers.logo = true;
+        self
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.modifiers.ctrl {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.alt {
+            write!(f, "Alt+")?;
+        }
+        if self.modifiers.shift {
+            write!(f, "Shift+")?;
+        }
+        if self.modifiers.logo {
+            write!(f, "Logo+")?;
+        }
+        write!(f, "{:?}", self.key)
+    }
+}
+
+/// One or more chords that have to be pressed in order to trigger a shortcut, ie. Ctrl+K Ctrl+S
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeySequence(pub Vec<KeyChord>);
+
+impl From<KeyChord> for KeySequence {
+    fn from(chord: KeyChord) -> Self {
+        KeySequence(vec![chord])
+    }
+}
+
+impl From<Vec<KeyChord>> for KeySequence {
+    fn from(chords: Vec<KeyChord>) -> Self {
+        KeySequence(chords)
+    }
+}
+
+impl fmt::Display for KeySequence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (index, chord) in self.0.iter().enumerate() {
+            if index > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}", chord)?;
+        }
+        Ok(())
+    }
+}
+
+/// Where a shortcut is active
+#[derive(Debug, Clone, PartialEq)]
+pub enum ShortcutScope {
+    /// Always active, the event is sent to the root widget
+    Global,
+    /// Active while the widget or any of it's descendants has focus, the event is sent to the widget.
+    /// Takes precedence over global shortcuts, and shortcuts of the widget's ancestors, including
+    /// a shorter sequence of an ancestor that one of the widget's sequences starts with.
+    Focused(Widget),
+}
+
+/// Returned when registering a shortcut that can't be distinguished from an existing shortcut in
+/// the same scope, because one sequence is the same as, or starts with, the other
+#[derive(Debug, Fail)]
+#[fail(display = "shortcut {} conflicts with existing shortcut {}", sequence, existing)]
+pub struct ShortcutConflict {
+    pub sequence: KeySequence,
+    pub existing: KeySequence,
+}
+
+struct Shortcut {
+    sequence: KeySequence,
+    scope: ShortcutScope,
+    action: Box<Fn(&Widget)>,
+}
+
+/// The registry of keyboard shortcuts for a `Ui`
+#[derive(Default)]
+pub struct Shortcuts {
+    shortcuts: Vec<Shortcut>,
+    /// Chords pressed so far that match the start of a sequence
+    pending: Vec<KeyChord>,
+}
+
+impl Shortcuts {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a shortcut that sends `event` when `sequence` is pressed while `scope` is active
+    pub fn register<S: Into<KeySequence>, E: Clone + 'static>(&mut self, sequence: S, scope: ShortcutScope, event: E) -> Result<(), ShortcutConflict> {
+        let sequence = sequence.into();
+        for shortcut in &self.shortcuts {
+            if shortcut.scope == scope && (shortcut.sequence.0.starts_with(&sequence.0) || sequence.0.starts_with(&shortcut.sequence.0)) {
+                return Err(ShortcutConflict {
+                    sequence: sequence,
+                    existing: shortcut.sequence.clone(),
+                });
+            }
+        }
+        self.shortcuts.push(Shortcut {
+            sequence: sequence,
+            scope: scope,
+            action: Box::new(move |widget| widget.event(event.clone())),
+        });
+        Ok(())
+    }
+
+    /// Removes the shortcut for `sequence` in `scope`, returning true if there was one
+    pub fn remove<S: Into<KeySequence>>(&mut self, sequence: S, scope: &ShortcutScope) -> bool {
+        let sequence = sequence.into();
+        let len = self.shortcuts.len();
+        self.shortcuts.retain(|shortcut| shortcut.sequence != sequence || shortcut.scope != *scope);
+        self.shortcuts.len() != len
+    }
+
+    /// Removes all the shortcuts scoped to `widget`
+    pub fn remove_scope(&mut self, widget: &Widget) {
+        let scope = ShortcutScope::Focused(widget.clone());
+        self.shortcuts.retain(|shortcut| shortcut.scope != scope);
+    }
+
+    /// Called for each key press, returns true if the key press was consumed by a shortcut,
+    /// either triggering it, or as part of a sequence that hasn't been completed yet.
+    /// If `modal` is set, shortcuts that aren't scoped to one of it's descendants are ignored.
+    pub(crate) fn key_pressed(&mut self, chord: KeyChord, focused: Option<&Widget>, root: &Widget, modal: Option<&Widget>) -> bool {
+        self.pending.push(chord);
+        let (exact, partial) = {
+            let mut exact: Option<(usize, &Shortcut)> = None;
+            // the depth of the most specific scope with a longer sequence starting with the pending chords
+            let mut partial: Option<usize> = None;
+            for shortcut in &self.shortcuts {
+                let in_modal = modal.map_or(true, |modal| match shortcut.scope {
+                    ShortcutScope::Global => false,
+                    ShortcutScope::Focused(ref widget) => is_descendant(widget, modal),
+                });
+                if !in_modal {
+                    continue;
+                }
+                if let Some(depth) = scope_depth(&shortcut.scope, focused) {
+                    if shortcut.sequence.0 == self.pending {
+                        if exact.map_or(true, |(exact_depth, _)| depth < exact_depth) {
+                            exact = Some((depth, shortcut));
+                        }
+                    } else if shortcut.sequence.0.starts_with(&self.pending) {
+                        partial = Some(partial.map_or(depth, |partial| partial.min(depth)));
+                    }
+                }
+            }
+            // a sequence in a more specific scope takes precedence over a shorter one it starts with
+            let exact = exact.and_then(|(depth, shortcut)| {
+                if partial.map_or(true, |partial| depth <= partial) { Some(shortcut) } else { None }
+            });
+            if let Some(shortcut) = exact {
+                match shortcut.scope {
+                    ShortcutScope::Global => (shortcut.action)(root),
+                    ShortcutScope::Focused(ref widget) => (shortcut.action)(widget),
+                }
+            }
+            (exact.is_some(), partial.is_some())
+        };
+        if exact {
+            self.pending.clear();
+            true
+        } else if partial {
+            true
+        } else if self.pending.len() > 1 {
+            // the sequence was broken, the chord could still start a new sequence
+            self.pending.clear();
+            self.key_pressed(chord, focused, root, modal)
+        } else {
+            self.pending.clear();
+            false
+        }
+    }
+}
+
+/// How many ancestors up from the focused widget the scope is, if it's active, global shortcuts are the furthest
+fn scope_depth(scope: &ShortcutScope, focused: Option<&Widget>) -> Option<usize> {
+    match *scope {
+        ShortcutScope::Global => Some(usize::max_value()),
+        ShortcutScope::Focused(ref scope_widget) => {
+            let mut depth = 0;
+            let mut widget = focused.cloned();
+            while let Some(current) = widget {
+                if current == *scope_widget {
+                    return Some(depth);
+                }
+                depth += 1;
+                widget = current.parent();
+            }
+            None
+        }
+    }
+}
+
+/// Returns true for keys that are only ever pressed as modifiers
+pub(crate) fn is_modifier_key(key: VirtualKeyCode) -> bool {
+    match key {
+        VirtualKeyCode::LControl | VirtualKeyCode::RControl |
+        VirtualKeyCode::LShift | VirtualKeyCode::RShift |
+        VirtualKeyCode::LAlt | VirtualKeyCode::RAlt |
+        VirtualKeyCode::LWin | VirtualKeyCode::RWin => true,
+        _ => false,
+    }
+}
+
+impl App {
+    /// The keyboard shortcuts of the app
+    pub fn shortcuts(&mut self) -> &mut Shortcuts {
+        self.ui.shortcuts()
+    }
+}
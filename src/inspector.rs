@@ -0,0 +1,179 @@
+//! In-app layout inspector, and export of the layout graph.
+//!
+//! While the inspector is enabled, toggled with `ToggleInspector` (F7 with the `DebugSettingsHandler`),
+//! the widget under the cursor is outlined, and a panel shows it's position in the widget tree,
+//! bounds, props, draw state, and the constraints and edit variables of it's layout.
+//! The overlay ignores the mouse, so the UI can still be used while inspecting it.
+//!
+//! `Ui::layout_graph_json` exports the whole layout graph as JSON, for analysis outside of the app
+//! (F8 with the `DebugSettingsHandler` writes it to `layout_graph.json`).
+
+use std::fs::File;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+
+use event::{EventHandler, EventArgs};
+use widget::Widget;
+use widgets::text::StaticTextStyle;
+use widgets::edit_text::TextUpdated;
+use draw::rect::RectStyle;
+use draw::text::TextStyle;
+use input::mouse::MouseMoved;
+use layout::stack_layout::Anchor;
+use layout::absolute_layout::Position;
+use ui::{Ui, INSPECTOR_Z_INDEX};
+use app::App;
+use color::*;
+
+/// Enables or disables the inspector
+#[derive(Debug, Copy, Clone)]
+pub struct ToggleInspector;
+
+/// Widgets drawn over the UI while the inspector is enabled
+struct InspectorOverlay {
+    overlay: Widget,
+    highlight: Widget,
+    info: Widget,
+}
+
+impl InspectorOverlay {
+    fn new() -> Self {
+        let mut overlay = Widget::new("inspector");
+        overlay.set_z_index(INSPECTOR_Z_INDEX);
+        overlay.absolute_layout();
+
+        let mut highlight = Widget::new("inspector_highlight");
+        highlight.set_draw_style(style!(RectStyle {
+            background_color: TRANSPARENT,
+            border: Some((2.0, RED)),
+        }));
+        highlight.layout().hide();
+
+        let text_style = style!(TextStyle {
+            text: String::new(),
+            font_size: 14.0,
+            background_color: WHITE,
+        });
+        let mut info = Widget::from_modifier_style(StaticTextStyle::from_style(text_style));
+        info.set_name("inspector_info");
+        info.layout().set_item_props(Position::new(0.0, 0.0).anchor(Anchor::BottomLeft));
+        info.layout().hide();
+
+        for widget in &mut [&mut overlay, &mut highlight, &mut info] {
+            widget.set_cursor_hit_fn(|_, _| false);
+        }
+        overlay.add_child(highlight.clone());
+        overlay.add_child(info.clone());
+        InspectorOverlay {
+            overlay: overlay,
+            highlight: highlight,
+            info: info,
+        }
+    }
+
+    fn inspect(&mut self, ui: &Ui, widget: &Widget) {
+        let bounds = widget.bounds();
+        {
+            let mut layout = self.highlight.layout();
+            layout.set_offset(bounds.origin.x, bounds.origin.y);
+            layout.edit_width().set(bounds.size.width);
+            layout.edit_height().set(bounds.size.height);
+            layout.show();
+        }
+        self.info.layout().show();
+        self.info.event(TextUpdated(describe_widget(ui, widget)));
+    }
+}
+
+#[derive(Default)]
+struct Inspector {
+    overlay: Option<InspectorOverlay>,
+    inspected: Option<Widget>,
+}
+
+impl Inspector {
+    fn toggle(&mut self, _: &ToggleInspector, args: EventArgs) {
+        if let Some(overlay) = self.overlay.take() {
+            overlay.overlay.clone().remove_widget();
+            self.inspected = None;
+        } else {
+            let overlay = InspectorOverlay::new();
+            args.ui.root.add_child(overlay.overlay.clone());
+            self.overlay = Some(overlay);
+        }
+    }
+
+    fn mouse_moved(&mut self, event: &MouseMoved, args: EventArgs) {
+        if let Some(ref mut overlay) = self.overlay {
+            let widget = args.ui.widget_under_cursor(event.0);
+            if widget != self.inspected {
+                if let Some(ref widget) = widget {
+                    overlay.inspect(args.ui, widget);
+                }
+                self.inspected = widget;
+            }
+        }
+    }
+}
+
+multi_event!{impl EventHandler<InspectorEvent> for Inspector {
+    ToggleInspector => toggle,
+    MouseMoved => mouse_moved,
+}}
+
+/// A readable description of `widget`, it's layout, and it's place in the widget tree,
+/// as shown by the inspector
+pub fn describe_widget(ui: &Ui, widget: &Widget) -> String {
+    let mut path = vec![widget.name()];
+    let mut parent = widget.parent();
+    while let Some(ancestor) = parent {
+        path.push(ancestor.name());
+        parent = ancestor.parent();
+    }
+    path.reverse();
+
+    let bounds = widget.bounds();
+    let props: Vec<String> = widget.props().iter().map(|prop| format!("{:?}", prop)).collect();
+    let draw_state = match widget.widget().draw_state.state {
+        Some(ref state) => format!("{:?}", state),
+        None => "none".to_owned(),
+    };
+    let mut lines = vec![
+        path.join(" > "),
+        format!("bounds: {}, {} {}x{}", bounds.origin.x, bounds.origin.y, bounds.size.width, bounds.size.height),
+        format!("z-index: {}", widget.z_index()),
+        format!("props: {}", props.join(", ")),
+        format!("draw state: {}", draw_state),
+        "constraints:".to_owned(),
+    ];
+    let id = widget.id().0;
+    for constraint in ui.solver.layout_constraints(id) {
+        lines.push(format!("    {}", constraint));
+    }
+    lines.push("edit vars:".to_owned());
+    for edit_var in ui.solver.layout_edit_vars(id) {
+        lines.push(format!("    {}", edit_var));
+    }
+    lines.join("\n")
+}
+
+impl Ui {
+    /// The layout graph serialized as JSON, see `LimnSolver::layout_graph_json`
+    pub fn layout_graph_json(&self) -> String {
+        self.solver.layout_graph_json()
+    }
+
+    /// Writes the layout graph as JSON to the file at `path`
+    pub fn write_layout_graph<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut file = File::create(path)?;
+        file.write_all(self.layout_graph_json().as_bytes())
+    }
+}
+
+impl App {
+    pub fn add_inspector_handlers(&mut self) {
+        self.add_handler(Inspector::default());
+        Inspector::add_adapters(&mut self.get_root());
+    }
+}
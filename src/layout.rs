@@ -1,36 +1,165 @@
 use std::ops::DerefMut;
+use std::collections::HashSet;
+
+use cassowary::Constraint;
+use cassowary::strength::*;
+use cassowary::WeightedRelation::*;
 
 use limn_layout::linear_layout::{LinearLayout, LinearLayoutSettings};
-use limn_layout::grid_layout::GridLayout;
+use limn_layout::grid_layout::{GridLayout, GridLayoutSettings};
+use limn_layout::flow_layout::{FlowLayout, FlowLayoutSettings};
+use limn_layout::stack_layout::{StackLayout, StackLayoutSettings};
+use limn_layout::absolute_layout::AbsoluteLayout;
 
 use resources::WidgetId;
 
 use app::App;
 
-use widget::Widget;
-use event::EventArgs;
+use widget::{Widget, StateUpdated};
+use widget::draw::SizeHint;
+use event::{EventHandler, EventArgs};
 
 pub use self::solver::LimnSolver;
 pub use limn_layout::*;
 
+/// How many times in a row containers can reflow before the layout is left as it is,
+/// in case a container's size depends on how it reflows, and it never settles
+pub(crate) const MAX_REFLOW_PASSES: usize = 10;
+
 impl Widget {
     /// Set this widgets container to be a `LinearLayout`.
     /// Children added to this widget will be arranged along one axis without overlapping.
     pub fn linear_layout(&mut self, settings: LinearLayoutSettings) -> &mut Self {
-        let container = LinearLayout::new(self.layout().deref_mut(), settings);
-        self.layout().set_container(container);
-        self
+        self.replace_container(|layout| LinearLayout::new(layout, settings))
     }
 
     /// Set this widgets container to be a `GridLayout`.
     /// Children added to this widget will be arranged in a grid.
     pub fn grid(&mut self, num_columns: usize) -> &mut Self {
-        let container = GridLayout::new(self.layout().deref_mut(), num_columns);
-        self.layout().set_container(container);
+        self.replace_container(|layout| GridLayout::new(layout, num_columns))
+    }
+
+    /// Set this widgets container to be a `GridLayout` with explicit track sizes and gaps.
+    /// Children are placed in the cell set with `GridCell`, or the next free cell.
+    pub fn grid_layout(&mut self, settings: GridLayoutSettings) -> &mut Self {
+        self.replace_container(|layout| GridLayout::with_settings(layout, settings))
+    }
+
+    /// Set this widgets container to be a `FlowLayout`.
+    /// Children added to this widget will be arranged in rows, wrapping when a row is full.
+    pub fn flow_layout(&mut self, settings: FlowLayoutSettings) -> &mut Self {
+        self.replace_container(|layout| FlowLayout::new(layout, settings))
+    }
+
+    /// Set this widgets container to be a `StackLayout`.
+    /// Children added to this widget will be layered on top of each other.
+    pub fn stack_layout(&mut self, settings: StackLayoutSettings) -> &mut Self {
+        self.replace_container(|layout| StackLayout::new(layout, settings))
+    }
+
+    /// Set this widgets container to be an `AbsoluteLayout`.
+    /// Children added to this widget will be placed at the offset set with `Position`.
+    pub fn absolute_layout(&mut self) -> &mut Self {
+        self.replace_container(|layout| AbsoluteLayout::new(layout))
+    }
+
+    /// Replaces the container of this widget, and adds the existing children to the new container,
+    /// so the container can be changed after the children are added, see `Layout::replace_container`
+    fn replace_container<T, F>(&mut self, build: F) -> &mut Self
+        where T: LayoutContainer + 'static, F: FnOnce(&mut Layout) -> T
+    {
+        self.layout().replace_container(build);
+        for mut child in self.children() {
+            self.layout().add_child(child.layout().deref_mut());
+        }
+        self
+    }
+
+    /// Constrain the size of this widget to fit it's content, if it's draw state implements `Measure`.
+    /// The constraints are updated when the draw state changes, or, for content with a height
+    /// for width, like wrapped text, when the width of the widget changes.
+    pub fn size_to_content(&mut self) -> &mut Self {
+        self.add_handler(MeasureHandler::default());
+        MeasureHandler::add_adapters(self);
+        self.event(StateUpdated);
         self
     }
 }
 
+multi_event!{impl EventHandler<MeasureEvent> for MeasureHandler {
+    StateUpdated => state_updated,
+    LayoutUpdated => layout_updated,
+}}
+
+#[derive(Default)]
+struct MeasureHandler {
+    /// The width the content was last measured at
+    width: f32,
+    measured: Option<(SizeHint, Option<f32>)>,
+    constraints: Vec<Constraint>,
+}
+
+impl MeasureHandler {
+    fn state_updated(&mut self, _: &StateUpdated, args: EventArgs) {
+        self.update(args, true);
+    }
+    fn layout_updated(&mut self, _: &LayoutUpdated, args: EventArgs) {
+        self.update(args, false);
+    }
+    fn update(&mut self, mut args: EventArgs, state_updated: bool) {
+        let vars = args.widget.layout_vars();
+        let width = args.ui.solver.get_value(vars.width) as f32;
+        if !state_updated && width == self.width {
+            return;
+        }
+        self.width = width;
+        let measured = {
+            let draw_state = args.widget.draw_state();
+            draw_state.measure().map(|measure| {
+                let hint = measure.size_hint();
+                // wrap at the preferred width until the widget is laid out
+                let width = if width > 0.0 { width } else { hint.preferred.width };
+                (hint, measure.height_for_width(width))
+            })
+        };
+        if measured != self.measured {
+            let constraints = match measured {
+                Some((hint, height)) => size_hint_constraints(&vars, hint, height),
+                None => Vec::new(),
+            };
+            let mut layout = args.widget.layout();
+            for constraint in self.constraints.drain(..) {
+                layout.remove_constraint(constraint);
+            }
+            layout.add(constraints.clone());
+            self.constraints = constraints;
+            self.measured = measured;
+        }
+    }
+}
+
+/// The content can't be smaller than the minimum or larger than the maximum size,
+/// and prefers it's preferred size, or the height for it's width if it has one
+fn size_hint_constraints(vars: &LayoutVars, hint: SizeHint, height: Option<f32>) -> Vec<Constraint> {
+    let mut constraints = vec![
+        vars.width | GE(REQUIRED) | hint.min.width,
+        vars.height | GE(REQUIRED) | hint.min.height,
+        vars.width | EQ(STRONG) | hint.preferred.width,
+    ];
+    match height {
+        Some(height) => {
+            constraints.push(vars.height | GE(REQUIRED) | height);
+            constraints.push(vars.height | EQ(STRONG) | height);
+        }
+        None => constraints.push(vars.height | EQ(STRONG) | hint.preferred.height),
+    }
+    if let Some(max) = hint.max {
+        constraints.push(vars.width | LE(REQUIRED) | max.width);
+        constraints.push(vars.height | LE(REQUIRED) | max.height);
+    }
+    constraints
+}
+
 #[derive(Clone)]
 pub struct UpdateLayout(pub Widget);
 #[derive(Debug, Copy, Clone)]
@@ -45,21 +174,49 @@ impl App {
         self.add_handler(|_: &ResizeWindow, args: EventArgs| {
             args.ui.resize_window_to_fit();
         });
+        self.add_handler(|error: &LayoutError, _: EventArgs| {
+            warn!("{}", error);
+        });
         self.add_handler(|event: &UpdateLayout, args: EventArgs| {
-            let event = event.clone();
-            let UpdateLayout(widget_ref) = event;
-            let mut widget = widget_ref.widget_mut();
-            args.ui.solver.update_layout(&mut widget.layout);
-            args.ui.check_layout_changes();
+            let UpdateLayout(ref widget_ref) = *event;
+            args.ui.dirty_layouts.push(widget_ref.clone());
         });
         self.add_handler(|event: &LayoutChanged, args: EventArgs| {
             let changes = &event.0;
+            let mut changed = HashSet::new();
             for &(widget_id, var, value) in changes {
                 let widget_id = WidgetId(widget_id);
                 if let Some(mut widget) = args.ui.get_widget(widget_id) {
                     debug!("{:?}: {:?} = {}", widget.name(), var, value);
-                    widget.update_bounds(var, value as f32);
+                    match widget.transition() {
+                        Some(transition) => args.ui.transition_bounds(widget.clone(), transition, var, value as f32),
+                        None => widget.update_bounds(var, value as f32),
+                    }
+                    if let Some(parent) = widget.parent() {
+                        changed.insert(parent);
+                    }
+                    changed.insert(widget);
+                }
+            }
+            // some containers, like `FlowLayout`, update their constraints based on the solved layout
+            if args.ui.reflow_passes < MAX_REFLOW_PASSES {
+                let mut reflowed = false;
+                for widget in changed {
+                    let mut widget = widget.widget_mut();
+                    if widget.layout.reflow(&args.ui.solver) {
+                        args.ui.solver.update_layout(&mut widget.layout);
+                        reflowed = true;
+                    }
+                }
+                if reflowed {
+                    args.ui.reflow_passes += 1;
+                    args.ui.check_layout_changes();
+                } else {
+                    args.ui.reflow_passes = 0;
                 }
+            } else {
+                warn!("layout didn't settle after {} reflows, a container's size may depend on it's own reflow", MAX_REFLOW_PASSES);
+                args.ui.reflow_passes = 0;
             }
             // redraw everything when layout changes, for now
             args.ui.redraw();
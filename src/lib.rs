@@ -62,7 +62,6 @@ pub extern crate glutin;
 pub extern crate webrender;
 extern crate euclid;
 extern crate linked_hash_map;
-extern crate stable_bst;
 extern crate gleam;
 extern crate app_units;
 extern crate image;
@@ -80,6 +79,7 @@ pub mod widget;
 pub mod layout;
 
 pub mod app;
+pub mod headless;
 pub mod widgets;
 pub mod draw;
 pub mod ui;
@@ -88,8 +88,16 @@ pub mod geometry;
 pub mod resources;
 pub mod color;
 pub mod input;
+pub mod clipboard;
+pub mod history;
+pub mod inspector;
+pub mod animation;
+pub mod responsive;
+pub mod popup;
+pub mod dialog;
 pub mod prelude;
 pub mod render;
+pub mod record;
 /// Wrapper around `glutin::Window`
 pub mod window;
 
@@ -0,0 +1,116 @@
+//! Popups, widgets that float above the rest of the UI, ie. the list of a dropdown, or a menu.
+//!
+//! Popups are added to a layer drawn over the widget tree, and have first crack at mouse input:
+//! pressing a mouse button outside of the open popups closes them, without the press reaching the
+//! widget under the mouse. Pressing escape closes the most recently opened popup.
+//! A popup is opened for an owner, usually the widget that opened it, presses within the owner
+//! don't close the popup, so the owner can toggle it.
+
+use glutin;
+
+use event::EventArgs;
+use widget::Widget;
+use input::keyboard::{KeyboardInput, is_descendant};
+use layout::constraint::*;
+use ui::{Ui, POPUP_Z_INDEX};
+use app::App;
+
+/// Sent to a popup, and it's owner, when the popup is closed
+#[derive(Debug, Clone)]
+pub struct PopupClosed(pub Widget);
+
+pub(crate) struct Popup {
+    widget: Widget,
+    owner: Widget,
+}
+
+impl Ui {
+    /// Opens `popup` above every other widget, on behalf of `owner`, which can be the popup itself
+    /// if presses outside of the popup should always close it.
+    /// The popup is bound by the window, constraints relative to `owner`, or any other widget,
+    /// can be added to position it.
+    pub fn open_popup(&mut self, owner: &Widget, mut popup: Widget) {
+        let mut layer = self.popup_layer();
+        popup.layout().add(bound_by(&layer));
+        layer.add_child(popup.clone());
+        self.popups.push(Popup {
+            widget: popup,
+            owner: owner.clone(),
+        });
+    }
+
+    /// Closes `popup`, and any popups opened after it, ie. it's submenus
+    pub fn close_popup(&mut self, popup: &Widget) {
+        if let Some(index) = self.popups.iter().position(|open| open.widget == *popup) {
+            self.close_popups_from(index);
+        }
+    }
+
+    /// Closes every open popup
+    pub fn close_popups(&mut self) {
+        self.close_popups_from(0);
+    }
+
+    /// Returns true if `popup` is open
+    pub fn is_popup_open(&self, popup: &Widget) -> bool {
+        self.popups.iter().any(|open| open.widget == *popup)
+    }
+
+    /// Closes the popups that neither contain `widget`, or have an owner that contains it,
+    /// or are below a popup that does. Returns true if any popups were closed.
+    pub(crate) fn dismiss_popups(&mut self, widget: Option<&Widget>) -> bool {
+        let keep = match widget {
+            Some(widget) => self.popups.iter().rposition(|open| {
+                is_descendant(widget, &open.widget) || is_descendant(widget, &open.owner)
+            }).map_or(0, |index| index + 1),
+            None => 0,
+        };
+        let dismissed = keep < self.popups.len();
+        self.close_popups_from(keep);
+        dismissed
+    }
+
+    fn close_popups_from(&mut self, index: usize) {
+        for Popup { mut widget, owner } in self.popups.drain(index..).rev() {
+            widget.remove_widget();
+            widget.event(PopupClosed(widget.clone()));
+            if owner != widget {
+                owner.event(PopupClosed(widget.clone()));
+            }
+        }
+        // the layer is only in the widget tree while there are popups
+        if self.popups.is_empty() {
+            if let Some(mut layer) = self.popup_layer.take() {
+                layer.remove_widget();
+            }
+        }
+    }
+
+    fn popup_layer(&mut self) -> Widget {
+        if let Some(ref layer) = self.popup_layer {
+            return layer.clone();
+        }
+        let mut layer = Widget::new("popup_layer");
+        layer
+            .set_z_index(POPUP_Z_INDEX)
+            .set_cursor_hit_fn(|_, _| false);
+        layer.layout().no_container();
+        self.root.add_child(layer.clone());
+        self.popup_layer = Some(layer.clone());
+        layer
+    }
+}
+
+impl App {
+    pub fn add_popup_handlers(&mut self) {
+        self.add_handler(|event: &KeyboardInput, args: EventArgs| {
+            let &KeyboardInput(input) = event;
+            if input.state == glutin::ElementState::Pressed && input.virtual_keycode == Some(glutin::VirtualKeyCode::Escape) {
+                let top = args.ui.popups.last().map(|open| open.widget.clone());
+                if let Some(popup) = top {
+                    args.ui.close_popup(&popup);
+                }
+            }
+        });
+    }
+}
@@ -21,13 +21,19 @@ pub use resources::id::{Id, IdGen};
 pub use resources::image::ImageSource;
 pub use ui::Ui;
 pub use app::{App, FrameEvent};
+pub use animation::{Transition, Easing};
+pub use responsive::{Breakpoint, WindowResized};
 pub use window::Window;
 pub use color::*;
 // re exports macros in limn-layout
 pub use layout::*;
 pub use layout::constraint::*;
 pub use layout::LAYOUT;
-pub use layout::linear_layout::{LinearLayoutSettings, Orientation, Spacing, ItemAlignment};
+pub use layout::linear_layout::{LinearLayoutSettings, Orientation, Spacing, ItemAlignment, FlexItem};
+pub use layout::flow_layout::{FlowLayoutSettings, FlowAlignment};
+pub use layout::grid_layout::{GridLayoutSettings, GridCell, Track};
+pub use layout::stack_layout::{StackLayoutSettings, Anchor};
+pub use layout::absolute_layout::Position;
 pub use text_layout::{Align, Wrap};
 
 // Re-export macros
@@ -0,0 +1,240 @@
+//! Software recording of display lists, for testing rendering without a GPU.
+//!
+//! When a `RenderBuilder` has a `DisplayListRecorder`, every primitive pushed through the
+//! `RenderBuilder` methods is also recorded as a `DisplayItem`. The recorded `DisplayList`
+//! is a tree, where clips and stacking contexts contain the items drawn inside them, and has a
+//! stable text serialization, so it can be compared against a golden snapshot with `assert_snapshot`.
+
+use std::fmt;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::env;
+
+use geometry::{Rect, Point, Size};
+use color::Color;
+
+/// A single recorded primitive, or a group of primitives
+#[derive(Debug, Clone, PartialEq)]
+pub enum DisplayItem {
+    Rect {
+        rect: Rect,
+        color: Color,
+        /// Rounded clip applied to the rect, the clip rect and corner radius
+        clip: Option<(Rect, Size)>,
+    },
+    Border {
+        rect: Rect,
+        width: f32,
+        color: Color,
+    },
+    Text {
+        rect: Rect,
+        font: String,
+        font_size: f32,
+        color: Color,
+        /// Glyph indices and positions
+        glyphs: Vec<(u32, Point)>,
+    },
+    Image {
+        rect: Rect,
+        name: String,
+    },
+    Clip {
+        rect: Rect,
+        items: Vec<DisplayItem>,
+    },
+    StackingContext {
+        opacity: Option<f32>,
+        /// The 2D components of the transform, if any
+        transform: Option<[f32; 6]>,
+        items: Vec<DisplayItem>,
+    },
+}
+
+/// The tree of items recorded while drawing a frame
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DisplayList {
+    pub items: Vec<DisplayItem>,
+}
+
+/// Records display items as they are pushed, see `RenderBuilder`
+#[derive(Debug, Default)]
+pub struct DisplayListRecorder {
+    /// Stack of unfinished groups, the root of the list is at the bottom.
+    stack: Vec<(DisplayItem, Vec<DisplayItem>)>,
+    items: Vec<DisplayItem>,
+}
+
+impl DisplayListRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn push(&mut self, item: DisplayItem) {
+        self.current().push(item);
+    }
+    /// Start a group (a `Clip` or `StackingContext`), subsequent items are added to this group until it is popped
+    pub fn push_group(&mut self, group: DisplayItem) {
+        self.stack.push((group, Vec::new()));
+    }
+    pub fn pop_group(&mut self) {
+        if let Some((mut group, children)) = self.stack.pop() {
+            match group {
+                DisplayItem::Clip { ref mut items, .. } | DisplayItem::StackingContext { ref mut items, .. } => {
+                    *items = children;
+                }
+                _ => (),
+            }
+            self.current().push(group);
+        } else {
+            error!("Popped display list group with no matching push");
+        }
+    }
+    /// Finishes recording, closing any groups that haven't been popped
+    pub fn finish(mut self) -> DisplayList {
+        while !self.stack.is_empty() {
+            self.pop_group();
+        }
+        DisplayList { items: self.items }
+    }
+    fn current(&mut self) -> &mut Vec<DisplayItem> {
+        if let Some(&mut (_, ref mut items)) = self.stack.last_mut() {
+            items
+        } else {
+            &mut self.items
+        }
+    }
+}
+
+fn fmt_rect(rect: &Rect) -> String {
+    format!("[{:.1} {:.1} {:.1} {:.1}]", rect.origin.x, rect.origin.y, rect.size.width, rect.size.height)
+}
+
+fn fmt_color(color: &Color) -> String {
+    format!("#{:08X}", color.0)
+}
+
+impl DisplayItem {
+    fn fmt_indented(&self, f: &mut fmt::Formatter, indent: usize) -> fmt::Result {
+        let pad = "  ".repeat(indent);
+        match *self {
+            DisplayItem::Rect { ref rect, ref color, ref clip } => {
+                write!(f, "{}rect {} {}", pad, fmt_rect(rect), fmt_color(color))?;
+                if let Some((ref clip_rect, ref radius)) = *clip {
+                    write!(f, " clip {} radius {:.1} {:.1}", fmt_rect(clip_rect), radius.width, radius.height)?;
+                }
+                writeln!(f)
+            }
+            DisplayItem::Border { ref rect, width, ref color } => {
+                writeln!(f, "{}border {} {:.1} {}", pad, fmt_rect(rect), width, fmt_color(color))
+            }
+            DisplayItem::Text { ref rect, ref font, font_size, ref color, ref glyphs } => {
+                writeln!(f, "{}text {} \"{}\" {:.1} {}", pad, fmt_rect(rect), font, font_size, fmt_color(color))?;
+                let glyphs: Vec<String> = glyphs.iter().map(|&(index, point)| {
+                    format!("{}@{:.1},{:.1}", index, point.x, point.y)
+                }).collect();
+                writeln!(f, "{}  glyphs {}", pad, glyphs.join(" "))
+            }
+            DisplayItem::Image { ref rect, ref name } => {
+                writeln!(f, "{}image {} {}", pad, fmt_rect(rect), name)
+            }
+            DisplayItem::Clip { ref rect, ref items } => {
+                writeln!(f, "{}clip {}", pad, fmt_rect(rect))?;
+                for item in items {
+                    item.fmt_indented(f, indent + 1)?;
+                }
+                Ok(())
+            }
+            DisplayItem::StackingContext { opacity, ref transform, ref items } => {
+                write!(f, "{}stacking_context", pad)?;
+                if let Some(opacity) = opacity {
+                    write!(f, " opacity {:.2}", opacity)?;
+                }
+                if let Some(ref transform) = *transform {
+                    let values: Vec<String> = transform.iter().map(|value| format!("{:.2}", value)).collect();
+                    write!(f, " transform [{}]", values.join(" "))?;
+                }
+                writeln!(f)?;
+                for item in items {
+                    item.fmt_indented(f, indent + 1)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Serializes the display list, one item per line, with the contents of groups indented
+impl fmt::Display for DisplayList {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for item in &self.items {
+            item.fmt_indented(f, 0)?;
+        }
+        Ok(())
+    }
+}
+
+/// Compares the serialized `DisplayList` against the snapshot stored in `tests/snapshots/<name>.snap`,
+/// relative to the current directory, panicking with a line diff if they differ.
+///
+/// If the `LIMN_UPDATE_SNAPSHOTS` environment variable is set, the snapshot is written instead.
+/// A missing snapshot is an error otherwise, so a snapshot that was never committed can't pass silently.
+pub fn assert_snapshot(name: &str, display_list: &DisplayList) {
+    let mut path = PathBuf::from("tests/snapshots");
+    path.push(format!("{}.snap", name));
+    let actual = display_list.to_string();
+    if env::var_os("LIMN_UPDATE_SNAPSHOTS").is_some() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::File::create(&path).and_then(|mut file| file.write_all(actual.as_bytes())).unwrap();
+        return;
+    }
+    let expected = fs::File::open(&path).and_then(|mut file| {
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)?;
+        Ok(contents)
+    });
+    match expected {
+        Ok(expected) => {
+            if expected != actual {
+                panic!("display list doesn't match snapshot {:?}, set LIMN_UPDATE_SNAPSHOTS=1 to update it\n{}",
+                    path, diff_lines(&expected, &actual));
+            }
+        }
+        Err(_) => panic!("snapshot missing: {:?}, set LIMN_UPDATE_SNAPSHOTS=1 to create it\n{}", path, actual),
+    }
+}
+
+/// Line based diff of `expected` and `actual`, using the longest common subsequence of lines.
+/// Removed lines are prefixed with '-', added lines with '+'.
+fn diff_lines(expected: &str, actual: &str) -> String {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+    let (n, m) = (expected.len(), actual.len());
+    // lcs[i][j] is the length of the lcs of expected[i..] and actual[j..]
+    let mut lcs = vec![vec![0; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if expected[i] == actual[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+    let mut diff = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && expected[i] == actual[j] {
+            diff.push_str(&format!("  {}\n", expected[i]));
+            i += 1;
+            j += 1;
+        } else if j < m && (i == n || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            diff.push_str(&format!("+ {}\n", actual[j]));
+            j += 1;
+        } else {
+            diff.push_str(&format!("- {}\n", expected[i]));
+            i += 1;
+        }
+    }
+    diff
+}
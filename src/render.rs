@@ -11,7 +11,10 @@ use webrender::api::*;
 use window::Window;
 use euclid::TypedPoint2D;
 use resources;
+use resources::font::FontDescriptor;
 use geometry::{Rect, Point, Size};
+use color::Color;
+use record::{DisplayItem, DisplayListRecorder};
 
 // Provides access to the WebRender context and API
 pub(super) struct WebRenderContext {
@@ -32,6 +35,104 @@ pub(super) struct WebRenderContext {
 pub struct RenderBuilder {
     pub builder: DisplayListBuilder,
     pub resources: ResourceUpdates,
+    /// If set, records every primitive pushed using the `RenderBuilder` methods
+    pub recorder: Option<DisplayListRecorder>,
+}
+
+/// Wrappers around the `DisplayListBuilder` methods used by limn, prefer these to using the builder directly
+/// so that the primitives can be recorded.
+impl RenderBuilder {
+    /// Creates a `RenderBuilder` that isn't attached to a renderer, the display list is only recorded
+    pub fn new_recording(window_size: LayoutSize) -> Self {
+        RenderBuilder {
+            builder: DisplayListBuilder::new(PipelineId(0, 0), window_size),
+            resources: ResourceUpdates::new(),
+            recorder: Some(DisplayListRecorder::new()),
+        }
+    }
+    fn record(&mut self, item: DisplayItem) {
+        if let Some(ref mut recorder) = self.recorder {
+            recorder.push(item);
+        }
+    }
+    pub fn push_rect(&mut self, info: &PrimitiveInfo<LayerPixel>, color: Color) {
+        self.builder.push_rect(info, color.into());
+        let clip = match info.local_clip {
+            LocalClip::RoundedRect(_, ref region) => Some((region.rect, region.radii.top_left)),
+            LocalClip::Rect(_) => None,
+        };
+        self.record(DisplayItem::Rect { rect: info.rect, color: color, clip: clip });
+    }
+    pub fn push_border(&mut self, info: &PrimitiveInfo<LayerPixel>, widths: BorderWidths, details: BorderDetails) {
+        let color = match details {
+            BorderDetails::Normal(ref border) => Color::from(border.left.color),
+            _ => Color::default(),
+        };
+        self.builder.push_border(info, widths, details);
+        self.record(DisplayItem::Border { rect: info.rect, width: widths.left, color: color });
+    }
+    pub fn push_text(&mut self, info: &PrimitiveInfo<LayerPixel>, glyphs: &[GlyphInstance], font: &FontDescriptor, font_size: f32, color: Color) {
+        let key = *resources::resources().font_loader.get_font_instance(font, font_size).unwrap();
+        self.builder.push_text(info, glyphs, key, color.into(), None);
+        let glyphs = glyphs.iter().map(|glyph| (glyph.index, Point::new(glyph.point.x, glyph.point.y))).collect();
+        self.record(DisplayItem::Text {
+            rect: info.rect,
+            font: font.family_name.clone(),
+            font_size: font_size,
+            color: color,
+            glyphs: glyphs,
+        });
+    }
+    /// Push an image, `name` is used to identify the image in recordings
+    pub fn push_image(&mut self, info: &PrimitiveInfo<LayerPixel>, key: ImageKey, name: &str) {
+        self.builder.push_image(
+            info,
+            info.rect.size,
+            LayoutSize::zero(),
+            ImageRendering::Auto,
+            AlphaType::Alpha,
+            key,
+        );
+        self.record(DisplayItem::Image { rect: info.rect, name: name.to_owned() });
+    }
+    /// Clip everything drawn until the matching `pop_clip` to `rect`
+    pub fn push_clip(&mut self, rect: Rect) {
+        let clip_id = self.builder.define_clip(rect, vec![], None);
+        self.builder.push_clip_id(clip_id);
+        if let Some(ref mut recorder) = self.recorder {
+            recorder.push_group(DisplayItem::Clip { rect: rect, items: Vec::new() });
+        }
+    }
+    pub fn pop_clip(&mut self) {
+        self.builder.pop_clip_id();
+        if let Some(ref mut recorder) = self.recorder {
+            recorder.pop_group();
+        }
+    }
+    /// Push a stacking context with an optional opacity and transform, applied to everything drawn
+    /// until the matching `pop_stacking_context`
+    pub fn push_stacking_context(&mut self, opacity: Option<f32>, transform: Option<LayoutTransform>) {
+        let filters = opacity.map(|alpha| vec![FilterOp::Opacity(PropertyBinding::Value(alpha), alpha)]).unwrap_or_default();
+        self.builder.push_stacking_context(
+            &PrimitiveInfo::new(Rect::zero()),
+            ScrollPolicy::Fixed,
+            transform.map(PropertyBinding::Value),
+            TransformStyle::Flat,
+            None,
+            MixBlendMode::Normal,
+            filters,
+        );
+        if let Some(ref mut recorder) = self.recorder {
+            let transform = transform.map(|t| [t.m11, t.m12, t.m21, t.m22, t.m41, t.m42]);
+            recorder.push_group(DisplayItem::StackingContext { opacity: opacity, transform: transform, items: Vec::new() });
+        }
+    }
+    pub fn pop_stacking_context(&mut self) {
+        self.builder.pop_stacking_context();
+        if let Some(ref mut recorder) = self.recorder {
+            recorder.pop_group();
+        }
+    }
 }
 
 impl WebRenderContext {
@@ -85,6 +186,7 @@ impl WebRenderContext {
         RenderBuilder {
             builder: builder,
             resources: ResourceUpdates::new(),
+            recorder: None,
         }
     }
     pub fn set_display_list(&mut self, builder: DisplayListBuilder, resources: ResourceUpdates, window_size: LayoutSize) {
@@ -160,7 +262,7 @@ pub fn draw_rect_outline<C: Into<ColorF>>(rect: Rect, color: C, renderer: &mut R
     let border = NormalBorder { left: side, right: side, top: side, bottom: side, radius: BorderRadius::zero() };
     let details = BorderDetails::Normal(border);
     let info = PrimitiveInfo::new(rect);
-    renderer.builder.push_border(&info, widths, details);
+    renderer.push_border(&info, widths, details);
 }
 
 pub fn draw_horizontal_line<C: Into<ColorF>>(baseline: f32, start: f32, end: f32, color: C, renderer: &mut RenderBuilder) {
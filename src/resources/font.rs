@@ -5,7 +5,7 @@ use failure::Error;
 use rusttype;
 use font_loader::system_fonts::{self, FontProperty, FontPropertyBuilder};
 use app_units;
-use webrender::api::{RenderApi, ResourceUpdates, FontKey, FontInstanceKey};
+use webrender::api::{RenderApi, ResourceUpdates, FontKey, FontInstanceKey, IdNamespace};
 
 use text_layout;
 
@@ -49,6 +49,8 @@ pub struct FontLoader {
     pub font_info: HashMap<FontDescriptor, FontInfo>,
     pub bundled_font_info: HashMap<FontDescriptor, FontInfo>,
     pub font_instances: HashMap<(FontDescriptor, app_units::Au), FontInstanceKey>,
+    /// Used to generate placeholder keys when there is no renderer, ie. for a headless `App`
+    headless_key: u32,
 }
 
 impl FontLoader {
@@ -77,7 +79,11 @@ impl FontLoader {
         let size = app_units::Au::from_f32_px(text_layout::px_to_pt(font_size));
         let key = (descriptor.clone(), size);
         if !self.font_instances.contains_key(&key) {
-            let instance_key = webrender_load_font_instance(self.render_api(), font_key, size);
+            let instance_key = if self.render.is_some() {
+                webrender_load_font_instance(self.render_api(), font_key, size)
+            } else {
+                FontInstanceKey(IdNamespace(0), self.next_headless_key())
+            };
             self.font_instances.insert(key.clone(), instance_key);
         }
         Ok(&self.font_instances[&key])
@@ -85,7 +91,11 @@ impl FontLoader {
 
     fn load_font(&mut self, data: Vec<u8>) -> Result<FontInfo, Error> {
         let font_info = rusttype_load_font_info(data.clone())?;
-        let key = webrender_load_font(self.render_api(), data)?;
+        let key = if self.render.is_some() {
+            webrender_load_font(self.render_api(), data)?
+        } else {
+            FontKey(IdNamespace(0), self.next_headless_key())
+        };
         Ok(FontInfo { key: key, info: font_info })
     }
 
@@ -98,6 +108,11 @@ impl FontLoader {
     fn render_api(&self) -> &RenderApi {
         self.render.as_ref().unwrap()
     }
+
+    fn next_headless_key(&mut self) -> u32 {
+        self.headless_key += 1;
+        self.headless_key
+    }
 }
 
 fn webrender_load_font(render_api: &RenderApi, data: Vec<u8>) -> Result<FontKey, io::Error> {
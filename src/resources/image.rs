@@ -3,7 +3,7 @@ use std::path::PathBuf;
 
 use failure::Error;
 
-use webrender::api::{RenderApi, ResourceUpdates, ExternalImageId, ExternalImageData, ImageKey, ImageFormat, ImageData, ImageDescriptor};
+use webrender::api::{RenderApi, ResourceUpdates, IdNamespace, ExternalImageId, ExternalImageData, ImageKey, ImageFormat, ImageData, ImageDescriptor};
 use image::{self, ImageError, DynamicImage, GenericImage};
 
 #[derive(PartialEq, Eq, Hash, Debug, Clone)]
@@ -44,6 +44,8 @@ pub struct ImageLoader {
     pub bundled_images: HashMap<ImageSource, ImageInfo>,
     pub images: HashMap<ImageSource, ImageInfo>,
     pub texture_descriptors: HashMap<u64, ImageDescriptor>,
+    /// Used to generate placeholder keys when there is no renderer, ie. for a headless `App`
+    headless_key: u32,
 }
 
 impl ImageLoader {
@@ -88,6 +90,10 @@ impl ImageLoader {
     }
 
     pub fn create_image_resource(&mut self, data: ImageData, descriptor: ImageDescriptor) -> ImageInfo {
+        if self.render.is_none() {
+            self.headless_key += 1;
+            return ImageInfo { key: ImageKey(IdNamespace(0), self.headless_key), descriptor: descriptor };
+        }
         let key = self.render_api().generate_image_key();
         let mut resources = ResourceUpdates::new();
         resources.add_image(key, descriptor, data, None);
@@ -96,9 +102,11 @@ impl ImageLoader {
     }
 
     pub fn update_texture(&mut self, key: ImageKey, descriptor: ImageDescriptor, data: ExternalImageData) {
-        let mut resources = ResourceUpdates::new();
-        resources.update_image(key, descriptor, ImageData::External(data), None);
-        self.render_api().update_resources(resources);
+        if let Some(ref render) = self.render {
+            let mut resources = ResourceUpdates::new();
+            resources.update_image(key, descriptor, ImageData::External(data), None);
+            render.update_resources(resources);
+        }
         let ExternalImageData { id: ExternalImageId(texture_id), .. } = data;
         self.texture_descriptors.insert(texture_id, descriptor);
     }
@@ -0,0 +1,112 @@
+//! Responsive layouts, that change when the window size crosses a breakpoint.
+//!
+//! A widget can add constraints, or change it's container, depending on the size of the window,
+//! ie. a row of panels can become a column in narrow windows, and a sidebar can collapse.
+//! Breakpoints are checked when they're added, and whenever the window is resized.
+//!
+//! ```no_run
+//! # use limn::prelude::*;
+//! # let mut panels = Widget::new("panels");
+//! # let mut sidebar = Widget::new("sidebar");
+//! panels.add_breakpoint(Breakpoint::MaxWidth(600.0), |panels, narrow| {
+//!     let orientation = if narrow { Orientation::Vertical } else { Orientation::Horizontal };
+//!     panels.linear_layout(LinearLayoutSettings::new(orientation));
+//! });
+//! sidebar.breakpoint_constraints(Breakpoint::MaxWidth(600.0), width(0.0));
+//! ```
+
+use cassowary::Constraint;
+
+use event::{self, EventHandler, EventArgs, Target};
+use widget::Widget;
+use layout::constraint::ConstraintBuilder;
+use geometry::Size;
+use app::App;
+
+/// A condition on the size of the window
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Breakpoint {
+    /// The window is narrower than the given width
+    MaxWidth(f32),
+    /// The window is at least as wide as the given width
+    MinWidth(f32),
+    /// The window is shorter than the given height
+    MaxHeight(f32),
+    /// The window is at least as tall as the given height
+    MinHeight(f32),
+}
+
+impl Breakpoint {
+    /// Returns true if the breakpoint applies to a window of the given size
+    pub fn matches(&self, window_size: Size) -> bool {
+        match *self {
+            Breakpoint::MaxWidth(width) => window_size.width < width,
+            Breakpoint::MinWidth(width) => window_size.width >= width,
+            Breakpoint::MaxHeight(height) => window_size.height < height,
+            Breakpoint::MinHeight(height) => window_size.height >= height,
+        }
+    }
+}
+
+/// Sent to every widget when the window is resized
+#[derive(Debug, Copy, Clone)]
+pub struct WindowResized(pub Size);
+
+/// Checks the breakpoints of a widget against the current window size
+struct CheckBreakpoints(Widget);
+
+struct BreakpointHandler {
+    breakpoint: Breakpoint,
+    active: Option<bool>,
+    apply: Box<FnMut(&mut Widget, bool)>,
+}
+
+impl EventHandler<WindowResized> for BreakpointHandler {
+    fn handle(&mut self, event: &WindowResized, mut args: EventArgs) {
+        let active = self.breakpoint.matches(event.0);
+        if self.active != Some(active) {
+            self.active = Some(active);
+            (self.apply)(&mut args.widget, active);
+        }
+    }
+}
+
+impl Widget {
+    /// Calls `apply` with true when the window size enters `breakpoint`, and with false when it leaves it.
+    /// `apply` is first called, with the current state, when the breakpoint is added.
+    pub fn add_breakpoint<F>(&mut self, breakpoint: Breakpoint, apply: F) -> &mut Self
+        where F: FnMut(&mut Widget, bool) + 'static
+    {
+        self.add_handler(BreakpointHandler {
+            breakpoint: breakpoint,
+            active: None,
+            apply: Box::new(apply),
+        });
+        event::event(Target::Root, CheckBreakpoints(self.clone()));
+        self
+    }
+
+    /// Adds constraints to the layout of this widget while the window size is within `breakpoint`,
+    /// and removes them when it leaves it
+    pub fn breakpoint_constraints<B: ConstraintBuilder>(&mut self, breakpoint: Breakpoint, builder: B) -> &mut Self {
+        let constraints: Vec<Constraint> = builder.build(&self.layout_vars());
+        let mut added = false;
+        self.add_breakpoint(breakpoint, move |widget, active| {
+            let mut layout = widget.layout();
+            if active {
+                layout.add(constraints.clone());
+            } else if added {
+                layout.remove_constraints(constraints.clone());
+            }
+            added = active;
+        })
+    }
+}
+
+impl App {
+    pub fn add_responsive_handlers(&mut self) {
+        self.add_handler(|event: &CheckBreakpoints, args: EventArgs| {
+            event.0.event(WindowResized(args.ui.window_size()));
+        });
+    }
+}
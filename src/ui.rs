@@ -4,6 +4,7 @@ use std::collections::{HashSet, HashMap, VecDeque};
 use std::any::{Any, TypeId};
 use std::rc::Rc;
 use std::cell::RefCell;
+use std::time::Instant;
 
 use cassowary::Constraint;
 use cassowary::strength::*;
@@ -13,52 +14,135 @@ use glutin;
 use window::Window;
 use app::App;
 use widget::Widget;
+use animation::BoundsAnimation;
+use popup::Popup;
+use dialog::Dialog;
+use responsive::WindowResized;
 use layout::{LimnSolver, LayoutChanged, LayoutVars, ExactFrame};
 use layout::constraint::*;
 use geometry::{Point, Rect, Size};
 use resources::WidgetId;
-use event::{Target, EventArgs};
-use render::WebRenderContext;
+use event::{self, Target, EventArgs};
+use render::{WebRenderContext, RenderBuilder};
+use record::DisplayList;
+use clipboard::{Clipboard, MemoryClipboard};
+use input::shortcuts::Shortcuts;
 
 /// If true, the constraint that matches the root layout size to the window size
 /// is required. This can be useful for debugging but can result in panics from resizing the window.
 const WINDOW_CONSTRAINT_REQUIRED: bool = false;
 
+/// Z-indices of the layers drawn over the widget tree, from the top down:
+/// the inspector is drawn over everything, tooltips over popups, and popups over dialogs,
+/// so they can be opened from within a dialog
+pub(crate) const INSPECTOR_Z_INDEX: i32 = i32::max_value();
+pub(crate) const TOOLTIP_Z_INDEX: i32 = i32::max_value() - 1;
+pub(crate) const POPUP_Z_INDEX: i32 = i32::max_value() - 2;
+pub(crate) const DIALOG_Z_INDEX: i32 = i32::max_value() - 3;
+
 /// The core of a limn application, holds the root of the widget tree and other application global state.
 /// `Ui` is accessible to every event handler, so features helper methods that can be accessed at any time.
 pub struct Ui {
     pub(crate) root: Widget,
     widget_map: HashMap<WidgetId, Widget>,
     pub(crate) solver: LimnSolver,
-    pub(crate) render: WebRenderContext,
+    /// Widgets whose layout changed since the solver was last updated, see `update_layouts`
+    pub(crate) dirty_layouts: Vec<Widget>,
+    /// Consecutive layout changes that made a container reflow, see `layout::MAX_REFLOW_PASSES`
+    pub(crate) reflow_passes: usize,
+    /// Widgets moving to new bounds, see `animation`
+    pub(crate) animations: HashMap<WidgetId, BoundsAnimation>,
+    /// Open popups, in the order they were opened, see `popup`
+    pub(crate) popups: Vec<Popup>,
+    /// Parent of the open popups, drawn over the widget tree
+    pub(crate) popup_layer: Option<Widget>,
+    /// Open modal dialogs, in the order they were opened, see `dialog`
+    pub(crate) dialogs: Vec<Dialog>,
+    /// The WebRender context, `None` if the `Ui` is headless
+    pub(crate) render: Option<WebRenderContext>,
     needs_redraw: bool,
+    /// When a `FrameEvent` has to be sent, even if nothing else wakes the window up, see `wake_at`
+    wakeup: Option<Instant>,
     should_close: bool,
     debug_draw_bounds: bool,
-    pub window: Rc<RefCell<Window>>,
+    /// The window the `Ui` is drawn to, `None` if the `Ui` is headless
+    pub window: Option<Rc<RefCell<Window>>>,
+    window_size: Size,
+    /// The position of the mouse, set by the `MouseController`, `None` if it's outside the window
+    pub(crate) cursor_position: Option<Point>,
     window_constraints: Vec<Constraint>,
+    clipboard: Box<Clipboard>,
+    shortcuts: Shortcuts,
 }
 
 impl Ui {
     pub(super) fn new(mut window: Window, events_loop: &glutin::EventsLoop) -> Self {
-        let mut root = Widget::new("window");
-        root.layout().set_container(ExactFrame);
-        root.layout().add(top_left(Point::zero()));
-        // x will crash if window size set to (0, 0)
-        root.layout().add(min_size(Size::new(1.0, 1.0)));
         let render = WebRenderContext::new(&mut window, events_loop);
         Ui {
             widget_map: HashMap::new(),
-            root: root.into(),
+            root: Ui::new_root(),
             solver: LimnSolver::new(),
-            render: render,
+            dirty_layouts: Vec::new(),
+            reflow_passes: 0,
+            animations: HashMap::new(),
+            popups: Vec::new(),
+            popup_layer: None,
+            dialogs: Vec::new(),
+            render: Some(render),
             needs_redraw: true,
+            wakeup: None,
             should_close: false,
             debug_draw_bounds: false,
-            window: Rc::new(RefCell::new(window)),
+            window: Some(Rc::new(RefCell::new(window))),
+            window_size: Size::zero(),
+            cursor_position: None,
             window_constraints: Vec::new(),
+            clipboard: Box::new(MemoryClipboard::new()),
+            shortcuts: Shortcuts::new(),
         }
     }
 
+    /// Creates a `Ui` without a window or renderer, nothing is drawn but events and layout are
+    /// handled normally, useful for testing.
+    pub(super) fn new_headless() -> Self {
+        Ui {
+            widget_map: HashMap::new(),
+            root: Ui::new_root(),
+            solver: LimnSolver::new(),
+            dirty_layouts: Vec::new(),
+            reflow_passes: 0,
+            animations: HashMap::new(),
+            popups: Vec::new(),
+            popup_layer: None,
+            dialogs: Vec::new(),
+            render: None,
+            needs_redraw: true,
+            wakeup: None,
+            should_close: false,
+            debug_draw_bounds: false,
+            window: None,
+            window_size: Size::zero(),
+            cursor_position: None,
+            window_constraints: Vec::new(),
+            clipboard: Box::new(MemoryClipboard::new()),
+            shortcuts: Shortcuts::new(),
+        }
+    }
+
+    fn new_root() -> Widget {
+        let mut root = Widget::new("window");
+        root.layout().set_container(ExactFrame);
+        root.layout().add(top_left(Point::zero()));
+        // x will crash if window size set to (0, 0)
+        root.layout().add(min_size(Size::new(1.0, 1.0)));
+        root
+    }
+
+    /// Returns true if the `Ui` has no window or renderer
+    pub fn is_headless(&self) -> bool {
+        self.window.is_none()
+    }
+
     pub fn get_widget(&self, widget_id: WidgetId) -> Option<Widget> {
         self.widget_map.get(&widget_id).cloned()
     }
@@ -71,6 +155,17 @@ impl Ui {
         self.get_root().event(data);
     }
 
+    /// The clipboard used by the app, see `App::set_clipboard`
+    pub fn clipboard(&mut self) -> &mut Clipboard {
+        &mut *self.clipboard
+    }
+    pub fn set_clipboard<C: Clipboard + 'static>(&mut self, clipboard: C) {
+        self.clipboard = Box::new(clipboard);
+    }
+    /// The keyboard shortcuts of the app, see `input::shortcuts`
+    pub fn shortcuts(&mut self) -> &mut Shortcuts {
+        &mut self.shortcuts
+    }
     pub fn close(&mut self) {
         self.should_close = true;
     }
@@ -81,12 +176,28 @@ impl Ui {
 
     pub(super) fn resize_window_to_fit(&mut self) {
         let window_dims = self.root.bounds().size;
-        self.window.borrow_mut().resize(window_dims.width as u32, window_dims.height as u32);
+        if let Some(ref window) = self.window {
+            window.borrow_mut().resize(window_dims.width as u32, window_dims.height as u32);
+        }
+    }
+
+    /// The size of the window, in the units used by the layout
+    pub fn window_size(&self) -> Size {
+        self.window_size
+    }
+
+    /// The position of the mouse in the window, if it's over the window
+    pub fn cursor_position(&self) -> Option<Point> {
+        self.cursor_position
     }
 
     pub(super) fn window_resized(&mut self, window_dims: Size) {
-        let window_size = self.window.borrow_mut().size_px();
-        self.render.window_resized(window_size);
+        if let Some(ref window) = self.window {
+            let window_size = window.borrow_mut().size_px();
+            if let Some(ref mut render) = self.render {
+                render.window_resized(window_size);
+            }
+        }
         let mut root = self.get_root();
 
         if WINDOW_CONSTRAINT_REQUIRED {
@@ -105,10 +216,39 @@ impl Ui {
             layout.edit_right().set(window_dims.width).strength(REQUIRED - 1.0);
             layout.edit_bottom().set(window_dims.height).strength(REQUIRED - 1.0);
         }
+        self.window_size = window_dims;
+        root.event_subtree(WindowResized(window_dims));
         self.needs_redraw = true;
     }
 
+    /// Updates the solver with every layout that changed while handling the last events,
+    /// parents before their children, then emits the resulting changes.
+    /// Returns false if no layouts changed.
+    pub(super) fn update_layouts(&mut self) -> bool {
+        if self.dirty_layouts.is_empty() {
+            return false;
+        }
+        let mut widgets: Vec<(usize, Widget)> = self.dirty_layouts.drain(..)
+            .map(|widget| (widget_depth(&widget), widget))
+            .collect();
+        widgets.sort_by_key(|&(depth, _)| depth);
+        for (_, widget_ref) in widgets {
+            // removed widgets shouldn't be added back to the solver
+            let registered = self.widget_map.contains_key(&widget_ref.id());
+            let mut widget = widget_ref.widget_mut();
+            widget.layout_dirty = false;
+            if registered {
+                self.solver.update_layout(&mut widget.layout);
+            }
+        }
+        self.check_layout_changes();
+        true
+    }
+
     pub fn check_layout_changes(&mut self) {
+        for error in self.solver.take_errors() {
+            self.event(error);
+        }
 
         let changes = self.solver.fetch_changes();
         debug!("layout has {} changes", changes.len());
@@ -125,6 +265,29 @@ impl Ui {
         self.needs_redraw
     }
 
+    /// Makes sure a `FrameEvent` is sent at `deadline`, even if nothing else happens by then,
+    /// for timers that are checked on each `FrameEvent`. Only the earliest deadline is kept,
+    /// so a timer that hasn't elapsed yet should call this again on the next `FrameEvent`.
+    pub fn wake_at(&mut self, deadline: Instant) {
+        if self.wakeup.map_or(true, |wakeup| deadline < wakeup) {
+            self.wakeup = Some(deadline);
+            if self.window.is_some() {
+                event::wakeup_at(deadline);
+            }
+        }
+    }
+
+    /// Returns true, once, if the deadline set with `wake_at` has passed
+    pub(super) fn wakeup_due(&mut self, now: Instant) -> bool {
+        match self.wakeup {
+            Some(wakeup) if now >= wakeup => {
+                self.wakeup = None;
+                true
+            }
+            _ => false,
+        }
+    }
+
     pub(super) fn draw_if_needed(&mut self) {
         if self.needs_redraw {
             self.draw();
@@ -132,29 +295,69 @@ impl Ui {
         }
     }
 
+    pub(super) fn frame_ready(&mut self) -> bool {
+        match self.render {
+            Some(ref mut render) => render.frame_ready(),
+            None => false,
+        }
+    }
+
     fn draw(&mut self) {
-        let window_size = self.window.borrow_mut().size_dp();
-        let (builder, resources) = {
-            let mut renderer = self.render.render_builder(window_size);
-            let crop_to = Rect::new(Point::zero(), Size::new(::std::f32::MAX, ::std::f32::MAX));
-            self.root.draw(crop_to, &mut renderer, self.debug_draw_bounds);
-            (renderer.builder, renderer.resources)
+        let window_size = match self.window {
+            Some(ref window) => window.borrow_mut().size_dp(),
+            None => return,
+        };
+        if let Some(ref mut render) = self.render {
+            let (builder, resources) = {
+                let mut renderer = render.render_builder(window_size);
+                let crop_to = Rect::new(Point::zero(), Size::new(::std::f32::MAX, ::std::f32::MAX));
+                self.root.draw(crop_to, &mut renderer, self.debug_draw_bounds);
+                (renderer.builder, renderer.resources)
+            };
+            render.set_display_list(builder, resources, window_size);
+            render.generate_frame();
+        }
+    }
+
+    /// Draws the widget tree without a renderer, returning the recorded display list,
+    /// see the [`record`](../record/index.html) module
+    pub fn record_display_list(&mut self) -> DisplayList {
+        let window_size = match self.window {
+            Some(ref window) => window.borrow_mut().size_dp(),
+            None => self.root.bounds().size,
         };
-        self.render.set_display_list(builder, resources, window_size);
-        self.render.generate_frame();
+        let mut renderer = RenderBuilder::new_recording(window_size);
+        let crop_to = Rect::new(Point::zero(), Size::new(::std::f32::MAX, ::std::f32::MAX));
+        self.root.draw(crop_to, &mut renderer, self.debug_draw_bounds);
+        renderer.recorder.take().unwrap().finish()
     }
 
     // Call after drawing
     pub(super) fn update(&mut self) {
-        self.render.update(self.window.borrow_mut().size_px());
-        let window = self.window.borrow_mut();
-        window.swap_buffers();
+        if let Some(ref window) = self.window {
+            let window = window.borrow_mut();
+            if let Some(ref mut render) = self.render {
+                render.update(window.size_px());
+            }
+            window.swap_buffers();
+        }
+    }
+
+    pub(super) fn deinit(&mut self) {
+        if let Some(render) = self.render.take() {
+            render.deinit();
+        }
     }
 
     pub fn widgets_bfs(&self) -> WidgetsBfs {
         WidgetsBfs::new(self.get_root())
     }
 
+    /// Find the first widget with the given name, in breadth first order
+    pub fn find_widget(&self, name: &str) -> Option<Widget> {
+        self.widgets_bfs().find(|widget| widget.name() == name)
+    }
+
     pub fn widgets_under_cursor(&mut self, point: Point) -> WidgetsUnderCursor {
         WidgetsUnderCursor::new(point, self.get_root())
     }
@@ -288,7 +491,7 @@ impl Iterator for WidgetsUnderCursor {
 }
 
 // Iterates in reverse of draw order, that is, depth first post order,
-// with siblings in reverse of insertion order
+// with siblings in reverse of draw order
 struct WidgetsDfsPostReverse {
     stack: Vec<Widget>,
     discovered: HashSet<Widget>,
@@ -310,7 +513,7 @@ impl Iterator for WidgetsDfsPostReverse {
     fn next(&mut self) -> Option<Widget> {
         while let Some(widget_ref) = self.stack.last().cloned() {
             if self.discovered.insert(widget_ref.clone()) {
-                for child in &widget_ref.children() {
+                for child in &widget_ref.children_in_draw_order() {
                     self.stack.push(child.clone());
                 }
             } else {
@@ -349,3 +552,14 @@ impl Iterator for WidgetsBfs {
         }
     }
 }
+
+/// The number of ancestors of `widget`
+fn widget_depth(widget: &Widget) -> usize {
+    let mut depth = 0;
+    let mut parent = widget.parent();
+    while let Some(ancestor) = parent {
+        depth += 1;
+        parent = ancestor.parent();
+    }
+    depth
+}
@@ -1,16 +1,53 @@
 use std::marker::PhantomData;
 
 use mopa;
-use webrender::api::*;
 
 use render::RenderBuilder;
 use event::{EventHandler, EventArgs};
 
-use geometry::Rect;
+use geometry::{Rect, Size};
 
 
 pub trait Draw: ::std::fmt::Debug + mopa::Any {
     fn draw(&mut self, bounds: Rect, crop_to: Rect, renderer: &mut RenderBuilder);
+    /// This draw state as `Measure`, if it's content has an intrinsic size
+    fn as_measure(&self) -> Option<&Measure> {
+        None
+    }
+}
+
+/// The size a widget needs to fit it's content, see `Measure`
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SizeHint {
+    pub min: Size,
+    pub preferred: Size,
+    /// The largest size the content can be stretched to, `None` if unbounded
+    pub max: Option<Size>,
+}
+
+impl SizeHint {
+    pub fn new(min: Size, preferred: Size, max: Option<Size>) -> Self {
+        SizeHint {
+            min: min,
+            preferred: preferred,
+            max: max,
+        }
+    }
+    /// A hint for content that can't be resized
+    pub fn exact(size: Size) -> Self {
+        SizeHint::new(size, size, Some(size))
+    }
+}
+
+/// Draw states with an intrinsic content size, like text or images.
+/// `Widget::size_to_content` turns the measured size into layout constraints.
+pub trait Measure {
+    fn size_hint(&self) -> SizeHint;
+    /// The height of the content when laid out at `width`, for content that wraps, like text.
+    /// Overrides the preferred height of the `SizeHint`.
+    fn height_for_width(&self, _width: f32) -> Option<f32> {
+        None
+    }
 }
 
 mopafy!(Draw);
@@ -59,20 +96,12 @@ impl Default for OpacityModifier {
 impl DrawModifier for OpacityModifier {
     fn push(&self, renderer: &mut RenderBuilder) {
         if self.alpha != 1.0 {
-            renderer.builder.push_stacking_context(
-                &PrimitiveInfo::new(Rect::zero()),
-                ScrollPolicy::Fixed,
-                None,
-                TransformStyle::Flat,
-                None,
-                MixBlendMode::Normal,
-                vec![FilterOp::Opacity(PropertyBinding::Value(self.alpha), self.alpha)],
-            );
+            renderer.push_stacking_context(Some(self.alpha), None);
         }
     }
     fn pop(&self, renderer: &mut RenderBuilder) {
         if self.alpha != 1.0 {
-            renderer.builder.pop_stacking_context();
+            renderer.pop_stacking_context();
         }
     }
 }
@@ -21,6 +21,7 @@ use std::hash::{Hash, Hasher};
 use std::ops::{Deref, DerefMut};
 use std::fmt;
 use std::fmt::Debug;
+use std::mem;
 
 use render::RenderBuilder;
 use event::{self, EventHandler, EventArgs, EventHandlerWrapper};
@@ -33,6 +34,7 @@ use color::Color;
 use event::Target;
 use layout::UpdateLayout;
 use style::*;
+use animation::Transition;
 
 use widget::draw::DrawModifier;
 
@@ -123,9 +125,17 @@ impl Widget {
     }
 
     pub fn layout(&mut self) -> LayoutGuardMut {
-        event::event(Target::Root, UpdateLayout(self.clone()));
+        self.invalidate_layout();
         LayoutGuardMut { guard: self.0.borrow_mut() }
     }
+    /// Marks the layout of this widget as changed. Changed layouts are updated in the solver
+    /// together, once the pending events have been handled.
+    fn invalidate_layout(&self) {
+        let dirty = mem::replace(&mut self.widget_mut().layout_dirty, true);
+        if !dirty {
+            event::event(Target::Root, UpdateLayout(self.clone()));
+        }
+    }
     pub fn layout_vars(&self) -> LayoutVars {
         self.0.borrow().layout.vars
     }
@@ -142,6 +152,10 @@ impl Widget {
         }
         self.event(LayoutUpdated);
     }
+    pub(crate) fn set_bounds(&mut self, bounds: Rect) {
+        self.0.borrow_mut().bounds = bounds;
+        self.event(LayoutUpdated);
+    }
     pub fn props(&self) -> PropsGuard {
         PropsGuard { guard: self.0.borrow() }
     }
@@ -175,7 +189,7 @@ impl Widget {
     pub fn set_name(&mut self, name: &str) -> &mut Self {
         self.widget_mut().name = name.to_owned();
         self.widget_mut().layout.name = Some(name.to_owned());
-        event::event(Target::Root, UpdateLayout(self.clone()));
+        self.invalidate_layout();
         self
     }
     pub fn set_debug_color(&mut self, color: Color) -> &mut Self {
@@ -197,6 +211,10 @@ impl Widget {
     pub fn bounds(&self) -> Rect {
         self.0.borrow().bounds
     }
+    /// Returns true if the widget's layout, or the layout of any of it's ancestors, is hidden
+    pub fn is_hidden(&self) -> bool {
+        self.0.borrow().layout.hidden || self.parent().map_or(false, |parent| parent.is_hidden())
+    }
     pub fn update<F, T: Draw + 'static>(&mut self, f: F)
         where F: FnOnce(&mut T)
     {
@@ -213,7 +231,7 @@ impl Widget {
 
     pub fn add_child<U: Into<Widget>>(&mut self, child: U) -> &mut Self {
         let mut child = child.into();
-        event::event(Target::Root, ::layout::UpdateLayout(child.clone()));
+        child.invalidate_layout();
         child.widget_mut().parent = Some(self.downgrade());
         child.widget_mut().props.extend(self.props().iter().cloned());
         self.widget_mut().children.push(child.clone());
@@ -254,6 +272,23 @@ impl Widget {
         self.children().iter().find(|child| child.name() == name).cloned()
     }
 
+    /// Sets the z-index of the widget, siblings with a higher z-index are drawn on top, and receive
+    /// mouse input first. Siblings with the same z-index are drawn in the order they were added.
+    pub fn set_z_index(&mut self, z_index: i32) -> &mut Self {
+        self.widget_mut().z_index = z_index;
+        self.event(StateUpdated);
+        self
+    }
+    pub fn z_index(&self) -> i32 {
+        self.widget().z_index
+    }
+    /// The children of this widget in the order they are drawn, from bottom to top
+    pub fn children_in_draw_order(&self) -> Vec<Widget> {
+        let mut children = self.children();
+        children.sort_by_key(|child| child.z_index());
+        children
+    }
+
     pub fn event<T: 'static>(&self, data: T) {
         event::event(Target::Widget(self.clone()), data);
     }
@@ -305,8 +340,7 @@ impl Widget {
 
     fn draw_widget(&mut self, crop_to: Rect, renderer: &mut RenderBuilder) {
         let bounds = self.bounds();
-        let clip_id = renderer.builder.define_clip(bounds, vec![], None);
-        renderer.builder.push_clip_id(clip_id);
+        renderer.push_clip(bounds);
         for (_, modifier) in &self.widget().draw_modifiers {
             modifier.push(renderer);
         }
@@ -314,19 +348,19 @@ impl Widget {
             draw_state.draw(bounds, crop_to, renderer);
         }
         if let Some(crop_to) = crop_to.intersection(&bounds) {
-            for child in &mut self.children() {
+            for child in &mut self.children_in_draw_order() {
                 child.draw_widget(crop_to, renderer);
             }
         }
         for (_, modifier) in &self.widget().draw_modifiers {
             modifier.pop(renderer);
         }
-        renderer.builder.pop_clip_id();
+        renderer.pop_clip();
     }
     fn draw_debug(&mut self, renderer: &mut RenderBuilder) {
         let color = self.debug_color().unwrap_or(::color::GREEN);
         render::draw_rect_outline(self.bounds(), color, renderer);
-        for child in &mut self.children() {
+        for child in &mut self.children_in_draw_order() {
             child.draw_debug(renderer);
         }
     }
@@ -422,6 +456,14 @@ impl<'a> DrawStateGuard<'a> {
             None
         }
     }
+    /// The draw state as `Measure`, if it has an intrinsic size
+    pub fn measure(&self) -> Option<&Measure> {
+        if let Some(ref draw_state) = self.guard.draw_state.state {
+            draw_state.as_measure()
+        } else {
+            None
+        }
+    }
     pub fn style(&mut self) -> Option<&mut DrawStyle> {
         if let Some(ref mut style) = self.guard.draw_state.style {
             Some(style)
@@ -454,9 +496,13 @@ pub(super) struct WidgetInner {
     has_updated: bool,
     props_updated: bool,
     pub(super) layout: Layout,
+    /// True if the layout has changed since it was last updated in the solver
+    pub(super) layout_dirty: bool,
     pub(super) bounds: Rect,
     name: String,
     debug_color: Option<Color>,
+    z_index: i32,
+    pub(super) transition: Option<Transition>,
     children: Vec<Widget>,
     parent: Option<WidgetWeak>,
     handlers: HashMap<TypeId, Vec<Rc<RefCell<EventHandlerWrapper>>>>,
@@ -473,11 +519,14 @@ impl WidgetInner {
             cursor_hit_fn: None,
             props: PropSet::new(),
             layout: Layout::new(id.0, Some(name.clone())),
+            layout_dirty: false,
             has_updated: true,
             props_updated: true,
             bounds: Rect::zero(),
             name: name,
             debug_color: None,
+            z_index: 0,
+            transition: None,
             children: Vec::new(),
             parent: None,
             handlers: HashMap::new(),
@@ -8,6 +8,10 @@ pub enum Property {
     Pressed,
     Inactive,
     Focused,
+    /// The widget's value is invalid, ie. text that fails validation
+    Invalid,
+    /// The widget's value is neither on or off, ie. a checkbox for a partially selected group
+    Indeterminate,
 }
 pub type PropSet = BTreeSet<Property>;
 
@@ -21,5 +25,7 @@ pub mod states {
         pub static ref SELECTED: PropSet = btreeset!{Property::Selected};
         pub static ref INACTIVE: PropSet = btreeset!{Property::Inactive};
         pub static ref FOCUSED: PropSet = btreeset!{Property::Focused};
+        pub static ref INVALID: PropSet = btreeset!{Property::Invalid};
+        pub static ref INDETERMINATE: PropSet = btreeset!{Property::Indeterminate};
     }
 }
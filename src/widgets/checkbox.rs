@@ -0,0 +1,193 @@
+use glutin::{ElementState, VirtualKeyCode};
+
+use layout::constraint::*;
+use event::{EventHandler, EventArgs};
+use widget::Widget;
+use widget::property::Property;
+use input::mouse::ClickEvent;
+use input::keyboard::WidgetKeyboardInput;
+use widgets::text::StaticTextStyle;
+use draw::rect::RectStyle;
+use draw::text::TextStyle;
+use geometry::Size;
+use color::*;
+use style::*;
+use widget::property::states::*;
+
+/// The state of a `Checkbox`
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum CheckState {
+    Unchecked,
+    Checked,
+    /// Neither checked or unchecked, ie. for a checkbox that selects a group of items,
+    /// only some of which are selected
+    Indeterminate,
+}
+
+component_style!{pub struct Checkbox<name="checkbox", style=CheckboxStyle> {
+    rect: RectStyle = style!(RectStyle {
+        background_color: WHITE,
+        border: Some((1.0, GRAY_30)),
+        corner_radius: Some(3.0),
+    }),
+    mark_color: Color = BLUE,
+    size: f32 = 20.0,
+    text: Option<TextStyle> = None,
+    init_state: CheckState = CheckState::Unchecked,
+    // Clicking the checkbox cycles through the indeterminate state, otherwise the indeterminate
+    // state can only be set with `SetCheckState`
+    tri_state: bool = false,
+}}
+
+impl CheckboxStyle {
+    pub fn from_text(text: &str) -> Self {
+        Self {
+            text: Some(Some(TextStyle::from_text(text))),
+            ..Self::default()
+        }
+    }
+}
+
+impl WidgetModifier for Checkbox {
+    fn apply(&self, widget: &mut Widget) {
+        let mut check_box = Widget::new("checkbox_box");
+        check_box.set_draw_style(self.rect.clone());
+
+        // a square when checked, a bar when indeterminate
+        let mut check_mark = Widget::new("checkbox_mark");
+        let mut mark_style = DrawStyle::from(style!(RectStyle {
+            background_color: TRANSPARENT,
+            corner_radius: Some(2.0),
+        }));
+        mark_style.prop_style(ACTIVATED.clone(), style!(RectStyle {
+            background_color: self.mark_color,
+        }));
+        check_mark.set_draw_style(mark_style);
+        check_mark.layout().add(constraints![
+            match_layout(&check_box).padding(self.size / 4.0),
+        ]);
+        let mut indeterminate_mark = Widget::new("checkbox_indeterminate");
+        let mut indeterminate_style = DrawStyle::from(style!(RectStyle {
+            background_color: TRANSPARENT,
+        }));
+        indeterminate_style.prop_style(INDETERMINATE.clone(), style!(RectStyle {
+            background_color: self.mark_color,
+        }));
+        indeterminate_mark.set_draw_style(indeterminate_style);
+        indeterminate_mark.layout().add(constraints![
+            height(self.size / 6.0),
+            center(&check_box),
+            match_width(&check_box).padding(self.size / 2.0),
+        ]);
+        check_box.add_child(check_mark);
+        check_box.add_child(indeterminate_mark);
+
+        add_indicator(widget, check_box, self.size, self.text.clone(), "checkbox_text");
+        widget
+            .add_handler(CheckboxHandler {
+                state: self.init_state,
+                tri_state: self.tri_state,
+            })
+            .enable_focus();
+        CheckboxHandler::add_adapters(widget);
+        widget.event(SetCheckState(self.init_state));
+    }
+}
+
+/// Adds `indicator`, the box or circle drawn by a checkbox or radio button, to `widget`,
+/// followed by a label if there's a `text` style, named and styled with the class `label_name`
+pub(crate) fn add_indicator(widget: &mut Widget, mut indicator: Widget, indicator_size: f32, text: Option<TextStyle>, label_name: &str) {
+    indicator.layout().add(constraints![
+        size(Size::new(indicator_size, indicator_size)),
+        align_left(widget),
+        center_vertical(widget),
+        bound_top(widget),
+        bound_bottom(widget),
+    ]);
+    if let Some(text_style) = text {
+        let mut label = Widget::new(label_name);
+        label.set_draw_style(DrawStyle::from_class::<TextStyle>(label_name));
+        StaticTextStyle::from_style(text_style).component().apply(&mut label);
+        label.layout().add(constraints![
+            to_right_of(&indicator).padding(indicator_size / 2.0),
+            align_right(widget),
+            center_vertical(widget),
+            bound_top(widget),
+            bound_bottom(widget),
+        ]);
+        widget.add_child(indicator);
+        widget.add_child(label);
+    } else {
+        indicator.layout().add(align_right(widget));
+        widget.add_child(indicator);
+    }
+    widget.layout().add(shrink());
+}
+
+/// Sent by a `Checkbox` when it's state changes
+#[derive(Debug, Copy, Clone)]
+pub struct CheckboxChanged(pub CheckState);
+
+/// Sets the state of a `Checkbox`
+#[derive(Debug, Copy, Clone)]
+pub struct SetCheckState(pub CheckState);
+
+multi_event!{impl EventHandler<CheckboxEvent> for CheckboxHandler {
+    ClickEvent => click,
+    WidgetKeyboardInput => keyboard_input,
+    SetCheckState => set_state,
+}}
+
+struct CheckboxHandler {
+    state: CheckState,
+    tri_state: bool,
+}
+
+impl CheckboxHandler {
+    fn click(&mut self, _: &ClickEvent, args: EventArgs) {
+        self.toggle(args);
+    }
+    fn keyboard_input(&mut self, event: &WidgetKeyboardInput, args: EventArgs) {
+        let &WidgetKeyboardInput(input) = event;
+        if input.state == ElementState::Pressed && input.virtual_keycode == Some(VirtualKeyCode::Space) {
+            self.toggle(args);
+        }
+    }
+    fn toggle(&mut self, args: EventArgs) {
+        if args.widget.props().contains(&Property::Inactive) {
+            return;
+        }
+        let state = match (self.state, self.tri_state) {
+            (CheckState::Unchecked, _) => CheckState::Checked,
+            (CheckState::Checked, true) => CheckState::Indeterminate,
+            (CheckState::Checked, false) => CheckState::Unchecked,
+            (CheckState::Indeterminate, true) => CheckState::Unchecked,
+            (CheckState::Indeterminate, false) => CheckState::Checked,
+        };
+        self.update(state, args);
+    }
+    fn set_state(&mut self, event: &SetCheckState, args: EventArgs) {
+        let &SetCheckState(state) = event;
+        self.update(state, args);
+    }
+    fn update(&mut self, state: CheckState, mut args: EventArgs) {
+        match state {
+            CheckState::Unchecked => {
+                args.widget.remove_prop(Property::Activated);
+                args.widget.remove_prop(Property::Indeterminate);
+            }
+            CheckState::Checked => {
+                args.widget.remove_prop(Property::Indeterminate);
+                args.widget.add_prop(Property::Activated);
+            }
+            CheckState::Indeterminate => {
+                args.widget.remove_prop(Property::Activated);
+                args.widget.add_prop(Property::Indeterminate);
+            }
+        }
+        if state != self.state {
+            self.state = state;
+            args.widget.event(CheckboxChanged(state));
+        }
+    }
+}
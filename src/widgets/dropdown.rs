@@ -0,0 +1,238 @@
+//! A dropdown, or select box, that shows the selected item, and opens a popup `List` of items
+//! below it when clicked.
+//!
+//! An editable dropdown is a combo box, the selected item is shown in an `EditText`, and typing
+//! into it filters the list to the items that contain the typed text.
+//! Selecting an item, by clicking it or sending `SetDropdownValue`, closes the popup and sends
+//! `DropdownChanged` to the dropdown if the value changed.
+
+use glutin::{ElementState, VirtualKeyCode};
+use cassowary::strength::*;
+
+use layout::constraint::*;
+use event::{EventHandler, EventArgs};
+use widget::Widget;
+use widget::property::Property;
+use input::mouse::ClickEvent;
+use input::keyboard::WidgetKeyboardInput;
+use popup::PopupClosed;
+use widgets::text::StaticTextStyle;
+use widgets::edit_text::{EditTextStyle, TextUpdated};
+use widgets::list::{ListStyle, ListItemSelected, default_text_adapter};
+use draw::rect::RectStyle;
+use draw::text::TextStyle;
+use geometry::Size;
+use color::*;
+use style::*;
+
+component_style!{pub struct Dropdown<name="dropdown", style=DropdownStyle> {
+    rect: RectStyle = style!(RectStyle {
+        background_color: WHITE,
+        border: Some((1.0, GRAY_30)),
+        corner_radius: Some(3.0),
+    }),
+    popup_rect: RectStyle = style!(RectStyle {
+        background_color: WHITE,
+        border: Some((1.0, GRAY_30)),
+    }),
+    text: TextStyle = TextStyle::default(),
+    items: Vec<String> = Vec::new(),
+    selected: Option<String> = None,
+    // The selected item is shown in an `EditText`, which filters the items
+    editable: bool = false,
+}}
+
+impl DropdownStyle {
+    /// A dropdown that selects one of `items`
+    pub fn from_items(items: &[&str]) -> Self {
+        Self {
+            items: Some(items.iter().map(|item| item.to_string()).collect()),
+            ..Self::default()
+        }
+    }
+    /// A combo box, an editable dropdown that selects one of `items`
+    pub fn combobox(items: &[&str]) -> Self {
+        Self {
+            editable: Some(true),
+            ..Self::from_items(items)
+        }
+    }
+}
+
+impl WidgetModifier for Dropdown {
+    fn apply(&self, widget: &mut Widget) {
+        let text = self.selected.clone().unwrap_or_default();
+        let text_widget = if self.editable {
+            let mut edit = Widget::from_modifier_style(EditTextStyle::default());
+            edit.set_name("dropdown_edit");
+            edit.add_handler(|event: &TextUpdated, args: EventArgs| {
+                if let Some(dropdown) = args.widget.parent() {
+                    dropdown.event(FilterItems(event.0.clone()));
+                }
+            });
+            edit.layout().add(match_layout(widget));
+            if self.selected.is_some() {
+                edit.event(TextUpdated(text));
+            }
+            edit
+        } else {
+            let mut draw_style = DrawStyle::from(self.rect.clone());
+            draw_style.set_class("dropdown_rect");
+            widget
+                .set_draw_style(draw_style)
+                .enable_hover()
+                .enable_focus();
+
+            let mut text_style = self.text.clone();
+            text_style.text = Some(text);
+            let mut text_widget = Widget::new("dropdown_text");
+            text_widget.set_draw_style(DrawStyle::from_class::<TextStyle>("dropdown_text"));
+            StaticTextStyle::from_style(text_style).component().apply(&mut text_widget);
+            text_widget.layout().add(constraints![
+                bound_left(widget).padding(10.0),
+                bound_right(widget).padding(10.0),
+                bound_top(widget).padding(5.0),
+                bound_bottom(widget).padding(5.0),
+                center_vertical(widget),
+            ]);
+            text_widget
+        };
+        widget.layout().add(constraints![
+            min_size(Size::new(100.0, 30.0)),
+            shrink(),
+        ]);
+        widget.add_child(text_widget.clone());
+        widget.add_handler(DropdownHandler {
+            items: self.items.clone(),
+            value: self.selected.clone(),
+            text_widget: text_widget,
+            popup_rect: self.popup_rect.clone(),
+            popup: None,
+        });
+        DropdownHandler::add_adapters(widget);
+    }
+}
+
+/// Sent by a `Dropdown` when a different item is selected
+#[derive(Debug, Clone)]
+pub struct DropdownChanged(pub String);
+
+/// Selects an item of a `Dropdown`
+#[derive(Debug, Clone)]
+pub struct SetDropdownValue(pub String);
+
+/// Sent from the `EditText` of an editable dropdown when it's text changes
+#[derive(Clone)]
+struct FilterItems(String);
+
+multi_event!{impl EventHandler<DropdownEvent> for DropdownHandler {
+    ClickEvent => click,
+    WidgetKeyboardInput => keyboard_input,
+    SetDropdownValue => set_value,
+    FilterItems => filter,
+    PopupClosed => popup_closed,
+}}
+
+struct DropdownHandler {
+    items: Vec<String>,
+    value: Option<String>,
+    /// Shows the value, a static text, or the `EditText` of an editable dropdown
+    text_widget: Widget,
+    popup_rect: RectStyle,
+    popup: Option<Widget>,
+}
+
+impl DropdownHandler {
+    fn click(&mut self, _: &ClickEvent, args: EventArgs) {
+        if args.widget.props().contains(&Property::Inactive) {
+            return;
+        }
+        if let Some(ref popup) = self.popup {
+            args.ui.close_popup(popup);
+            return;
+        }
+        self.open("", args);
+    }
+    fn keyboard_input(&mut self, event: &WidgetKeyboardInput, args: EventArgs) {
+        let &WidgetKeyboardInput(input) = event;
+        if input.state == ElementState::Pressed && self.popup.is_none() {
+            match input.virtual_keycode {
+                Some(VirtualKeyCode::Space) | Some(VirtualKeyCode::Return) | Some(VirtualKeyCode::Down) => {
+                    self.open("", args);
+                }
+                _ => (),
+            }
+        }
+    }
+    fn set_value(&mut self, event: &SetDropdownValue, args: EventArgs) {
+        let &SetDropdownValue(ref value) = event;
+        if let Some(ref popup) = self.popup {
+            args.ui.close_popup(popup);
+        }
+        // also resets text typed into an editable dropdown
+        self.text_widget.event(TextUpdated(value.clone()));
+        if self.value.as_ref() != Some(value) {
+            self.value = Some(value.clone());
+            args.widget.event(DropdownChanged(value.clone()));
+        }
+    }
+    fn filter(&mut self, event: &FilterItems, args: EventArgs) {
+        let &FilterItems(ref text) = event;
+        // the text was set to the selected value, rather than typed
+        if self.value.as_ref() == Some(text) {
+            return;
+        }
+        self.open(text, args);
+    }
+    fn popup_closed(&mut self, event: &PopupClosed, mut args: EventArgs) {
+        if self.popup.as_ref() == Some(&event.0) {
+            self.popup = None;
+            args.widget.remove_prop(Property::Activated);
+        }
+    }
+    /// Opens the list of items containing `filter`, replacing the list if it's already open
+    fn open(&mut self, filter: &str, mut args: EventArgs) {
+        if let Some(popup) = self.popup.take() {
+            args.ui.close_popup(&popup);
+        }
+        let filter = filter.to_lowercase();
+        let items: Vec<String> = self.items.iter()
+            .filter(|item| item.to_lowercase().contains(&filter))
+            .cloned()
+            .collect();
+        if items.is_empty() {
+            args.widget.remove_prop(Property::Activated);
+            return;
+        }
+
+        let mut popup = Widget::from_modifier_style(ListStyle::default());
+        popup.set_name("dropdown_popup");
+        let mut draw_style = DrawStyle::from(self.popup_rect.clone());
+        draw_style.set_class("dropdown_popup");
+        popup.set_draw_style(draw_style);
+        popup.layout().add(constraints![
+            align_below(&args.widget).strength(STRONG),
+            align_left(&args.widget).strength(STRONG),
+            match_width(&args.widget),
+        ]);
+        {
+            let dropdown = &args.widget;
+            let value = &self.value;
+            popup.set_contents(items.into_iter(), |item, list| {
+                let mut item_widget = default_text_adapter(item.clone(), list);
+                if value.as_ref() == Some(&item) {
+                    item_widget.add_prop(Property::Selected);
+                    list.event(ListItemSelected { widget: Some(item_widget.clone()) });
+                }
+                let dropdown = dropdown.clone();
+                item_widget.add_handler(move |_: &ClickEvent, _: EventArgs| {
+                    dropdown.event(SetDropdownValue(item.clone()));
+                });
+                item_widget
+            });
+        }
+        args.ui.open_popup(&args.widget, popup.clone());
+        args.widget.add_prop(Property::Activated);
+        self.popup = Some(popup);
+    }
+}
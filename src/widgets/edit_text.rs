@@ -1,70 +1,528 @@
-use cassowary::Constraint;
+use std::cmp;
+use std::mem;
+use std::ops::Range;
+
+use glutin::{ElementState, VirtualKeyCode};
 
-use layout::constraint::ConstraintBuilder;
 use layout::constraint::*;
 use widget::{Widget, StyleUpdated};
-use ui::{WidgetAttachedEvent, WidgetDetachedEvent};
-use input::keyboard::{WidgetReceivedCharacter, KeyboardInputEvent};
+use input::keyboard::{WidgetReceivedCharacter, WidgetKeyboardInput};
+use input::drag::{DragEvent, DragState};
+use input::compose::{Composition, WidgetComposition};
+use clipboard::{ClipboardEvent, ClipboardContents};
+use widgets::scroll::ScrollIntoView;
+use widgets::text_filter::TextFilter;
+use geometry::{Point, RectExt};
+use history::{History, Command};
 use draw::rect::RectStyle;
 use draw::text::{TextState, TextStyle};
 use event::{EventHandler, EventArgs};
 use color::*;
+use widget::property::Property;
 use widget::property::states::*;
 use style::{WidgetModifier, DrawStyle};
 
 const BACKSPACE: char = '\u{8}';
+const BULLET: char = '\u{2022}';
 
 #[derive(Debug, Clone)]
 pub struct TextUpdated(pub String);
 
 multi_event!{impl EventHandler<EditTextEvent> for EditTextHandler {
     WidgetReceivedCharacter => received_char,
+    WidgetKeyboardInput => keyboard_input,
+    DragEvent => drag,
+    ClipboardContents => paste,
+    WidgetComposition => composition,
     TextUpdated => text_updated,
     StyleUpdated => style_updated,
 }}
 
+/// The text being edited, and the cursor and selection within it
+#[derive(Debug, Clone, PartialEq, Default)]
+struct EditState {
+    text: String,
+    /// Char index of the cursor
+    cursor: usize,
+    /// Char index of the other end of the selection, if any text is selected
+    anchor: Option<usize>,
+}
+
+impl EditState {
+    fn char_count(&self) -> usize {
+        self.text.chars().count()
+    }
+
+    fn byte_index(&self, char_index: usize) -> usize {
+        self.text.char_indices().nth(char_index).map(|(index, _)| index).unwrap_or_else(|| self.text.len())
+    }
+
+    fn selection(&self) -> Option<Range<usize>> {
+        match self.anchor {
+            Some(anchor) if anchor != self.cursor => {
+                Some(cmp::min(anchor, self.cursor)..cmp::max(anchor, self.cursor))
+            }
+            _ => None,
+        }
+    }
+
+    fn selected_text(&self) -> Option<String> {
+        self.selection().map(|selection| {
+            let (start, end) = (self.byte_index(selection.start), self.byte_index(selection.end));
+            self.text[start..end].to_owned()
+        })
+    }
+
+    /// Moves the cursor, extending the selection from the old cursor position if `select` is true,
+    /// otherwise clearing the selection
+    fn move_cursor(&mut self, char_index: usize, select: bool) {
+        if select {
+            if self.anchor.is_none() {
+                self.anchor = Some(self.cursor);
+            }
+        } else {
+            self.anchor = None;
+        }
+        self.cursor = cmp::min(char_index, self.char_count());
+    }
+
+    /// Removes the selected text, returns false if nothing was selected
+    fn delete_selection(&mut self) -> bool {
+        if let Some(selection) = self.selection() {
+            let (start, end) = (self.byte_index(selection.start), self.byte_index(selection.end));
+            self.text.drain(start..end);
+            self.cursor = selection.start;
+            self.anchor = None;
+            true
+        } else {
+            self.anchor = None;
+            false
+        }
+    }
+
+    /// Deletes the char before the cursor, or the selection
+    fn backspace(&mut self) {
+        if !self.delete_selection() && self.cursor > 0 {
+            let index = self.byte_index(self.cursor - 1);
+            self.text.remove(index);
+            self.cursor -= 1;
+        }
+    }
+
+    /// Deletes the char after the cursor, or the selection
+    fn delete(&mut self) {
+        if !self.delete_selection() && self.cursor < self.char_count() {
+            let index = self.byte_index(self.cursor);
+            self.text.remove(index);
+        }
+    }
+
+    /// Replaces the selection with `text`, at the cursor
+    fn insert(&mut self, text: &str) {
+        self.delete_selection();
+        let index = self.byte_index(self.cursor);
+        self.text.insert_str(index, text);
+        self.cursor += text.chars().count();
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum EditKind {
+    /// Typed a single char
+    Typed(char),
+    /// Deleted a single char, with backspace or delete
+    Deleted,
+    /// Any other change, ie. pasting or cutting text
+    Other,
+}
+
+/// Undoable edit, restores a snapshot of the `EditState`
+#[derive(Debug, Clone)]
+struct TextEdit {
+    state: EditState,
+    kind: EditKind,
+}
+
+impl Command for TextEdit {
+    type Context = EditState;
+    fn apply(self, state: &mut EditState) -> Self {
+        let previous = mem::replace(state, self.state);
+        TextEdit {
+            state: previous,
+            kind: self.kind,
+        }
+    }
+    /// Typing is undone a word at a time, and consecutive deletes together
+    fn coalesce(&mut self, newer: &Self) -> bool {
+        let coalesce = match (self.kind, newer.kind) {
+            (EditKind::Typed(last), EditKind::Typed(next)) => last.is_whitespace() || !next.is_whitespace(),
+            (EditKind::Deleted, EditKind::Deleted) => true,
+            _ => false,
+        };
+        if coalesce {
+            // keep the oldest snapshot
+            self.kind = newer.kind;
+        }
+        coalesce
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+enum LinePosition {
+    Start,
+    End,
+    Above,
+    Below,
+}
+
+/// How the text of an `EditText` or `TextArea` may be edited
+#[derive(Default)]
+pub(crate) struct EditConfig {
+    /// Allow newlines and text taller than the text box
+    pub multiline: bool,
+    /// Draw a bullet in place of each char
+    pub obscured: bool,
+    pub filters: Vec<TextFilter>,
+    pub validators: Vec<TextFilter>,
+}
+
 struct EditTextHandler {
     text_box: Widget,
-    text: String,
+    state: EditState,
+    history: History<TextEdit>,
+    multiline: bool,
+    obscured: bool,
+    filters: Vec<TextFilter>,
+    validators: Vec<TextFilter>,
+    invalid: bool,
+    /// Text being composed, drawn at the cursor but not yet part of the text
+    preedit: Option<String>,
 }
 
 impl EditTextHandler {
+    fn new(text_box: Widget, config: EditConfig) -> Self {
+        EditTextHandler {
+            text_box: text_box,
+            state: EditState::default(),
+            history: History::new(),
+            multiline: config.multiline,
+            obscured: config.obscured,
+            filters: config.filters,
+            validators: config.validators,
+            invalid: false,
+            preedit: None,
+        }
+    }
+
+    /// The text as it's drawn
+    fn display_text(&self, text: &str) -> String {
+        if self.obscured {
+            text.chars().map(|_| BULLET).collect()
+        } else {
+            text.to_owned()
+        }
+    }
+
     fn update_text(&mut self) {
-        let text = self.text.clone();
+        let (text, cursor, selection, underline) = match self.preedit {
+            Some(ref preedit) => {
+                // preview the text as it will be when the composition is committed
+                let mut state = self.state.clone();
+                state.delete_selection();
+                let start = state.cursor;
+                state.insert(preedit);
+                (state.text, state.cursor, None, Some(start..state.cursor))
+            }
+            None => (self.state.text.clone(), self.state.cursor, self.state.selection(), None),
+        };
+        let text = self.display_text(&text);
+        let focused = self.text_box.props().contains(&Property::Focused);
+        let cursor = if focused { Some(cursor) } else { None };
         self.text_box.update(|state: &mut TextState| {
             state.text = text;
+            state.cursor = cursor;
+            state.selection = selection;
+            state.underline = underline;
         });
     }
 
+    /// Applies `edit` to the text, recording it in the history if it changes the text,
+    /// the result is allowed by the filters, and fits in the text box
+    fn edit<F: FnOnce(&mut EditState)>(&mut self, kind: EditKind, edit: F, args: EventArgs) {
+        let mut state = self.state.clone();
+        edit(&mut state);
+        if state.text == self.state.text {
+            self.state = state;
+            self.update_text();
+            return;
+        }
+        if let EditKind::Typed(_) = kind {
+            if state.cursor == state.char_count() {
+                for filter in &self.filters {
+                    filter.fill_literals(&mut state.text);
+                }
+                state.cursor = state.char_count();
+            }
+        }
+        let allowed = self.filters.iter().all(|filter| filter.allows(&state.text));
+        let fits = self.multiline || {
+            let bounds = self.text_box.bounds();
+            let display_text = self.display_text(&state.text);
+            self.text_state(|text_state| text_state.text_fits(&display_text, bounds))
+        };
+        if allowed && fits {
+            let previous = mem::replace(&mut self.state, state);
+            self.history.record(TextEdit {
+                state: previous,
+                kind: kind,
+            });
+            self.text_changed(args);
+        }
+    }
+
+    fn move_cursor(&mut self, char_index: usize, select: bool) {
+        self.state.move_cursor(char_index, select);
+        self.history.seal();
+        self.scroll_to_cursor();
+    }
+
+    fn text_changed(&mut self, args: EventArgs) {
+        self.update_text();
+        self.scroll_to_cursor();
+        self.validate(args.widget.clone());
+        args.widget.event(TextUpdated(self.state.text.clone()));
+    }
+
+    /// Updates the `Invalid` property of the widget, empty text is always valid
+    fn validate(&mut self, mut widget: Widget) {
+        let invalid = {
+            let text = &self.state.text;
+            !text.is_empty() && !self.validators.iter().all(|validator| validator.validates(text))
+        };
+        if invalid != self.invalid {
+            self.invalid = invalid;
+            if invalid {
+                widget.add_prop(Property::Invalid);
+            } else {
+                widget.remove_prop(Property::Invalid);
+            }
+        }
+    }
+
+    /// Keeps the cursor visible if the text is inside a `ScrollContainer`
+    fn scroll_to_cursor(&self) {
+        if self.multiline {
+            let (bounds, cursor) = (self.text_box.bounds(), self.state.cursor);
+            let rect = self.text_state(|state| state.cursor_rect(bounds, cursor));
+            self.text_box.event_bubble_up(ScrollIntoView(rect));
+        }
+    }
+
+    /// The char index of the cursor position at the start or end of the line containing the cursor,
+    /// or on the line above or below it
+    fn line_position(&self, position: LinePosition) -> usize {
+        let (bounds, cursor) = (self.text_box.bounds(), self.state.cursor);
+        self.text_state(|state| {
+            let rect = state.cursor_rect(bounds, cursor);
+            let line_height = state.line_height();
+            let point = match position {
+                LinePosition::Start => Point::new(bounds.left(), rect.center().y),
+                LinePosition::End => Point::new(bounds.right(), rect.center().y),
+                LinePosition::Above => Point::new(rect.left(), rect.center().y - line_height),
+                LinePosition::Below => Point::new(rect.left(), rect.center().y + line_height),
+            };
+            state.closest_char_index(bounds, point)
+        })
+    }
+
     fn received_char(&mut self, event: &WidgetReceivedCharacter, args: EventArgs) {
         let &WidgetReceivedCharacter(char) = event;
+        // while composing, input is handled by the input method
+        if self.preedit.is_some() {
+            return;
+        }
         match char {
-            BACKSPACE => {
-                self.text.pop();
-            }
+            BACKSPACE => self.edit(EditKind::Deleted, |state| state.backspace(), args),
+            '\r' | '\n' if self.multiline => self.edit(EditKind::Typed('\n'), |state| state.insert("\n"), args),
+            // other control characters are handled as keyboard input, if at all
+            _ if char.is_control() => (),
             _ => {
-                self.text.push(char);
-                let bounds = self.text_box.bounds();
-                let draw_state = self.text_box.draw_state();
-                let text_draw_state = draw_state.downcast_ref::<TextState>().unwrap();
-                if !text_draw_state.text_fits(&self.text, bounds) {
-                    self.text.pop();
+                self.edit(EditKind::Typed(char), |state| {
+                    let mut buf = [0; 4];
+                    state.insert(char.encode_utf8(&mut buf));
+                }, args);
+            }
+        }
+    }
+
+    fn keyboard_input(&mut self, event: &WidgetKeyboardInput, args: EventArgs) {
+        let &WidgetKeyboardInput(input) = event;
+        if input.state != ElementState::Pressed || self.preedit.is_some() {
+            return;
+        }
+        let (shift, ctrl) = (input.modifiers.shift, input.modifiers.ctrl);
+        let bounds = self.text_box.bounds();
+        let cursor = self.state.cursor;
+        let selection = self.state.selection();
+        match input.virtual_keycode {
+            Some(VirtualKeyCode::Left) => {
+                let index = match selection {
+                    Some(ref selection) if !shift => selection.start,
+                    _ if ctrl => self.text_state(|state| state.previous_word_start(bounds, cursor)),
+                    _ => cursor.saturating_sub(1),
+                };
+                self.move_cursor(index, shift);
+            }
+            Some(VirtualKeyCode::Right) => {
+                let index = match selection {
+                    Some(ref selection) if !shift => selection.end,
+                    _ if ctrl => self.text_state(|state| state.next_word_end(bounds, cursor)),
+                    _ => cursor + 1,
+                };
+                self.move_cursor(index, shift);
+            }
+            Some(VirtualKeyCode::Up) if self.multiline => {
+                let index = self.line_position(LinePosition::Above);
+                self.move_cursor(index, shift);
+            }
+            Some(VirtualKeyCode::Down) if self.multiline => {
+                let index = self.line_position(LinePosition::Below);
+                self.move_cursor(index, shift);
+            }
+            Some(VirtualKeyCode::Home) if self.multiline && !ctrl => {
+                let index = self.line_position(LinePosition::Start);
+                self.move_cursor(index, shift);
+            }
+            Some(VirtualKeyCode::End) if self.multiline && !ctrl => {
+                let index = self.line_position(LinePosition::End);
+                self.move_cursor(index, shift);
+            }
+            Some(VirtualKeyCode::Home) => self.move_cursor(0, shift),
+            Some(VirtualKeyCode::End) => {
+                let end = self.state.char_count();
+                self.move_cursor(end, shift);
+            }
+            Some(VirtualKeyCode::A) if ctrl => {
+                self.move_cursor(0, false);
+                let end = self.state.char_count();
+                self.move_cursor(end, true);
+            }
+            // obscured text can't be copied
+            Some(VirtualKeyCode::C) | Some(VirtualKeyCode::X) if ctrl && self.obscured => return,
+            Some(VirtualKeyCode::C) if ctrl => {
+                if let Some(text) = self.state.selected_text() {
+                    args.ui.event(ClipboardEvent::Set(text));
+                }
+                return;
+            }
+            Some(VirtualKeyCode::X) if ctrl => {
+                if let Some(text) = self.state.selected_text() {
+                    args.ui.event(ClipboardEvent::Set(text));
+                    self.edit(EditKind::Other, |state| { state.delete_selection(); }, args);
                 }
+                return;
+            }
+            Some(VirtualKeyCode::V) if ctrl => {
+                args.ui.event(ClipboardEvent::Get(args.widget.clone()));
+                return;
+            }
+            Some(VirtualKeyCode::Z) if ctrl && shift => {
+                self.redo(args);
+                return;
+            }
+            Some(VirtualKeyCode::Z) if ctrl => {
+                self.undo(args);
+                return;
+            }
+            Some(VirtualKeyCode::Y) if ctrl => {
+                self.redo(args);
+                return;
+            }
+            Some(VirtualKeyCode::Delete) => {
+                self.edit(EditKind::Deleted, |state| state.delete(), args);
+                return;
             }
+            _ => return,
         }
         self.update_text();
-        args.widget.event(TextUpdated(self.text.clone()));
     }
 
-    fn text_updated(&mut self, event: &TextUpdated, _: EventArgs) {
+    fn undo(&mut self, args: EventArgs) {
+        if self.history.undo(&mut self.state) {
+            self.text_changed(args);
+        }
+    }
+
+    fn redo(&mut self, args: EventArgs) {
+        if self.history.redo(&mut self.state) {
+            self.text_changed(args);
+        }
+    }
+
+    fn drag(&mut self, event: &DragEvent, _: EventArgs) {
+        let bounds = self.text_box.bounds();
+        let index = self.text_state(|state| state.closest_char_index(bounds, event.position));
+        let select = event.state != DragState::Start;
+        self.move_cursor(index, select);
+        self.update_text();
+    }
+
+    fn paste(&mut self, event: &ClipboardContents, args: EventArgs) {
+        if let ClipboardContents(Some(ref text)) = *event {
+            self.edit(EditKind::Other, |state| state.insert(text), args);
+        }
+    }
+
+    fn text_updated(&mut self, event: &TextUpdated, args: EventArgs) {
         let &TextUpdated(ref text) = event;
-        self.text = text.clone();
+        if *text != self.state.text {
+            self.state.text = text.clone();
+            let end = self.state.char_count();
+            self.state.move_cursor(end, false);
+            self.history.clear();
+            self.update_text();
+            self.validate(args.widget);
+        }
+    }
+
+    fn composition(&mut self, event: &WidgetComposition, args: EventArgs) {
+        let &WidgetComposition(ref composition) = event;
+        match *composition {
+            Composition::Start => self.preedit = Some(String::new()),
+            Composition::Update(ref text) => self.preedit = Some(text.clone()),
+            Composition::Commit(ref text) => {
+                self.preedit = None;
+                let mut chars = text.chars();
+                match (chars.next(), chars.next()) {
+                    (None, _) => (),
+                    (Some(char), None) => self.edit(EditKind::Typed(char), |state| state.insert(text), args),
+                    _ => self.edit(EditKind::Other, |state| state.insert(text), args),
+                }
+            }
+        }
         self.update_text();
     }
 
     fn style_updated(&mut self, _: &StyleUpdated, _: EventArgs) {
         self.update_text();
     }
+
+    fn text_state<F: FnOnce(&TextState) -> T, T>(&self, f: F) -> T {
+        let mut text_box = self.text_box.clone();
+        let draw_state = text_box.draw_state();
+        let text_state = draw_state.downcast_ref::<TextState>().unwrap();
+        f(text_state)
+    }
+}
+
+/// Makes `widget` a focusable text editor, editing the text drawn by `text_widget`, which
+/// should be a child with a `TextState`. Shared by `EditText` and `TextArea`.
+pub(crate) fn add_edit_handlers(widget: &mut Widget, text_widget: &Widget, config: EditConfig) {
+    widget
+        .add_handler(EditTextHandler::new(text_widget.clone(), config))
+        .enable_focus()
+        .make_draggable();
+    EditTextHandler::add_adapters(widget);
 }
 
 component_style!{pub struct EditText<name="scroll", style=EditTextStyle> {
@@ -75,67 +533,49 @@ component_style!{pub struct EditText<name="scroll", style=EditTextStyle> {
     focused_rect: Option<RectStyle> = Some(style!(RectStyle {
         border: Some((1.0, BLUE)),
     })),
+    invalid_rect: Option<RectStyle> = Some(style!(RectStyle {
+        border: Some((1.0, RED)),
+    })),
+    // Draw a bullet in place of each char, ie. for passwords
+    obscured: bool = false,
+    // Edits that result in text that isn't allowed by every filter are rejected
+    filters: Vec<TextFilter> = Vec::new(),
+    // The widget has the `Invalid` property while the text fails any validator
+    validators: Vec<TextFilter> = Vec::new(),
 }}
 
 impl WidgetModifier for EditText {
     fn apply(&self, widget: &mut Widget) {
         let mut text_widget = Widget::new("edit_text_text");
         let mut draw_style = DrawStyle::from(self.rect.clone());
-        widget
-            .add_handler(|_: &WidgetAttachedEvent, args: EventArgs| {
-                args.ui.event(KeyboardInputEvent::AddFocusable(args.widget));
-            })
-            .add_handler(|_: &WidgetDetachedEvent, args: EventArgs| {
-                args.ui.event(KeyboardInputEvent::RemoveFocusable(args.widget));
-            })
-            .add_handler(EditTextHandler {
-                text_box: text_widget.clone(),
-                text: "".to_owned(),
-            })
-            .make_focusable();
-        EditTextHandler::add_adapters(widget);
+        add_edit_handlers(widget, &text_widget, EditConfig {
+            multiline: false,
+            obscured: self.obscured,
+            filters: self.filters.clone(),
+            validators: self.validators.clone(),
+        });
 
         if let Some(ref focused_rect) = self.focused_rect {
             draw_style.prop_style(FOCUSED.clone(), focused_rect.clone());
         }
+        if let Some(ref invalid_rect) = self.invalid_rect {
+            draw_style.prop_style(INVALID.clone(), invalid_rect.clone());
+        }
         widget.set_draw_style(draw_style);
 
+        let mut text_style = DrawStyle::from(TextStyle::default());
+        text_style.set_class("edit_text");
         text_widget
-            .set_draw_style(DrawStyle::from(TextStyle::default()))
-            .add_handler(TextHeightHandler::default());
+            .set_draw_style(text_style)
+            .size_to_content();
 
         text_widget.layout().add(constraints![
             align_left(widget).padding(5.0),
             align_top(widget).padding(5.0),
+            align_right(widget).padding(5.0),
             bound_by(widget).padding(5.0),
         ]);
         widget.add_child(text_widget);
     }
 }
 
-// Ensures the edit text is at least tall enough to fit the text. Width is unconstrained.
-#[derive(Default)]
-struct TextHeightHandler {
-    measured_height: f32,
-    size_constraints: Vec<Constraint>,
-}
-
-impl EventHandler<StyleUpdated> for TextHeightHandler {
-    fn handle(&mut self, _: &StyleUpdated, mut args: EventArgs) {
-        let line_height = {
-            let draw_state = args.widget.draw_state();
-            let text_draw_state = draw_state.downcast_ref::<TextState>().unwrap();
-            text_draw_state.line_height()
-        };
-        if self.measured_height != line_height {
-            let size_constraints = min_height(line_height).build(&args.widget.layout_vars());
-            let mut layout = args.widget.layout();
-            for constraint in self.size_constraints.drain(..) {
-                layout.remove_constraint(constraint);
-            }
-            layout.add(size_constraints.clone());
-            self.size_constraints = size_constraints;
-            self.measured_height = line_height;
-        }
-    }
-}
@@ -1,6 +1,5 @@
 use widget::Widget;
 use draw::image::ImageState;
-use layout::constraint::*;
 use resources::image::ImageSource;
 
 use style::*;
@@ -26,10 +25,8 @@ impl Component for Image {
 
 impl WidgetModifier for Image {
     fn apply(&self, widget: &mut Widget) {
-        let image_draw_state = ImageState::new(self.source.clone());
-        let image_size = image_draw_state.measure();
         widget.set_name("image");
-        widget.set_draw_state(image_draw_state);
-        widget.layout().add(size(image_size));
+        widget.set_draw_state(ImageState::new(self.source.clone()));
+        widget.size_to_content();
     }
 }
@@ -0,0 +1,611 @@
+//! Menus, opened as a context menu by right clicking a widget, or from a `MenuBar`.
+//!
+//! A `Menu` is a list of `MenuItem`s, actions, check items, separators and submenus. Selecting an
+//! item sends it's event to the target of the menu, the widget that was right clicked, or the menu
+//! bar, bubbling up from there, and closes the menu.
+//! Open menus are popups, see `popup`, and have keyboard focus while they're open: up and down
+//! move between the items, return or space selects an item, right and left open and close
+//! submenus, or move between the menus of a menu bar, and escape closes the menu.
+//!
+//! ```no_run
+//! # use limn::prelude::*;
+//! # use limn::glutin::VirtualKeyCode;
+//! use limn::input::shortcuts::KeyChord;
+//! use limn::widgets::menu::{Menu, MenuItem};
+//! #[derive(Clone)]
+//! struct CopySelection;
+//! #[derive(Clone)]
+//! struct ShowGrid(bool);
+//! # let mut canvas = Widget::new("canvas");
+//! canvas.set_context_menu(Menu::new()
+//!     .item(MenuItem::new("Copy").event(CopySelection).shortcut(KeyChord::new(VirtualKeyCode::C).ctrl()))
+//!     .separator()
+//!     .item(MenuItem::check("Show grid", true).event_with(ShowGrid)));
+//! ```
+
+use std::rc::Rc;
+use std::cell::Cell;
+
+use glutin::{ElementState, MouseButton, VirtualKeyCode};
+use cassowary::strength::*;
+
+use layout::constraint::*;
+use layout::linear_layout::{LinearLayoutSettings, Orientation, ItemAlignment};
+use event::{EventHandler, EventArgs};
+use widget::Widget;
+use widget::property::Property;
+use input::mouse::{ClickEvent, WidgetMouseButton, MouseOverEvent};
+use input::keyboard::WidgetKeyboardInput;
+use input::shortcuts::KeySequence;
+use popup::PopupClosed;
+use widgets::text::StaticTextStyle;
+use draw::rect::RectStyle;
+use draw::text::TextStyle;
+use geometry::{Point, Size};
+use ui::Ui;
+use color::*;
+use style::*;
+use widget::property::states::*;
+
+/// A list of items, shown as a context menu, a submenu, or one of the menus of a `MenuBar`
+#[derive(Clone, Default)]
+pub struct Menu {
+    items: Vec<MenuItem>,
+}
+
+impl Menu {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Adds `item` to the end of the menu
+    pub fn item(mut self, item: MenuItem) -> Self {
+        self.items.push(item);
+        self
+    }
+    /// Adds a line separating groups of items
+    pub fn separator(mut self) -> Self {
+        self.items.push(MenuItem {
+            label: String::new(),
+            kind: ItemKind::Separator,
+            shortcut: None,
+            action: None,
+        });
+        self
+    }
+    /// Adds an item that opens `menu` as a submenu
+    pub fn submenu(mut self, label: &str, menu: Menu) -> Self {
+        self.items.push(MenuItem {
+            label: label.to_owned(),
+            kind: ItemKind::Submenu(menu),
+            shortcut: None,
+            action: None,
+        });
+        self
+    }
+}
+
+/// An item of a `Menu`, that sends an event when selected
+#[derive(Clone)]
+pub struct MenuItem {
+    label: String,
+    kind: ItemKind,
+    shortcut: Option<KeySequence>,
+    action: Option<Rc<Fn(&Widget, bool)>>,
+}
+
+#[derive(Clone)]
+enum ItemKind {
+    Action,
+    /// Shared by every widget created for the item, so the state is kept between openings of the menu
+    Check(Rc<Cell<bool>>),
+    Submenu(Menu),
+    Separator,
+}
+
+impl MenuItem {
+    pub fn new(label: &str) -> Self {
+        MenuItem {
+            label: label.to_owned(),
+            kind: ItemKind::Action,
+            shortcut: None,
+            action: None,
+        }
+    }
+    /// An item with a check mark, that is toggled when the item is selected
+    pub fn check(label: &str, checked: bool) -> Self {
+        MenuItem {
+            kind: ItemKind::Check(Rc::new(Cell::new(checked))),
+            ..MenuItem::new(label)
+        }
+    }
+    /// Sends `event` to the target of the menu when the item is selected
+    pub fn event<E: Clone + 'static>(self, event: E) -> Self {
+        self.event_with(move |_| event.clone())
+    }
+    /// Sends the event returned by `event` to the target of the menu when the item is selected,
+    /// `event` is called with the new state of a check item, or false for other items
+    pub fn event_with<E: 'static, F: Fn(bool) -> E + 'static>(mut self, event: F) -> Self {
+        self.action = Some(Rc::new(move |target: &Widget, checked| {
+            target.event_bubble_up(event(checked));
+        }));
+        self
+    }
+    /// Shows the keys of a shortcut for the item. The shortcut itself is registered separately,
+    /// see `input::shortcuts`
+    pub fn shortcut<S: Into<KeySequence>>(mut self, shortcut: S) -> Self {
+        self.shortcut = Some(shortcut.into());
+        self
+    }
+    /// Returns true if this is a check item, and it's checked
+    pub fn is_checked(&self) -> bool {
+        match self.kind {
+            ItemKind::Check(ref checked) => checked.get(),
+            _ => false,
+        }
+    }
+}
+
+/// A horizontal bar of menus, each opened by clicking it's label, ie. at the top of a window.
+/// Selected items send their events to the menu bar.
+#[derive(Clone, Default)]
+pub struct MenuBar {
+    menus: Vec<(String, Menu)>,
+}
+
+impl MenuBar {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Adds `menu`, opened by clicking `label`, to the end of the bar
+    pub fn menu(mut self, label: &str, menu: Menu) -> Self {
+        self.menus.push((label.to_owned(), menu));
+        self
+    }
+}
+
+impl Component for MenuBar {
+    fn name() -> String {
+        String::from("menu_bar")
+    }
+}
+
+impl WidgetModifier for MenuBar {
+    fn apply(&self, widget: &mut Widget) {
+        let mut draw_style = DrawStyle::from(style!(RectStyle {
+            background_color: GRAY_90,
+        }));
+        draw_style.set_class("menu_bar");
+        widget.set_draw_style(draw_style);
+        let mut settings = LinearLayoutSettings::new(Orientation::Horizontal);
+        settings.item_align = ItemAlignment::Fill;
+        widget.linear_layout(settings);
+        widget.layout().add(shrink_vertical());
+
+        let mut menus = Vec::new();
+        for (index, &(ref label, ref menu)) in self.menus.iter().enumerate() {
+            let mut item = Widget::new("menu_bar_item");
+            let mut draw_style = DrawStyle::from(style!(RectStyle {
+                background_color: TRANSPARENT,
+            }));
+            draw_style.prop_style(ACTIVATED.clone(), style!(RectStyle {
+                background_color: GRAY_80,
+            }));
+            draw_style.set_class("menu_bar_item");
+            item.set_draw_style(draw_style);
+            let mut text = menu_text("menu_bar_item_text", label);
+            text.layout().add(constraints![
+                bound_by(&item).padding(5.0),
+                align_left(&item).padding(10.0),
+                align_right(&item).padding(10.0),
+                center_vertical(&item),
+            ]);
+            item.add_child(text);
+            item
+                .add_handler(move |_: &ClickEvent, args: EventArgs| {
+                    args.widget.event_bubble_up(MenuBarInput::Open(index));
+                })
+                .add_handler(move |event: &MouseOverEvent, args: EventArgs| {
+                    if let MouseOverEvent::Over = *event {
+                        args.widget.event_bubble_up(MenuBarInput::Hover(index));
+                    }
+                });
+            widget.add_child(item.clone());
+            menus.push((item, menu.clone()));
+        }
+        widget.add_handler(MenuBarHandler {
+            menus: menus,
+            open: None,
+        });
+        MenuBarHandler::add_adapters(widget);
+    }
+}
+
+impl Widget {
+    /// Opens `menu` at the mouse position when the widget is right clicked,
+    /// selected items send their events to this widget
+    pub fn set_context_menu(&mut self, menu: Menu) -> &mut Self {
+        self.add_handler(move |event: &WidgetMouseButton, args: EventArgs| {
+            if let WidgetMouseButton(ElementState::Pressed, MouseButton::Right) = *event {
+                *args.handled = true;
+                if let Some(position) = args.ui.cursor_position() {
+                    args.ui.open_context_menu(&args.widget, &menu, position);
+                }
+            }
+        })
+    }
+}
+
+impl Ui {
+    /// Opens `menu` with it's top left corner at `position`, or as close as it fits in the window,
+    /// selected items send their events to `target`
+    pub fn open_context_menu(&mut self, target: &Widget, menu: &Menu, position: Point) {
+        let mut menu = menu_widget(menu, target, None);
+        menu.layout().add(top_left(position).strength(STRONG));
+        let owner = menu.clone();
+        open_menu(self, &owner, menu);
+    }
+}
+
+/// Opens `menu` as a popup that takes keyboard focus, until it's closed
+fn open_menu(ui: &mut Ui, owner: &Widget, menu: Widget) {
+    ui.open_popup(owner, menu.clone());
+    menu.push_focus_scope();
+}
+
+fn menu_text(name: &str, text: &str) -> Widget {
+    let mut widget = Widget::new(name);
+    widget.set_draw_style(DrawStyle::from_class::<TextStyle>(name));
+    StaticTextStyle::from_text(text).component().apply(&mut widget);
+    widget
+}
+
+/// Creates the widget for `menu`, `parent` is the menu it's a submenu of
+fn menu_widget(menu: &Menu, target: &Widget, parent: Option<&Widget>) -> Widget {
+    let mut widget = Widget::new("menu");
+    let mut draw_style = DrawStyle::from(style!(RectStyle {
+        background_color: WHITE,
+        border: Some((1.0, GRAY_30)),
+    }));
+    draw_style.set_class("menu");
+    widget.set_draw_style(draw_style);
+    let mut settings = LinearLayoutSettings::new(Orientation::Vertical);
+    settings.item_align = ItemAlignment::Fill;
+    widget.linear_layout(settings);
+    widget.layout().add(constraints![
+        min_width(150.0),
+        shrink(),
+    ]);
+
+    let mut entries = Vec::new();
+    for item in &menu.items {
+        if let ItemKind::Separator = item.kind {
+            let mut separator = Widget::new("menu_separator");
+            separator.layout().add(height(9.0));
+            let mut line = Widget::new("menu_separator_line");
+            let mut draw_style = DrawStyle::from(style!(RectStyle {
+                background_color: GRAY_80,
+            }));
+            draw_style.set_class("menu_separator");
+            line.set_draw_style(draw_style);
+            line.layout().add(constraints![
+                height(1.0),
+                center_vertical(&separator),
+                match_width(&separator).padding(4.0),
+                center_horizontal(&separator),
+            ]);
+            separator.add_child(line);
+            widget.add_child(separator);
+        } else {
+            let item_widget = item_widget(item, entries.len());
+            widget.add_child(item_widget.clone());
+            entries.push((item_widget, item.clone()));
+        }
+    }
+    widget
+        .add_handler(MenuHandler {
+            entries: entries,
+            highlighted: None,
+            target: target.clone(),
+            parent: parent.cloned(),
+            submenu: None,
+        })
+        .enable_focus();
+    MenuHandler::add_adapters(&mut widget);
+    widget
+}
+
+fn item_widget(item: &MenuItem, index: usize) -> Widget {
+    let mut widget = Widget::new("menu_item");
+    let mut draw_style = DrawStyle::from(style!(RectStyle {
+        background_color: TRANSPARENT,
+    }));
+    draw_style.prop_style(SELECTED.clone(), style!(RectStyle {
+        background_color: GRAY_80,
+    }));
+    draw_style.set_class("menu_item");
+    widget.set_draw_style(draw_style);
+
+    // drawn while a check item is checked, like the mark of a checkbox
+    let mut check_mark = Widget::new("menu_item_check");
+    let mut mark_style = DrawStyle::from(style!(RectStyle {
+        background_color: TRANSPARENT,
+    }));
+    mark_style.prop_style(ACTIVATED.clone(), style!(RectStyle {
+        background_color: BLUE,
+    }));
+    check_mark.set_draw_style(mark_style);
+    check_mark.layout().add(constraints![
+        size(Size::new(8.0, 8.0)),
+        align_left(&widget).padding(8.0),
+        center_vertical(&widget),
+    ]);
+
+    let mut label = menu_text("menu_item_text", &item.label);
+    label.layout().add(constraints![
+        align_left(&widget).padding(24.0),
+        bound_top(&widget).padding(4.0),
+        bound_bottom(&widget).padding(4.0),
+        center_vertical(&widget),
+    ]);
+    let right_text = match item.kind {
+        ItemKind::Submenu(_) => Some(">".to_owned()),
+        _ => item.shortcut.as_ref().map(|shortcut| shortcut.to_string()),
+    };
+    if let Some(right_text) = right_text {
+        let mut shortcut = menu_text("menu_item_shortcut", &right_text);
+        shortcut.layout().add(constraints![
+            to_right_of(&label).padding(24.0),
+            align_right(&widget).padding(8.0),
+            bound_top(&widget).padding(4.0),
+            bound_bottom(&widget).padding(4.0),
+            center_vertical(&widget),
+        ]);
+        widget.add_child(shortcut);
+    } else {
+        label.layout().add(bound_right(&widget).padding(8.0));
+    }
+    widget.add_child(check_mark);
+    widget.add_child(label);
+    if item.is_checked() {
+        widget.add_prop(Property::Activated);
+    }
+    widget
+        .add_handler(move |_: &ClickEvent, args: EventArgs| {
+            args.widget.event_bubble_up(MenuInput::Activate(index));
+        })
+        .add_handler(move |event: &MouseOverEvent, args: EventArgs| {
+            if let MouseOverEvent::Over = *event {
+                args.widget.event_bubble_up(MenuInput::Hover(index));
+            }
+        });
+    widget
+}
+
+/// Sent from the items of a menu to the menu, or to a menu to highlight one of it's items
+#[derive(Debug, Copy, Clone)]
+enum MenuInput {
+    Hover(usize),
+    Activate(usize),
+    Highlight(usize),
+}
+
+/// Sent from a menu of a menu bar to the bar, to open the previous or next menu
+#[derive(Debug, Copy, Clone)]
+struct SwitchMenu(isize);
+
+multi_event!{impl EventHandler<MenuHandlerEvent> for MenuHandler {
+    MenuInput => input,
+    WidgetKeyboardInput => keyboard_input,
+    PopupClosed => popup_closed,
+}}
+
+struct MenuHandler {
+    /// The widgets of the items that can be selected, ie. not separators
+    entries: Vec<(Widget, MenuItem)>,
+    highlighted: Option<usize>,
+    /// Receives the events of the selected items
+    target: Widget,
+    /// The menu this is a submenu of
+    parent: Option<Widget>,
+    /// The open submenu, and the index of it's item
+    submenu: Option<(usize, Widget)>,
+}
+
+impl MenuHandler {
+    fn input(&mut self, event: &MenuInput, args: EventArgs) {
+        match *event {
+            MenuInput::Hover(index) => {
+                self.highlight(Some(index));
+                self.open_submenu(index, args);
+            }
+            MenuInput::Activate(index) => self.activate(index, args),
+            MenuInput::Highlight(index) => {
+                if index < self.entries.len() {
+                    self.highlight(Some(index));
+                }
+            }
+        }
+    }
+    fn keyboard_input(&mut self, event: &WidgetKeyboardInput, args: EventArgs) {
+        let &WidgetKeyboardInput(input) = event;
+        let len = self.entries.len();
+        if input.state != ElementState::Pressed || len == 0 {
+            return;
+        }
+        match input.virtual_keycode {
+            Some(VirtualKeyCode::Down) => {
+                let next = self.highlighted.map_or(0, |index| (index + 1) % len);
+                self.highlight(Some(next));
+            }
+            Some(VirtualKeyCode::Up) => {
+                let previous = self.highlighted.map_or(len - 1, |index| (index + len - 1) % len);
+                self.highlight(Some(previous));
+            }
+            Some(VirtualKeyCode::Return) | Some(VirtualKeyCode::Space) => {
+                if let Some(index) = self.highlighted {
+                    self.activate(index, args);
+                }
+            }
+            Some(VirtualKeyCode::Right) => {
+                let submenu = self.highlighted.and_then(|index| {
+                    if let ItemKind::Submenu(_) = self.entries[index].1.kind { Some(index) } else { None }
+                });
+                if let Some(index) = submenu {
+                    self.open_submenu(index, args);
+                    if let Some((_, ref submenu)) = self.submenu {
+                        submenu.event(MenuInput::Highlight(0));
+                    }
+                } else if self.parent.is_none() {
+                    self.target.event(SwitchMenu(1));
+                }
+            }
+            Some(VirtualKeyCode::Left) => {
+                if self.parent.is_some() {
+                    args.ui.close_popup(&args.widget);
+                } else {
+                    self.target.event(SwitchMenu(-1));
+                }
+            }
+            _ => (),
+        }
+    }
+    fn popup_closed(&mut self, event: &PopupClosed, args: EventArgs) {
+        if event.0 == args.widget {
+            args.widget.pop_focus_scope();
+        } else if self.submenu.as_ref().map_or(false, |&(_, ref submenu)| *submenu == event.0) {
+            self.submenu = None;
+        }
+    }
+    fn highlight(&mut self, index: Option<usize>) {
+        if index != self.highlighted {
+            if let Some(previous) = self.highlighted {
+                self.entries[previous].0.remove_prop(Property::Selected);
+            }
+            if let Some(index) = index {
+                self.entries[index].0.add_prop(Property::Selected);
+            }
+            self.highlighted = index;
+        }
+    }
+    fn activate(&mut self, index: usize, args: EventArgs) {
+        let item = self.entries[index].1.clone();
+        let checked = match item.kind {
+            ItemKind::Submenu(_) => {
+                self.open_submenu(index, args);
+                return;
+            }
+            ItemKind::Check(ref checked) => {
+                checked.set(!checked.get());
+                checked.get()
+            }
+            _ => false,
+        };
+        if let Some(ref action) = item.action {
+            action(&self.target, checked);
+        }
+        // closes this menu, and any menus it's a submenu of
+        args.ui.close_popups();
+    }
+    /// Opens the submenu of the item at `index`, if it has one, closing any other open submenu
+    fn open_submenu(&mut self, index: usize, args: EventArgs) {
+        if self.submenu.as_ref().map_or(false, |&(open, _)| open == index) {
+            return;
+        }
+        if let Some((_, submenu)) = self.submenu.take() {
+            args.ui.close_popup(&submenu);
+        }
+        if let ItemKind::Submenu(ref menu) = self.entries[index].1.kind {
+            let item = &self.entries[index].0;
+            let mut submenu = menu_widget(menu, &self.target, Some(&args.widget));
+            submenu.layout().add(constraints![
+                align_to_right_of(item).strength(STRONG),
+                align_top(item).strength(STRONG),
+            ]);
+            open_menu(args.ui, &args.widget, submenu.clone());
+            self.submenu = Some((index, submenu));
+        }
+    }
+}
+
+/// Sent from the items of a menu bar to the bar
+#[derive(Debug, Copy, Clone)]
+enum MenuBarInput {
+    Open(usize),
+    Hover(usize),
+}
+
+multi_event!{impl EventHandler<MenuBarEvent> for MenuBarHandler {
+    MenuBarInput => input,
+    SwitchMenu => switch_menu,
+    PopupClosed => popup_closed,
+}}
+
+struct MenuBarHandler {
+    menus: Vec<(Widget, Menu)>,
+    /// The open menu, and it's index
+    open: Option<(usize, Widget)>,
+}
+
+impl MenuBarHandler {
+    fn input(&mut self, event: &MenuBarInput, args: EventArgs) {
+        let open = self.open.as_ref().map(|&(index, _)| index);
+        match *event {
+            MenuBarInput::Open(index) => {
+                if open == Some(index) {
+                    self.close(args);
+                } else {
+                    self.open(index, args);
+                }
+            }
+            // once a menu is open, hovering over the other menus opens them
+            MenuBarInput::Hover(index) => {
+                if open.is_some() && open != Some(index) {
+                    self.open(index, args);
+                }
+            }
+        }
+    }
+    fn switch_menu(&mut self, event: &SwitchMenu, args: EventArgs) {
+        let &SwitchMenu(offset) = event;
+        if let Some(index) = self.open.as_ref().map(|&(index, _)| index) {
+            let len = self.menus.len() as isize;
+            let next = ((index as isize + offset) % len + len) % len;
+            self.open(next as usize, args);
+            if let Some((_, ref menu)) = self.open {
+                menu.event(MenuInput::Highlight(0));
+            }
+        }
+    }
+    fn popup_closed(&mut self, event: &PopupClosed, _: EventArgs) {
+        let closed = self.open.as_ref().map_or(false, |&(_, ref menu)| *menu == event.0);
+        if closed {
+            if let Some((index, _)) = self.open.take() {
+                self.menus[index].0.remove_prop(Property::Activated);
+            }
+        }
+    }
+    fn close(&mut self, args: EventArgs) {
+        if let Some((index, menu)) = self.open.take() {
+            self.menus[index].0.remove_prop(Property::Activated);
+            args.ui.close_popup(&menu);
+        }
+    }
+    fn open(&mut self, index: usize, args: EventArgs) {
+        if let Some((previous, menu)) = self.open.take() {
+            self.menus[previous].0.remove_prop(Property::Activated);
+            args.ui.close_popup(&menu);
+        }
+        let mut menu = menu_widget(&self.menus[index].1, &args.widget, None);
+        {
+            let item = &mut self.menus[index].0;
+            menu.layout().add(constraints![
+                align_below(item).strength(STRONG),
+                align_left(item).strength(STRONG),
+            ]);
+            item.add_prop(Property::Activated);
+        }
+        // presses on the bar don't close the menu, so the other menus can be opened
+        open_menu(args.ui, &args.widget, menu.clone());
+        self.open = Some((index, menu));
+    }
+}
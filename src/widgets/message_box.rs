@@ -0,0 +1,127 @@
+//! A message box, a dialog with an optional title, a message, and a row of buttons that close it.
+//!
+//! Usually opened with `Ui::alert`, `Ui::confirm`, or `Ui::open_message_box`, see `dialog`.
+//! Buttons can be focused, and pressed with return or space.
+
+use glutin::{ElementState, VirtualKeyCode};
+
+use layout::constraint::*;
+use event::EventArgs;
+use widget::Widget;
+use input::mouse::ClickEvent;
+use input::keyboard::WidgetKeyboardInput;
+use dialog::{DialogResponse, CloseDialog};
+use widgets::button::ButtonStyle;
+use widgets::text::StaticTextStyle;
+use draw::rect::RectStyle;
+use draw::text::TextStyle;
+use color::*;
+use style::*;
+
+component_style!{pub struct MessageBox<name="message_box", style=MessageBoxStyle> {
+    rect: RectStyle = style!(RectStyle {
+        background_color: WHITE,
+        border: Some((1.0, GRAY_30)),
+        corner_radius: Some(5.0),
+    }),
+    text: TextStyle = TextStyle::default(),
+    title: Option<String> = None,
+    message: String = String::new(),
+    // A button for each response, from left to right
+    buttons: Vec<DialogResponse> = vec![DialogResponse::Ok],
+}}
+
+impl MessageBoxStyle {
+    /// A message box with `message` and an OK button
+    pub fn alert(message: &str) -> Self {
+        Self {
+            message: Some(message.to_owned()),
+            ..Self::default()
+        }
+    }
+    /// A message box with `message`, and OK and Cancel buttons
+    pub fn confirm(message: &str) -> Self {
+        Self {
+            buttons: Some(vec![DialogResponse::Ok, DialogResponse::Cancel]),
+            ..Self::alert(message)
+        }
+    }
+}
+
+impl WidgetModifier for MessageBox {
+    fn apply(&self, widget: &mut Widget) {
+        let mut draw_style = DrawStyle::from(self.rect.clone());
+        draw_style.set_class("message_box_rect");
+        widget.set_draw_style(draw_style);
+        widget.layout().add(constraints![
+            min_width(250.0),
+            shrink(),
+        ]);
+
+        let title = self.title.as_ref().map(|title| {
+            let mut title_widget = text_widget("message_box_title", &self.text, title, widget);
+            title_widget.layout().add(align_top(widget).padding(15.0));
+            title_widget
+        });
+        let mut message = text_widget("message_box_text", &self.text, &self.message, widget);
+        match title {
+            Some(ref title) => message.layout().add(below(title).padding(10.0)),
+            None => message.layout().add(align_top(widget).padding(15.0)),
+        }
+        if let Some(title) = title {
+            widget.add_child(title);
+        }
+        widget.add_child(message.clone());
+
+        // buttons are right aligned, and focusable in order, the first is focused when the dialog opens
+        let mut previous: Option<Widget> = None;
+        for (index, response) in self.buttons.iter().enumerate() {
+            let mut button = Widget::from_modifier_style(ButtonStyle::from_text(response.label()));
+            button.set_name("message_box_button");
+            button.enable_focus();
+            {
+                let response = response.clone();
+                button.add_handler(move |_: &ClickEvent, args: EventArgs| {
+                    args.widget.event_bubble_up(CloseDialog(response.clone()));
+                });
+            }
+            {
+                let response = response.clone();
+                button.add_handler(move |event: &WidgetKeyboardInput, args: EventArgs| {
+                    let &WidgetKeyboardInput(input) = event;
+                    if input.state == ElementState::Pressed {
+                        if let Some(VirtualKeyCode::Return) | Some(VirtualKeyCode::Space) = input.virtual_keycode {
+                            args.widget.event_bubble_up(CloseDialog(response.clone()));
+                        }
+                    }
+                });
+            }
+            button.layout().add(constraints![
+                below(&message).padding(20.0),
+                bound_left(widget).padding(15.0),
+                bound_bottom(widget).padding(15.0),
+            ]);
+            if let Some(ref previous) = previous {
+                button.layout().add(align_to_right_of(previous).padding(10.0));
+            }
+            if index == self.buttons.len() - 1 {
+                button.layout().add(align_right(widget).padding(15.0));
+            }
+            widget.add_child(button.clone());
+            previous = Some(button);
+        }
+    }
+}
+
+fn text_widget(name: &str, style: &TextStyle, text: &str, message_box: &Widget) -> Widget {
+    let mut text_style = style.clone();
+    text_style.text = Some(text.to_owned());
+    let mut text_widget = Widget::new(name);
+    text_widget.set_draw_style(DrawStyle::from_class::<TextStyle>(name));
+    StaticTextStyle::from_style(text_style).component().apply(&mut text_widget);
+    text_widget.layout().add(constraints![
+        bound_left(message_box).padding(15.0),
+        bound_right(message_box).padding(15.0),
+    ]);
+    text_widget
+}
@@ -1,10 +1,18 @@
 //! Includes standard bundled widgets.
 
 pub mod button;
+pub mod checkbox;
+pub mod radio;
+pub mod dropdown;
+pub mod menu;
+pub mod message_box;
+pub mod tooltip;
 pub mod scroll;
 pub mod list;
 pub mod slider;
 pub mod edit_text;
+pub mod text_area;
+pub mod text_filter;
 pub mod image;
 pub mod glcanvas;
 pub mod text;
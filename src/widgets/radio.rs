@@ -0,0 +1,156 @@
+//! Radio buttons, of which only one in a `RadioGroup` can be selected at a time.
+//!
+//! A `RadioButton` can be a child, or any descendant, of a `RadioGroup`. Selecting a button,
+//! by clicking it, pressing space while it's focused, or sending it `SelectRadio`, deselects the
+//! previously selected button in the group, and the group sends a `RadioChanged` event with the
+//! value of the newly selected button.
+
+use glutin::{ElementState, VirtualKeyCode};
+
+use layout::constraint::*;
+use layout::linear_layout::{LinearLayoutSettings, Orientation};
+use event::{EventHandler, EventArgs};
+use widget::Widget;
+use widget::property::Property;
+use input::mouse::ClickEvent;
+use input::keyboard::WidgetKeyboardInput;
+use widgets::checkbox::add_indicator;
+use draw::ellipse::EllipseStyle;
+use draw::text::TextStyle;
+use color::*;
+use style::*;
+use widget::property::states::*;
+
+component_style!{pub struct RadioGroup<name="radio_group", style=RadioGroupStyle> {
+    orientation: Orientation = Orientation::Vertical,
+    spacing: f32 = 10.0,
+}}
+
+impl WidgetModifier for RadioGroup {
+    fn apply(&self, widget: &mut Widget) {
+        let mut settings = LinearLayoutSettings::new(self.orientation);
+        settings.padding = self.spacing;
+        widget
+            .linear_layout(settings)
+            .add_handler(RadioGroupHandler::default());
+    }
+}
+
+component_style!{pub struct RadioButton<name="radio_button", style=RadioButtonStyle> {
+    // Sent by the group in `RadioChanged` when this button is selected
+    value: String = String::new(),
+    ellipse: EllipseStyle = style!(EllipseStyle {
+        background_color: WHITE,
+        border: Some((1.0, GRAY_30)),
+    }),
+    dot_color: Color = BLUE,
+    size: f32 = 20.0,
+    text: Option<TextStyle> = None,
+    selected: bool = false,
+}}
+
+impl RadioButtonStyle {
+    /// A radio button with the label `text`, that selects `value`
+    pub fn from_text(text: &str, value: &str) -> Self {
+        Self {
+            text: Some(Some(TextStyle::from_text(text))),
+            value: Some(value.to_owned()),
+            ..Self::default()
+        }
+    }
+}
+
+impl WidgetModifier for RadioButton {
+    fn apply(&self, widget: &mut Widget) {
+        let mut circle = Widget::new("radio_button_circle");
+        circle.set_draw_style(self.ellipse.clone());
+
+        let mut dot = Widget::new("radio_button_dot");
+        let mut dot_style = DrawStyle::from(style!(EllipseStyle {
+            background_color: TRANSPARENT,
+        }));
+        dot_style.prop_style(ACTIVATED.clone(), style!(EllipseStyle {
+            background_color: self.dot_color,
+        }));
+        dot.set_draw_style(dot_style);
+        dot.layout().add(match_layout(&circle).padding(self.size / 4.0));
+        circle.add_child(dot);
+
+        add_indicator(widget, circle, self.size, self.text.clone(), "radio_button_text");
+        widget
+            .add_handler(RadioButtonHandler { value: self.value.clone() })
+            .enable_focus();
+        RadioButtonHandler::add_adapters(widget);
+        if self.selected {
+            widget.event(SelectRadio);
+        }
+    }
+}
+
+/// Selects a `RadioButton`
+#[derive(Debug, Copy, Clone)]
+pub struct SelectRadio;
+
+/// Sent by a `RadioGroup` when a different button is selected, with the value of the button
+#[derive(Debug, Clone)]
+pub struct RadioChanged(pub String);
+
+/// Sent from a newly selected button to it's group
+struct RadioSelected {
+    button: Widget,
+    value: String,
+}
+
+multi_event!{impl EventHandler<RadioButtonEvent> for RadioButtonHandler {
+    ClickEvent => click,
+    WidgetKeyboardInput => keyboard_input,
+    SelectRadio => select,
+}}
+
+struct RadioButtonHandler {
+    value: String,
+}
+
+impl RadioButtonHandler {
+    fn click(&mut self, _: &ClickEvent, args: EventArgs) {
+        self.select(&SelectRadio, args);
+    }
+    fn keyboard_input(&mut self, event: &WidgetKeyboardInput, args: EventArgs) {
+        let &WidgetKeyboardInput(input) = event;
+        if input.state == ElementState::Pressed && input.virtual_keycode == Some(VirtualKeyCode::Space) {
+            self.select(&SelectRadio, args);
+        }
+    }
+    fn select(&mut self, _: &SelectRadio, mut args: EventArgs) {
+        let selectable = {
+            let props = args.widget.props();
+            !props.contains(&Property::Inactive) && !props.contains(&Property::Activated)
+        };
+        if !selectable {
+            return;
+        }
+        args.widget.add_prop(Property::Activated);
+        args.widget.event_bubble_up(RadioSelected {
+            button: args.widget.clone(),
+            value: self.value.clone(),
+        });
+    }
+}
+
+#[derive(Default)]
+struct RadioGroupHandler {
+    selected: Option<Widget>,
+}
+
+impl EventHandler<RadioSelected> for RadioGroupHandler {
+    fn handle(&mut self, event: &RadioSelected, args: EventArgs) {
+        *args.handled = true;
+        if let Some(mut previous) = self.selected.take() {
+            if previous != event.button {
+                previous.remove_prop(Property::Activated);
+            }
+        }
+        self.selected = Some(event.button.clone());
+        args.widget.event(RadioChanged(event.value.clone()));
+    }
+}
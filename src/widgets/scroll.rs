@@ -6,7 +6,7 @@ use layout::constraint::*;
 use event::{EventArgs, EventHandler};
 use widget::Widget;
 use widgets::slider::{SliderStyle, SliderEvent, SetSliderValue, Orientation};
-use geometry::{Size, Vector, Rect, RectExt};
+use geometry::{Point, Size, Vector, Rect, RectExt};
 use layout::{LayoutUpdated, LAYOUT};
 use input::mouse::WidgetMouseWheel;
 use draw::rect::RectStyle;
@@ -135,6 +135,12 @@ impl ScrollBars {
     }
 }
 
+/// Scrolls the nearest `ScrollContainer` so that the rect is visible, should be sent using `Widget::event_bubble_up`
+/// from a descendant of the scrollable content.
+/// The rect is kept visible as the content is resized, until the content is scrolled by the user.
+#[derive(Debug, Copy, Clone)]
+pub struct ScrollIntoView(pub Rect);
+
 #[derive(Clone)]
 struct ContentLayoutUpdated(Rect);
 #[derive(Clone)]
@@ -148,6 +154,7 @@ multi_event!{impl EventHandler<ScrollParentEvent> for ScrollParent {
     ContentLayoutUpdated => content_layout_updated,
     WidgetMouseWheel => widget_mouse_wheel,
     ScrollBarMoved => scrollbar_moved,
+    ScrollIntoView => scroll_into_view,
 }}
 
 struct ScrollParent {
@@ -158,6 +165,8 @@ struct ScrollParent {
     height_ratio: f32,
     scrollable_area: Size,
     offset: Vector,
+    /// Rect to keep visible, relative to the content
+    scroll_into_view: Option<Rect>,
     pub scrollbars: Option<ScrollBars>,
 }
 
@@ -171,6 +180,7 @@ impl ScrollParent {
             height_ratio: 0.0,
             scrollable_area: Size::zero(),
             offset: Vector::zero(),
+            scroll_into_view: None,
             scrollbars: None,
         }
     }
@@ -255,9 +265,11 @@ impl ScrollParent {
         }
         self.width_ratio = width_ratio;
         self.height_ratio = height_ratio;
+        self.update_scroll_into_view();
     }
 
     fn widget_mouse_wheel(&mut self, event: &WidgetMouseWheel, _: EventArgs) {
+        self.scroll_into_view = None;
         let &WidgetMouseWheel(mouse_wheel) = event;
         let scroll = get_scroll(mouse_wheel);
         if self.scrollable_area.width > 0.0 {
@@ -273,6 +285,7 @@ impl ScrollParent {
     }
 
     fn scrollbar_moved(&mut self, event: &ScrollBarMoved, _: EventArgs) {
+        self.scroll_into_view = None;
         match *event {
             ScrollBarMoved::Horizontal(offset) => {
                 self.offset.x = -offset * self.scrollable_area.width;
@@ -285,6 +298,45 @@ impl ScrollParent {
         }
     }
 
+    fn scroll_into_view(&mut self, event: &ScrollIntoView, args: EventArgs) {
+        let &ScrollIntoView(rect) = event;
+        self.scroll_into_view = Some(rect.translate(&-self.content_rect.origin.to_vector()));
+        self.update_scroll_into_view();
+        *args.handled = true;
+    }
+
+    /// Moves the content the minimum distance so that the `scroll_into_view` rect is visible
+    fn update_scroll_into_view(&mut self) {
+        if let Some(rect) = self.scroll_into_view {
+            let visible = Rect::new(Point::new(-self.offset.x, -self.offset.y), self.container_rect.size);
+            let mut offset = self.offset;
+            if rect.right() > visible.right() {
+                offset.x = self.container_rect.width() - rect.right();
+            }
+            if rect.left() < visible.left() {
+                offset.x = -rect.left();
+            }
+            if rect.bottom() > visible.bottom() {
+                offset.y = self.container_rect.height() - rect.bottom();
+            }
+            if rect.top() < visible.top() {
+                offset.y = -rect.top();
+            }
+            offset.x = f32::min(0.0, f32::max(-self.scrollable_area.width, offset.x));
+            offset.y = f32::min(0.0, f32::max(-self.scrollable_area.height, offset.y));
+            if (offset.x - self.offset.x).abs() > 0.5 {
+                self.offset.x = offset.x;
+                self.move_content_x();
+                self.move_slider_x();
+            }
+            if (offset.y - self.offset.y).abs() > 0.5 {
+                self.offset.y = offset.y;
+                self.move_content_y();
+                self.move_slider_y();
+            }
+        }
+    }
+
     fn move_content_x(&mut self) {
         let scroll_to = self.container_rect.left() + self.offset.x;
         let mut layout = self.scrollable.layout();
@@ -1,10 +1,6 @@
-use cassowary::Constraint;
-
-use widget::{Widget, StateUpdated, StyleUpdated};
+use widget::{Widget, StyleUpdated};
 use draw::text::{TextState, TextStyle};
 use event::{EventHandler, EventArgs};
-use layout::constraint::*;
-use geometry::Size;
 use style::WidgetModifier;
 use widgets::edit_text::TextUpdated;
 
@@ -29,7 +25,7 @@ impl StaticTextStyle {
 
 impl WidgetModifier for StaticText {
     fn apply(&self, widget: &mut Widget) {
-        widget.add_handler(TextSizeHandler::default());
+        widget.size_to_content();
         widget.add_handler(TextUpdateHandler::default());
         widget.add_handler(|_: &StyleUpdated, args: EventArgs| {
             args.widget.event(StaticTextUpdate::StyleUpdated);
@@ -70,32 +66,3 @@ impl EventHandler<StaticTextUpdate> for TextUpdateHandler {
         }
     }
 }
-
-#[derive(Default)]
-struct TextSizeHandler {
-    measured_size: Option<Size>,
-    size_constraints: Vec<Constraint>,
-}
-
-impl EventHandler<StateUpdated> for TextSizeHandler {
-    fn handle(&mut self, _: &StateUpdated, mut args: EventArgs) {
-        let text_size = {
-            let draw_state = args.widget.draw_state();
-            if let Some(state) = draw_state.downcast_ref::<TextState>() {
-                state.measure()
-            } else {
-                Size::zero()
-            }
-        };
-        if self.measured_size.is_none() || self.measured_size.unwrap() != text_size {
-            let size_constraints = size(text_size).build(&args.widget.layout_vars());
-            let mut layout = args.widget.layout();
-            for constraint in self.size_constraints.drain(..) {
-                layout.remove_constraint(constraint);
-            }
-            layout.add(size_constraints.clone());
-            self.size_constraints = size_constraints;
-            self.measured_size = Some(text_size);
-        }
-    }
-}
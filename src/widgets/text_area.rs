@@ -0,0 +1,54 @@
+//! A multi-line text editor, that wraps text to it's width and grows vertically to fit it.
+//!
+//! To limit the height of a `TextArea`, add it to a `ScrollContainer`, which will be scrolled
+//! to keep the cursor visible while editing.
+
+use layout::constraint::*;
+use widget::Widget;
+use draw::rect::RectStyle;
+use draw::text::TextStyle;
+use text_layout::Wrap;
+use color::*;
+use widget::property::states::*;
+use style::{WidgetModifier, DrawStyle};
+use widgets::edit_text::{add_edit_handlers, EditConfig};
+
+component_style!{pub struct TextArea<name="text_area", style=TextAreaStyle> {
+    rect: RectStyle = style!(RectStyle {
+        border: Some((1.0, GRAY_70)),
+        corner_radius: Some(3.0),
+    }),
+    focused_rect: Option<RectStyle> = Some(style!(RectStyle {
+        border: Some((1.0, BLUE)),
+    })),
+}}
+
+impl WidgetModifier for TextArea {
+    fn apply(&self, widget: &mut Widget) {
+        let mut text_widget = Widget::new("text_area_text");
+        let mut draw_style = DrawStyle::from(self.rect.clone());
+        add_edit_handlers(widget, &text_widget, EditConfig {
+            multiline: true,
+            ..EditConfig::default()
+        });
+
+        if let Some(ref focused_rect) = self.focused_rect {
+            draw_style.prop_style(FOCUSED.clone(), focused_rect.clone());
+        }
+        widget.set_draw_style(draw_style);
+
+        text_widget
+            .set_draw_style(DrawStyle::from(style!(TextStyle {
+                wrap: Wrap::Whitespace,
+            })))
+            .size_to_content();
+
+        text_widget.layout().add(constraints![
+            align_left(widget).padding(5.0),
+            align_top(widget).padding(5.0),
+            align_right(widget).padding(5.0),
+            bound_by(widget).padding(5.0),
+        ]);
+        widget.add_child(text_widget);
+    }
+}
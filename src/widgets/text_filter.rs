@@ -0,0 +1,93 @@
+//! Filters and validators for the text entered in an `EditText`.
+//!
+//! Filters reject any edit that would result in text they don't allow, validators don't prevent
+//! editing, but while the text fails a validator the `EditText` has the `Invalid` property,
+//! so it can be styled differently, either with the `invalid_rect` field of `EditTextStyle`, or
+//! by registering a style for the `INVALID` state with the theme, using the `edit_text` class for the text.
+
+use std::fmt;
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub enum TextFilter {
+    /// A decimal number, optionally negative
+    Numeric,
+    /// At most this many chars
+    MaxLength(usize),
+    /// Text that matches a mask, where '9' matches any digit, 'a' any letter, '*' any letter or digit,
+    /// and any other char matches itself, ie. "99/99/9999" for a date.
+    ///
+    /// When used as a filter, literal chars in the mask are inserted automatically while typing.
+    Mask(String),
+    /// Any predicate on the text
+    Custom(Arc<Fn(&str) -> bool + Send + Sync>),
+}
+
+impl TextFilter {
+    pub fn custom<F: Fn(&str) -> bool + Send + Sync + 'static>(predicate: F) -> Self {
+        TextFilter::Custom(Arc::new(predicate))
+    }
+
+    /// Returns true if `text` is allowed while it's being typed, that is, it is valid,
+    /// or could become valid by typing more chars at the end
+    pub fn allows(&self, text: &str) -> bool {
+        match *self {
+            TextFilter::Numeric => {
+                let digits = if text.starts_with('-') { &text[1..] } else { text };
+                digits.chars().all(|char| char.is_digit(10) || char == '.') &&
+                    digits.chars().filter(|char| *char == '.').count() <= 1
+            }
+            TextFilter::MaxLength(length) => text.chars().count() <= length,
+            TextFilter::Mask(ref mask) => {
+                text.chars().count() <= mask.chars().count() &&
+                    text.chars().zip(mask.chars()).all(|(char, mask_char)| mask_matches(mask_char, char))
+            }
+            TextFilter::Custom(ref predicate) => predicate(text),
+        }
+    }
+
+    /// Returns true if `text` is complete and valid
+    pub fn validates(&self, text: &str) -> bool {
+        match *self {
+            TextFilter::Numeric => text.parse::<f64>().is_ok(),
+            TextFilter::Mask(ref mask) => {
+                text.chars().count() == mask.chars().count() && self.allows(text)
+            }
+            _ => self.allows(text),
+        }
+    }
+
+    /// Appends any literal chars from a `Mask` that follow the end of `text`
+    pub(crate) fn fill_literals(&self, text: &mut String) {
+        if let TextFilter::Mask(ref mask) = *self {
+            let literals = mask.chars()
+                .skip(text.chars().count())
+                .take_while(|mask_char| !is_mask_placeholder(*mask_char));
+            text.extend(literals);
+        }
+    }
+}
+
+impl fmt::Debug for TextFilter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TextFilter::Numeric => write!(f, "Numeric"),
+            TextFilter::MaxLength(length) => write!(f, "MaxLength({})", length),
+            TextFilter::Mask(ref mask) => write!(f, "Mask({:?})", mask),
+            TextFilter::Custom(_) => write!(f, "Custom"),
+        }
+    }
+}
+
+fn is_mask_placeholder(mask_char: char) -> bool {
+    mask_char == '9' || mask_char == 'a' || mask_char == '*'
+}
+
+fn mask_matches(mask_char: char, char: char) -> bool {
+    match mask_char {
+        '9' => char.is_digit(10),
+        'a' => char.is_alphabetic(),
+        '*' => char.is_alphanumeric(),
+        _ => char == mask_char,
+    }
+}
@@ -0,0 +1,284 @@
+//! Tooltips, shown near the mouse after it has hovered over a widget for a while.
+//!
+//! A tooltip is hidden when the mouse leaves the widget, or a mouse button is pressed over it,
+//! and is kept inside the window. Tooltips are styled with the `TooltipStyle` registered with the
+//! theme, including the delay before they're shown:
+//!
+//! ```no_run
+//! # #[macro_use] extern crate limn;
+//! # use limn::prelude::*;
+//! use std::time::Duration;
+//! use limn::widgets::tooltip::TooltipStyle;
+//! # fn main() {
+//! resources().theme.register_modifier_type_style(style!(TooltipStyle {
+//!     delay: Duration::from_millis(200),
+//! }));
+//! # let mut button = Widget::new("button");
+//! button.set_tooltip("Saves the file");
+//! # }
+//! ```
+//!
+//! The delay is checked on each `FrameEvent`, with `Ui::wake_at` making sure there is a frame once
+//! it has elapsed. A headless `App` can skip ahead with `App::advance_tooltips`.
+
+use std::any::TypeId;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use glutin::ElementState;
+
+use layout::constraint::*;
+use event::{EventHandler, EventArgs};
+use widget::Widget;
+use input::mouse::{MouseOverEvent, WidgetMouseButton};
+use input::keyboard::is_descendant;
+use widgets::text::StaticTextStyle;
+use draw::rect::RectStyle;
+use draw::text::TextStyle;
+use geometry::{Point, Vector};
+use resources::resources;
+use ui::{Ui, TOOLTIP_Z_INDEX};
+use app::{App, FrameEvent};
+use color::*;
+use style::*;
+
+/// Distance of the tooltip below the mouse, so it doesn't cover the cursor
+const CURSOR_OFFSET: f32 = 20.0;
+
+component_style!{pub struct Tooltip<name="tooltip", style=TooltipStyle> {
+    rect: RectStyle = style!(RectStyle {
+        background_color: GRAY_90,
+        border: Some((1.0, GRAY_50)),
+        corner_radius: Some(3.0),
+    }),
+    text: TextStyle = style!(TextStyle {
+        font_size: 16.0,
+    }),
+    padding: f32 = 5.0,
+    // How long the mouse has to hover over the widget before the tooltip is shown
+    delay: Duration = Duration::from_millis(500),
+}}
+
+impl WidgetModifier for Tooltip {
+    fn apply(&self, widget: &mut Widget) {
+        let mut draw_style = DrawStyle::from(self.rect.clone());
+        draw_style.set_class("tooltip");
+        widget
+            .set_draw_style(draw_style)
+            .set_cursor_hit_fn(|_, _| false);
+        widget.layout().add(shrink());
+    }
+}
+
+/// The contents of a tooltip, text, or a widget created when the tooltip is shown
+pub enum TooltipContent {
+    Text(String),
+    Widget(Box<Fn() -> Widget>),
+}
+
+impl TooltipContent {
+    /// Content created by `build` each time the tooltip is shown
+    pub fn widget<F: Fn() -> Widget + 'static>(build: F) -> Self {
+        TooltipContent::Widget(Box::new(build))
+    }
+}
+
+impl<'a> From<&'a str> for TooltipContent {
+    fn from(text: &'a str) -> Self {
+        TooltipContent::Text(text.to_owned())
+    }
+}
+
+impl From<String> for TooltipContent {
+    fn from(text: String) -> Self {
+        TooltipContent::Text(text)
+    }
+}
+
+impl Widget {
+    /// Shows a tooltip with `content` after the mouse hovers over this widget, ie.
+    /// `widget.set_tooltip("text")` or `widget.set_tooltip(TooltipContent::widget(build))`
+    pub fn set_tooltip<C: Into<TooltipContent>>(&mut self, content: C) -> &mut Self {
+        let content = Rc::new(content.into());
+        self.add_handler(move |event: &MouseOverEvent, args: EventArgs| {
+            match *event {
+                MouseOverEvent::Over => {
+                    args.ui.event(TooltipInput::Hover(args.widget.clone(), Rc::clone(&content)));
+                }
+                MouseOverEvent::Out => {
+                    // moving between the widget's children
+                    let inside = args.ui.cursor_position().map_or(false, |point| args.widget.bounds().contains(&point));
+                    if !inside {
+                        args.ui.event(TooltipInput::Leave(args.widget.clone()));
+                    }
+                }
+            }
+        })
+        .add_handler(|event: &WidgetMouseButton, args: EventArgs| {
+            if let WidgetMouseButton(ElementState::Pressed, _) = *event {
+                args.ui.event(TooltipInput::Dismiss(args.widget.clone()));
+            }
+        })
+    }
+}
+
+#[derive(Clone)]
+enum TooltipInput {
+    Hover(Widget, Rc<TooltipContent>),
+    Leave(Widget),
+    /// A mouse button was pressed, the tooltip isn't shown again until the mouse leaves the widget
+    Dismiss(Widget),
+}
+
+/// The widget the mouse is hovering over, and it's tooltip
+struct Hovered {
+    widget: Widget,
+    content: Rc<TooltipContent>,
+    style: Tooltip,
+    /// When the tooltip is shown, by the handler's clock
+    deadline: Instant,
+    dismissed: bool,
+}
+
+/// Skips the hover delay of tooltips ahead, see `App::advance_tooltips`
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct AdvanceTooltips(pub Duration);
+
+multi_event!{impl EventHandler<TooltipEvent> for TooltipHandler {
+    TooltipInput => input,
+    FrameEvent => frame,
+    AdvanceTooltips => advance,
+}}
+
+#[derive(Default)]
+struct TooltipHandler {
+    hovered: Option<Hovered>,
+    /// Layer over the widget tree that contains the visible tooltip
+    shown: Option<Widget>,
+    /// Time skipped with `AdvanceTooltips`, added to the current time
+    skipped: Duration,
+}
+
+impl TooltipHandler {
+    fn now(&self) -> Instant {
+        Instant::now() + self.skipped
+    }
+    fn input(&mut self, event: &TooltipInput, args: EventArgs) {
+        let current = self.hovered.as_ref().map(|hovered| hovered.widget.clone());
+        match *event {
+            TooltipInput::Hover(ref widget, ref content) => {
+                // the hover event bubbles up, the innermost widget with a tooltip gets it first
+                if current.map_or(false, |current| is_descendant(&current, widget)) {
+                    return;
+                }
+                self.hide();
+                let style = tooltip_style();
+                let deadline = self.now() + style.delay;
+                self.hovered = Some(Hovered {
+                    widget: widget.clone(),
+                    content: Rc::clone(content),
+                    style: style,
+                    deadline: deadline,
+                    dismissed: false,
+                });
+                self.show_if_ready(args.ui);
+            }
+            TooltipInput::Leave(ref widget) => {
+                if current.as_ref() == Some(widget) {
+                    self.hide();
+                    self.hovered = None;
+                }
+            }
+            TooltipInput::Dismiss(ref widget) => {
+                if current.as_ref() == Some(widget) {
+                    self.hide();
+                    if let Some(ref mut hovered) = self.hovered {
+                        hovered.dismissed = true;
+                    }
+                }
+            }
+        }
+    }
+    fn frame(&mut self, _: &FrameEvent, args: EventArgs) {
+        self.show_if_ready(args.ui);
+    }
+    fn advance(&mut self, event: &AdvanceTooltips, args: EventArgs) {
+        self.skipped += event.0;
+        self.show_if_ready(args.ui);
+    }
+    /// Shows the tooltip of the hovered widget if it's delay has elapsed,
+    /// otherwise makes sure there's a frame to check it again when it has
+    fn show_if_ready(&mut self, ui: &mut Ui) {
+        if self.shown.is_some() {
+            return;
+        }
+        let deadline = match self.hovered {
+            Some(ref hovered) if !hovered.dismissed => hovered.deadline,
+            _ => return,
+        };
+        if self.now() < deadline {
+            ui.wake_at(deadline - self.skipped);
+            return;
+        }
+        let position = match ui.cursor_position() {
+            Some(position) => position + Vector::new(0.0, CURSOR_OFFSET),
+            None => return,
+        };
+        let mut layer = Widget::new("tooltip_layer");
+        layer
+            .set_z_index(TOOLTIP_Z_INDEX)
+            .set_cursor_hit_fn(|_, _| false);
+        layer.layout().no_container();
+        if let Some(ref hovered) = self.hovered {
+            let tooltip = tooltip_widget(hovered, position, &layer);
+            layer.add_child(tooltip);
+        }
+        ui.get_root().add_child(layer.clone());
+        self.shown = Some(layer);
+    }
+    fn hide(&mut self) {
+        if let Some(mut layer) = self.shown.take() {
+            layer.remove_widget();
+        }
+    }
+}
+
+/// The tooltip style from the theme
+fn tooltip_style() -> Tooltip {
+    let style = resources().theme.get_modifier_style(Box::new(TooltipStyle::default()), TypeId::of::<TooltipStyle>(), None);
+    let style = style.as_any().downcast_ref::<TooltipStyle>().unwrap().clone();
+    style.component()
+}
+
+/// Creates the tooltip of `hovered`, placed at `position`, or as close as it fits in `layer`
+fn tooltip_widget(hovered: &Hovered, position: Point, layer: &Widget) -> Widget {
+    let mut tooltip = Widget::new("tooltip");
+    hovered.style.apply(&mut tooltip);
+    tooltip.layout().add(constraints![
+        top_left(position).strength(STRONG),
+        bound_by(layer),
+    ]);
+    let mut content = match *hovered.content {
+        TooltipContent::Text(ref text) => {
+            let mut text_style = hovered.style.text.clone();
+            text_style.text = Some(text.clone());
+            let mut text_widget = Widget::new("tooltip_text");
+            text_widget.set_draw_style(DrawStyle::from_class::<TextStyle>("tooltip_text"));
+            StaticTextStyle::from_style(text_style).component().apply(&mut text_widget);
+            text_widget
+        }
+        TooltipContent::Widget(ref build) => build(),
+    };
+    // a tooltip pushed under the mouse by the edge of the window shouldn't take the hover
+    content.set_cursor_hit_fn(|_, _| false);
+    content.layout().add(bound_by(&tooltip).padding(hovered.style.padding));
+    tooltip.add_child(content);
+    tooltip
+}
+
+impl App {
+    pub fn add_tooltip_handlers(&mut self) {
+        self.add_handler(TooltipHandler::default());
+        TooltipHandler::add_adapters(&mut self.get_root());
+    }
+}
@@ -0,0 +1,31 @@
+//! Fixtures shared by the widget tests
+#![allow(dead_code)]
+
+use limn::prelude::*;
+use limn::resources::font::FontDescriptor;
+use limn::draw::rect::RectStyle;
+use limn::draw::text::TextStyle;
+use limn::draw::ellipse::EllipseStyle;
+
+/// Creates a headless app, with the NotoSans font and the default styles of the draw states registered
+pub fn setup() -> App {
+    let app = App::new_headless(Size::new(300.0, 300.0));
+    let mut res = resources();
+    res.font_loader.register_font_data(FontDescriptor::from_family("NotoSans"), include_bytes!("../../assets/fonts/NotoSans/NotoSans-Regular.ttf").to_vec()).unwrap();
+    res.theme.register_type_style(EllipseStyle::default());
+    res.theme.register_type_style(RectStyle::default());
+    res.theme.register_type_style(style!(TextStyle {
+        font: FontDescriptor::from_family("NotoSans"),
+        font_size: 20.0,
+        text_color: BLACK,
+        background_color: TRANSPARENT,
+        wrap: Wrap::Whitespace,
+        align: Align::Start,
+    }));
+    app
+}
+
+pub fn center_of(widget: &Widget) -> Point {
+    let bounds = widget.bounds();
+    Point::new(bounds.origin.x + bounds.size.width / 2.0, bounds.origin.y + bounds.size.height / 2.0)
+}
@@ -0,0 +1,138 @@
+/// These tests are not parallelizable (resources/theme access not thread safe yet), use RUST_TEST_THREADS=1 cargo test for now
+
+#[macro_use]
+extern crate limn;
+
+use std::rc::Rc;
+use std::cell::{Cell, RefCell};
+
+use limn::prelude::*;
+use limn::glutin::{VirtualKeyCode, ModifiersState};
+use limn::resources::font::FontDescriptor;
+use limn::draw::rect::RectStyle;
+use limn::draw::text::TextStyle;
+use limn::dialog::{DialogClosed, DialogResponse};
+use limn::input::shortcuts::{KeyChord, ShortcutScope};
+use limn::widgets::edit_text::{EditTextStyle, TextUpdated};
+
+fn setup() -> App {
+    let app = App::new_headless(Size::new(300.0, 300.0));
+    let mut res = resources();
+    res.font_loader.register_font_data(FontDescriptor::from_family("NotoSans"), include_bytes!("../assets/fonts/NotoSans/NotoSans-Regular.ttf").to_vec()).unwrap();
+    res.theme.register_type_style(RectStyle::default());
+    res.theme.register_type_style(style!(TextStyle {
+        font: FontDescriptor::from_family("NotoSans"),
+        font_size: 20.0,
+        text_color: BLACK,
+        background_color: TRANSPARENT,
+        wrap: Wrap::Whitespace,
+        align: Align::Start,
+    }));
+    app
+}
+
+fn center_of(widget: &Widget) -> Point {
+    let bounds = widget.bounds();
+    Point::new(bounds.origin.x + bounds.size.width / 2.0, bounds.origin.y + bounds.size.height / 2.0)
+}
+
+fn ctrl() -> ModifiersState {
+    ModifiersState { ctrl: true, ..ModifiersState::default() }
+}
+
+#[derive(Debug, Clone)]
+struct Save;
+
+#[test]
+fn confirm() {
+    let mut app = setup();
+    let mut root = Widget::new("root");
+    let mut edit_text = Widget::from_modifier_style(EditTextStyle::default());
+    edit_text.set_name("edit_text");
+    edit_text.layout().add(constraints![
+        align_top(&root),
+        align_left(&root),
+        width(200.0),
+    ]);
+    let text = Rc::new(RefCell::new(String::new()));
+    {
+        let text = text.clone();
+        edit_text.add_handler(move |event: &TextUpdated, _: EventArgs| {
+            *text.borrow_mut() = event.0.clone();
+        });
+    }
+    // clicking the button opens a confirm dialog, which sends it the response
+    let mut button = Widget::new("discard");
+    button.layout().add(constraints![
+        align_bottom(&root),
+        align_left(&root),
+        size(Size::new(100.0, 30.0)),
+    ]);
+    let clicks = Rc::new(Cell::new(0));
+    let responses = Rc::new(RefCell::new(Vec::new()));
+    {
+        let clicks = clicks.clone();
+        button.add_handler(move |_: &ClickEvent, args: EventArgs| {
+            clicks.set(clicks.get() + 1);
+            args.ui.confirm(&args.widget, "Discard changes?");
+        });
+    }
+    {
+        let responses = responses.clone();
+        button.add_handler(move |event: &DialogClosed, _: EventArgs| {
+            responses.borrow_mut().push(event.response.clone());
+        });
+    }
+    let saves = Rc::new(Cell::new(0));
+    {
+        let saves = saves.clone();
+        root.add_handler(move |_: &Save, _: EventArgs| {
+            saves.set(saves.get() + 1);
+        });
+    }
+    root.add_child(edit_text.clone());
+    root.add_child(button.clone());
+    app.set_root(root);
+    app.shortcuts().register(KeyChord::new(VirtualKeyCode::S).ctrl(), ShortcutScope::Global, Save).unwrap();
+    assert!(edit_text.props().contains(&Property::Focused));
+
+    let button_center = center_of(&button);
+    app.click(button_center);
+    let dialog = app.find_widget("message_box").unwrap();
+    assert_eq!(center_of(&dialog), Point::new(150.0, 150.0));
+    let buttons: Vec<Widget> = dialog.children().into_iter().filter(|child| child.name() == "message_box_button").collect();
+    assert_eq!(buttons.len(), 2);
+
+    // the rest of the ui doesn't receive input while the dialog is open
+    app.click(button_center);
+    assert_eq!(clicks.get(), 1);
+    app.type_text("abc");
+    assert_eq!(*text.borrow(), "");
+    app.key_press_with(VirtualKeyCode::S, ctrl());
+    assert_eq!(saves.get(), 0);
+
+    // focus is trapped in the dialog
+    assert!(buttons[0].props().contains(&Property::Focused));
+    app.key_press(VirtualKeyCode::Tab);
+    assert!(buttons[1].props().contains(&Property::Focused));
+    app.key_press(VirtualKeyCode::Tab);
+    assert!(buttons[0].props().contains(&Property::Focused));
+    app.key_press(VirtualKeyCode::Return);
+    assert!(app.find_widget("dialog_layer").is_none());
+    assert!(edit_text.props().contains(&Property::Focused));
+
+    app.click(button_center);
+    app.key_press(VirtualKeyCode::Escape);
+    assert!(app.find_widget("dialog_layer").is_none());
+
+    app.click(button_center);
+    let dialog = app.find_widget("message_box").unwrap();
+    let cancel = dialog.children().into_iter().filter(|child| child.name() == "message_box_button").last().unwrap();
+    app.click(center_of(&cancel));
+    assert!(app.find_widget("dialog_layer").is_none());
+
+    assert_eq!(clicks.get(), 3);
+    assert_eq!(*responses.borrow(), vec![DialogResponse::Ok, DialogResponse::Cancel, DialogResponse::Cancel]);
+    app.key_press_with(VirtualKeyCode::S, ctrl());
+    assert_eq!(saves.get(), 1);
+}
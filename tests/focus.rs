@@ -0,0 +1,107 @@
+/// These tests are not parallelizable (resources/theme access not thread safe yet), use RUST_TEST_THREADS=1 cargo test for now
+
+extern crate limn;
+
+use std::rc::Rc;
+use std::cell::RefCell;
+
+use limn::prelude::*;
+use limn::glutin::{VirtualKeyCode, ModifiersState};
+use limn::ui::WidgetAttachedEvent;
+use limn::input::keyboard::{KeyboardInputEvent, FocusGained, FocusLost};
+
+/// Adds a focusable child to `parent`, recording focus changes in `log`
+fn add_focusable(parent: &mut Widget, name: &'static str, log: &Rc<RefCell<Vec<String>>>) -> Widget {
+    let mut widget = Widget::new(name);
+    widget.make_focusable();
+    widget.add_handler(|_: &WidgetAttachedEvent, args: EventArgs| {
+        args.ui.event(KeyboardInputEvent::AddFocusable(args.widget));
+    });
+    {
+        let log = log.clone();
+        widget.add_handler(move |_: &FocusGained, _: EventArgs| {
+            log.borrow_mut().push(format!("gained {}", name));
+        });
+    }
+    {
+        let log = log.clone();
+        widget.add_handler(move |_: &FocusLost, _: EventArgs| {
+            log.borrow_mut().push(format!("lost {}", name));
+        });
+    }
+    parent.add_child(widget.clone());
+    widget
+}
+
+fn focused(widgets: &[Widget]) -> Option<String> {
+    widgets.iter().find(|widget| widget.props().contains(&Property::Focused)).map(|widget| widget.name())
+}
+
+fn shift() -> ModifiersState {
+    ModifiersState { shift: true, ..ModifiersState::default() }
+}
+
+#[test]
+fn tab_order() {
+    let mut app = App::new_headless(Size::new(300.0, 300.0));
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let mut root = Widget::new("root");
+    let a = add_focusable(&mut root, "a", &log);
+    let mut b = add_focusable(&mut root, "b", &log);
+    let mut c = add_focusable(&mut root, "c", &log);
+    let mut d = add_focusable(&mut root, "d", &log);
+    // b comes after c and d, c is inactive, d is hidden
+    b.set_tab_index(1);
+    c.add_prop(Property::Inactive);
+    d.layout().hide();
+    app.set_root(root);
+    let widgets = [a.clone(), b.clone(), c.clone(), d.clone()];
+
+    // the first focusable widget is focused automatically
+    assert_eq!(focused(&widgets), Some("a".to_owned()));
+    app.key_press(VirtualKeyCode::Tab);
+    assert_eq!(focused(&widgets), Some("b".to_owned()));
+    app.key_press(VirtualKeyCode::Tab);
+    assert_eq!(focused(&widgets), Some("a".to_owned()));
+    app.key_press_with(VirtualKeyCode::Tab, shift());
+    assert_eq!(focused(&widgets), Some("b".to_owned()));
+    assert_eq!(*log.borrow(), vec!["gained a", "lost a", "gained b", "lost b", "gained a", "lost a", "gained b"]);
+
+    c.remove_prop(Property::Inactive);
+    app.key_press(VirtualKeyCode::Tab);
+    assert_eq!(focused(&widgets), Some("a".to_owned()));
+    app.key_press(VirtualKeyCode::Tab);
+    assert_eq!(focused(&widgets), Some("c".to_owned()));
+}
+
+#[test]
+fn focus_scope() {
+    let mut app = App::new_headless(Size::new(300.0, 300.0));
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let mut root = Widget::new("root");
+    let a = add_focusable(&mut root, "a", &log);
+    let mut dialog = Widget::new("dialog");
+    let b = add_focusable(&mut dialog, "b", &log);
+    let c = add_focusable(&mut dialog, "c", &log);
+    root.add_child(dialog.clone());
+    app.set_root(root);
+    let widgets = [a.clone(), b.clone(), c.clone()];
+    assert_eq!(focused(&widgets), Some("a".to_owned()));
+
+    // focus moves into the scope, and can't leave it
+    dialog.push_focus_scope();
+    app.handle_events();
+    assert_eq!(focused(&widgets), Some("b".to_owned()));
+    app.key_press(VirtualKeyCode::Tab);
+    assert_eq!(focused(&widgets), Some("c".to_owned()));
+    app.key_press(VirtualKeyCode::Tab);
+    assert_eq!(focused(&widgets), Some("b".to_owned()));
+    app.get_root().event(KeyboardInputEvent::FocusChange(Some(a.clone())));
+    app.handle_events();
+    assert_eq!(focused(&widgets), Some("b".to_owned()));
+
+    // the previous focus is restored when the scope ends
+    dialog.pop_focus_scope();
+    app.handle_events();
+    assert_eq!(focused(&widgets), Some("a".to_owned()));
+}
@@ -0,0 +1,67 @@
+extern crate limn;
+
+use limn::history::{History, Command};
+
+/// Adds a value to a counter, consecutive additions of the same sign are coalesced
+#[derive(Debug, Clone, PartialEq)]
+struct Add(i32);
+
+impl Command for Add {
+    type Context = i32;
+    fn apply(self, counter: &mut i32) -> Self {
+        *counter += self.0;
+        Add(-self.0)
+    }
+    fn coalesce(&mut self, newer: &Self) -> bool {
+        if self.0.signum() == newer.0.signum() {
+            self.0 += newer.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[test]
+fn undo_redo() {
+    let mut counter = 0;
+    let mut history = History::new();
+    assert!(!history.can_undo());
+    history.apply(Add(1), &mut counter);
+    history.seal();
+    history.apply(Add(2), &mut counter);
+    assert_eq!(counter, 3);
+
+    assert!(history.undo(&mut counter));
+    assert_eq!(counter, 1);
+    assert!(history.undo(&mut counter));
+    assert_eq!(counter, 0);
+    assert!(!history.undo(&mut counter));
+
+    assert!(history.redo(&mut counter));
+    assert_eq!(counter, 1);
+    assert!(history.can_redo());
+
+    // a new change discards the redo branch
+    history.apply(Add(5), &mut counter);
+    assert!(!history.can_redo());
+    assert!(!history.redo(&mut counter));
+    assert_eq!(counter, 6);
+}
+
+#[test]
+fn coalesce() {
+    let mut counter = 0;
+    let mut history = History::new();
+    history.apply(Add(1), &mut counter);
+    history.apply(Add(2), &mut counter);
+    history.apply(Add(-1), &mut counter);
+    history.apply(Add(-1), &mut counter);
+    assert_eq!(counter, 1);
+
+    history.undo(&mut counter);
+    assert_eq!(counter, 3);
+    history.undo(&mut counter);
+    assert_eq!(counter, 0);
+    assert!(!history.can_undo());
+}
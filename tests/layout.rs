@@ -0,0 +1,272 @@
+/// These tests are not parallelizable (resources/theme access not thread safe yet), use RUST_TEST_THREADS=1 cargo test for now
+
+extern crate limn;
+
+use std::rc::Rc;
+use std::cell::RefCell;
+use std::time::Duration;
+
+use limn::prelude::*;
+use limn::draw::text::TextState;
+use limn::inspector::ToggleInspector;
+
+/// Adds a child to `parent` that records it's name in `clicks` when clicked
+fn add_clickable(parent: &mut Widget, name: &'static str, clicks: &Rc<RefCell<Vec<&'static str>>>) -> Widget {
+    let mut widget = Widget::new(name);
+    {
+        let clicks = clicks.clone();
+        widget.add_handler(move |_: &ClickEvent, _: EventArgs| {
+            clicks.borrow_mut().push(name);
+        });
+    }
+    parent.add_child(widget.clone());
+    widget
+}
+
+#[test]
+fn stack_z_index() {
+    let mut app = App::new_headless(Size::new(300.0, 300.0));
+    let clicks = Rc::new(RefCell::new(Vec::new()));
+    let mut root = Widget::new("root");
+    root.layout().add(constraints![
+        top_left(Point::new(0.0, 0.0)),
+        size(Size::new(100.0, 100.0)),
+    ]);
+    root.stack_layout(StackLayoutSettings::new());
+    let mut below = add_clickable(&mut root, "below", &clicks);
+    let mut above = add_clickable(&mut root, "above", &clicks);
+    above.layout().add(size(Size::new(50.0, 50.0)));
+    above.layout().set_item_props(Anchor::TopLeft);
+    app.set_root(root);
+
+    assert_eq!(below.bounds(), Rect::new(Point::new(0.0, 0.0), Size::new(100.0, 100.0)));
+    assert_eq!(above.bounds(), Rect::new(Point::new(0.0, 0.0), Size::new(50.0, 50.0)));
+
+    // later children are on top
+    app.click(Point::new(25.0, 25.0));
+    app.click(Point::new(75.0, 75.0));
+    assert_eq!(*clicks.borrow(), vec!["above", "below"]);
+
+    // unless a lower child has a higher z-index
+    below.set_z_index(1);
+    app.handle_events();
+    clicks.borrow_mut().clear();
+    app.click(Point::new(25.0, 25.0));
+    assert_eq!(*clicks.borrow(), vec!["below"]);
+    above.set_z_index(2);
+    app.handle_events();
+    app.click(Point::new(25.0, 25.0));
+    assert_eq!(*clicks.borrow(), vec!["below", "above"]);
+}
+
+#[test]
+fn absolute_position() {
+    let mut app = App::new_headless(Size::new(300.0, 300.0));
+    let mut root = Widget::new("root");
+    root.layout().add(constraints![
+        top_left(Point::new(0.0, 0.0)),
+        size(Size::new(200.0, 200.0)),
+    ]);
+    root.absolute_layout();
+    let mut node = Widget::new("node");
+    node.layout().add(size(Size::new(40.0, 20.0)));
+    node.layout().set_item_props(Position::new(30.0, 60.0));
+    root.add_child(node.clone());
+    let mut corner = Widget::new("corner");
+    corner.layout().add(size(Size::new(20.0, 20.0)));
+    corner.layout().set_item_props(Position::new(-10.0, -10.0).anchor(Anchor::BottomRight));
+    root.add_child(corner.clone());
+    app.set_root(root);
+
+    assert_eq!(node.bounds(), Rect::new(Point::new(30.0, 60.0), Size::new(40.0, 20.0)));
+    assert_eq!(corner.bounds(), Rect::new(Point::new(170.0, 170.0), Size::new(20.0, 20.0)));
+
+    node.layout().set_offset(100.0, 0.0);
+    app.handle_events();
+    assert_eq!(node.bounds(), Rect::new(Point::new(100.0, 0.0), Size::new(40.0, 20.0)));
+}
+
+#[test]
+fn layout_errors() {
+    let mut app = App::new_headless(Size::new(300.0, 300.0));
+    let errors = Rc::new(RefCell::new(Vec::new()));
+    {
+        let errors = errors.clone();
+        app.add_handler(move |error: &LayoutError, _: EventArgs| {
+            errors.borrow_mut().push(error.clone());
+        });
+    }
+    let mut root = Widget::new("root");
+    root.layout().add(size(Size::new(100.0, 100.0)));
+    let mut child = Widget::new("child");
+    child.layout().add(width(200.0));
+    root.add_child(child);
+    app.set_root(root);
+
+    let errors = errors.borrow();
+    assert!(!errors.is_empty());
+    let error = match errors[0] {
+        LayoutError::Unsatisfiable(ref error) => error,
+        ref error => panic!("unexpected layout error {}", error),
+    };
+    let mut involved = error.conflicts.clone();
+    involved.push(error.constraint.clone());
+    assert!(involved.iter().any(|info| info.layouts.contains(&"child".to_owned())));
+    assert!(involved.iter().any(|info| info.description == "REQD  root.width == 100"));
+}
+
+#[test]
+fn inspector() {
+    let mut app = App::new_headless(Size::new(300.0, 300.0));
+    let mut root = Widget::new("root");
+    let mut panel = Widget::new("panel");
+    panel.layout().add(constraints![
+        top_left(Point::new(10.0, 20.0)),
+        size(Size::new(100.0, 50.0)),
+    ]);
+    panel.add_prop(Property::Inactive);
+    root.add_child(panel);
+    app.set_root(root);
+
+    app.get_root().event(ToggleInspector);
+    app.mouse_moved(Point::new(50.0, 50.0));
+    let mut info = app.find_widget("inspector_info").unwrap();
+    let text = info.draw_state().downcast_ref::<TextState>().unwrap().text.clone();
+    assert!(text.starts_with("window > root > panel\nbounds: 10, 20 100x50\nz-index: 0\nprops: Inactive\n"));
+    assert!(text.contains("\n    REQD  panel.width == 100\n"));
+    let highlight = app.find_widget("inspector_highlight").unwrap();
+    assert_eq!(highlight.bounds(), Rect::new(Point::new(10.0, 20.0), Size::new(100.0, 50.0)));
+
+    // the inspector ignores the mouse, so clicks still reach the widget under it
+    let clicks = Rc::new(RefCell::new(Vec::new()));
+    let mut panel = app.find_widget("panel").unwrap();
+    panel.remove_prop(Property::Inactive);
+    add_clickable(&mut panel, "button", &clicks);
+    app.click(Point::new(50.0, 50.0));
+    assert_eq!(*clicks.borrow(), vec!["button"]);
+
+    app.get_root().event(ToggleInspector);
+    app.handle_events();
+    assert!(app.find_widget("inspector").is_none());
+}
+
+#[test]
+fn batched_layout_updates() {
+    let mut app = App::new_headless(Size::new(300.0, 300.0));
+    let updates = Rc::new(RefCell::new(0));
+    {
+        let updates = updates.clone();
+        app.add_handler(move |_: &UpdateLayout, _: EventArgs| {
+            *updates.borrow_mut() += 1;
+        });
+    }
+    let mut root = Widget::new("root");
+    let mut row = Widget::new("row");
+    row.layout().isolate();
+    row.layout().add(size(Size::new(200.0, 50.0)));
+    row.layout().edit_left().set(0.0);
+    row.layout().edit_top().set(0.0);
+    let mut label = Widget::new("label");
+    label.layout().add(width(50.0));
+    row.add_child(label.clone());
+    root.add_child(row.clone());
+    app.set_root(root);
+    assert_eq!(label.bounds(), Rect::new(Point::new(0.0, 0.0), Size::new(50.0, 50.0)));
+
+    // a widget's layout is updated once, however many times it's changed between updates
+    *updates.borrow_mut() = 0;
+    for step in 1..4 {
+        row.layout().edit_left().set(step as f32 * 10.0);
+    }
+    app.handle_events();
+    assert_eq!(*updates.borrow(), 1);
+    // the isolated row's children follow it
+    assert_eq!(label.bounds(), Rect::new(Point::new(30.0, 0.0), Size::new(50.0, 50.0)));
+}
+
+#[test]
+fn transition() {
+    let mut app = App::new_headless(Size::new(300.0, 300.0));
+    let mut root = Widget::new("root");
+    let mut panel = Widget::new("panel");
+    panel.set_transition(Transition::new(Duration::from_millis(100)).easing(Easing::Linear));
+    panel.layout().add(constraints![
+        top_left(Point::new(0.0, 0.0)),
+        width(100.0),
+    ]);
+    panel.layout().edit_height().set(100.0);
+    let content = Widget::new("content");
+    panel.add_child(content.clone());
+    root.add_child(panel.clone());
+    app.set_root(root);
+
+    // appearing widgets grow from their top left corner
+    assert_eq!(panel.bounds(), Rect::zero());
+    app.advance_animations(Duration::from_millis(100));
+    assert_eq!(panel.bounds(), Rect::new(Point::new(0.0, 0.0), Size::new(100.0, 100.0)));
+
+    panel.layout().edit_height().set(200.0);
+    app.handle_events();
+    assert_eq!(panel.bounds().size.height, 100.0);
+    app.advance_animations(Duration::from_millis(50));
+    // descendants follow the transition of their ancestors
+    for widget in &[&panel, &content] {
+        assert!((widget.bounds().size.height - 150.0).abs() < 0.01);
+    }
+    app.advance_animations(Duration::from_millis(50));
+    assert_eq!(panel.bounds(), Rect::new(Point::new(0.0, 0.0), Size::new(100.0, 200.0)));
+    assert_eq!(content.bounds(), panel.bounds());
+}
+
+#[test]
+fn breakpoints() {
+    let mut app = App::new_headless(Size::new(800.0, 300.0));
+    let mut root = Widget::new("root");
+    let mut panels = Widget::new("panels");
+    panels.layout().add(constraints![
+        top_left(Point::new(0.0, 0.0)),
+        size(Size::new(200.0, 200.0)),
+    ]);
+    let mut first = Widget::new("first");
+    first.layout().add(size(Size::new(50.0, 50.0)));
+    let mut second = Widget::new("second");
+    second.layout().add(size(Size::new(50.0, 50.0)));
+    panels.add_child(first.clone());
+    panels.add_child(second.clone());
+    // the panels are arranged in a column when the window is narrow
+    panels.add_breakpoint(Breakpoint::MaxWidth(600.0), |panels, narrow| {
+        let settings = if narrow {
+            let mut settings = LinearLayoutSettings::new(Orientation::Vertical);
+            settings.item_align = ItemAlignment::Left;
+            settings
+        } else {
+            let mut settings = LinearLayoutSettings::new(Orientation::Horizontal);
+            settings.item_align = ItemAlignment::Top;
+            settings
+        };
+        panels.linear_layout(settings);
+    });
+    let mut sidebar = Widget::new("sidebar");
+    sidebar.layout().add(constraints![
+        top_left(Point::new(200.0, 0.0)),
+        height(100.0),
+    ]);
+    sidebar.layout().edit_width().set(100.0);
+    sidebar.breakpoint_constraints(Breakpoint::MaxWidth(600.0), width(0.0));
+    root.add_child(panels);
+    root.add_child(sidebar.clone());
+    app.set_root(root);
+
+    assert_eq!(first.bounds().origin, Point::new(0.0, 0.0));
+    assert_eq!(second.bounds().origin, Point::new(50.0, 0.0));
+    assert_eq!(sidebar.bounds().size.width, 100.0);
+
+    app.resize_window(Size::new(500.0, 300.0));
+    assert_eq!(first.bounds().origin, Point::new(0.0, 0.0));
+    assert_eq!(second.bounds().origin, Point::new(0.0, 50.0));
+    assert_eq!(sidebar.bounds().size.width, 0.0);
+
+    app.resize_window(Size::new(700.0, 300.0));
+    assert_eq!(second.bounds().origin, Point::new(50.0, 0.0));
+    assert_eq!(sidebar.bounds().size.width, 100.0);
+}
@@ -0,0 +1,133 @@
+/// These tests are not parallelizable (resources/theme access not thread safe yet), use RUST_TEST_THREADS=1 cargo test for now
+
+#[macro_use]
+extern crate limn;
+
+use limn::prelude::*;
+use limn::input::DebugSettingsHandler;
+use limn::glutin::VirtualKeyCode;
+use limn::resources::font::FontDescriptor;
+use limn::draw::rect::RectStyle;
+use limn::draw::text::TextStyle;
+use limn::widget::draw::OpacityModifier;
+use limn::record::assert_snapshot;
+
+#[test]
+fn rect_opacity() {
+    let mut app = App::new_headless(Size::new(200.0, 100.0));
+    resources().theme.register_type_style(RectStyle::default());
+
+    let mut root = Widget::new("root");
+    root.set_draw_style(style!(RectStyle {
+        background_color: WHITE,
+    }));
+    let mut rect = Widget::new("rect");
+    rect.set_draw_style(style!(RectStyle {
+        background_color: RED,
+    }));
+    rect.add_modifier(OpacityModifier { alpha: 0.5 });
+    rect.layout().add(constraints![
+        top_left(Point::new(10.0, 10.0)),
+        size(Size::new(50.0, 30.0)),
+    ]);
+    root.add_child(rect);
+    app.set_root(root);
+
+    assert_snapshot("rect_opacity", &app.display_list());
+}
+
+#[test]
+fn text() {
+    let mut app = App::new_headless(Size::new(200.0, 100.0));
+    {
+        let mut res = resources();
+        res.font_loader.register_font_data(FontDescriptor::from_family("NotoSans"), include_bytes!("../assets/fonts/NotoSans/NotoSans-Regular.ttf").to_vec()).unwrap();
+        res.theme.register_type_style(RectStyle::default());
+        res.theme.register_type_style(style!(TextStyle {
+            font: FontDescriptor::from_family("NotoSans"),
+            font_size: 20.0,
+            text_color: BLACK,
+            background_color: TRANSPARENT,
+            wrap: Wrap::NoWrap,
+            align: Align::Start,
+        }));
+    }
+
+    let mut root = Widget::new("root");
+    root.set_draw_style(style!(RectStyle {
+        background_color: WHITE,
+    }));
+    let mut text = Widget::new("text");
+    text.set_draw_style(style!(TextStyle {
+        text: String::from("Limn"),
+    }));
+    text.layout().add(constraints![
+        top_left(Point::new(10.0, 10.0)),
+        size(Size::new(100.0, 30.0)),
+    ]);
+    root.add_child(text);
+    app.set_root(root);
+
+    assert_snapshot("text", &app.display_list());
+}
+
+#[test]
+fn debug_bounds_border() {
+    let mut app = App::new_headless(Size::new(200.0, 100.0));
+    resources().theme.register_type_style(RectStyle::default());
+    app.add_handler(DebugSettingsHandler::new());
+
+    let mut root = Widget::new("root");
+    root.set_draw_style(style!(RectStyle {
+        background_color: WHITE,
+    }));
+    let mut rect = Widget::new("rect");
+    rect.set_draw_style(style!(RectStyle {
+        background_color: RED,
+    }));
+    rect.set_debug_color(BLUE);
+    rect.layout().add(constraints![
+        top_left(Point::new(10.0, 10.0)),
+        size(Size::new(50.0, 30.0)),
+    ]);
+    root.add_child(rect);
+    app.set_root(root);
+    // toggles drawing widget bounds, outlined after the widget tree
+    app.key_press(VirtualKeyCode::F1);
+
+    assert_snapshot("debug_bounds_border", &app.display_list());
+}
+
+#[test]
+fn nested_clip() {
+    let mut app = App::new_headless(Size::new(200.0, 100.0));
+    resources().theme.register_type_style(RectStyle::default());
+
+    let mut root = Widget::new("root");
+    root.set_draw_style(style!(RectStyle {
+        background_color: WHITE,
+    }));
+    let mut panel = Widget::new("panel");
+    panel.set_draw_style(style!(RectStyle {
+        background_color: GRAY_80,
+    }));
+    panel.layout().no_container();
+    panel.layout().add(constraints![
+        top_left(Point::new(20.0, 20.0)),
+        size(Size::new(100.0, 50.0)),
+    ]);
+    // extends past the right edge of the panel, clipped to the panel when drawn
+    let mut rect = Widget::new("rect");
+    rect.set_draw_style(style!(RectStyle {
+        background_color: BLUE,
+    }));
+    rect.layout().add(constraints![
+        top_left(Point::new(100.0, 40.0)),
+        size(Size::new(50.0, 50.0)),
+    ]);
+    panel.add_child(rect);
+    root.add_child(panel);
+    app.set_root(root);
+
+    assert_snapshot("nested_clip", &app.display_list());
+}
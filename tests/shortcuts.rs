@@ -0,0 +1,204 @@
+/// These tests are not parallelizable (resources/theme access not thread safe yet), use RUST_TEST_THREADS=1 cargo test for now
+
+#[macro_use]
+extern crate limn;
+
+use std::rc::Rc;
+use std::cell::RefCell;
+
+use limn::prelude::*;
+use limn::glutin::{VirtualKeyCode, ModifiersState, ElementState};
+use limn::input::keyboard::WidgetKeyboardInput;
+use limn::input::shortcuts::{KeyChord, ShortcutScope};
+use limn::widgets::edit_text::{EditTextStyle, TextUpdated};
+
+mod common;
+use common::setup;
+
+#[derive(Debug, Clone, PartialEq)]
+struct Action(&'static str);
+
+/// Records the actions received by `widget`
+fn record_actions(widget: &mut Widget) -> Rc<RefCell<Vec<&'static str>>> {
+    let actions = Rc::new(RefCell::new(Vec::new()));
+    {
+        let actions = actions.clone();
+        widget.add_handler(move |event: &Action, _: EventArgs| {
+            actions.borrow_mut().push(event.0);
+        });
+    }
+    actions
+}
+
+fn ctrl() -> ModifiersState {
+    ModifiersState { ctrl: true, ..ModifiersState::default() }
+}
+
+#[test]
+fn chord_display() {
+    assert_eq!(KeyChord::new(VirtualKeyCode::S).ctrl().to_string(), "Ctrl+S");
+    assert_eq!(KeyChord::new(VirtualKeyCode::Z).ctrl().shift().to_string(), "Ctrl+Shift+Z");
+}
+
+#[test]
+fn global_shortcuts() {
+    let mut app = App::new_headless(Size::new(300.0, 300.0));
+    let actions = record_actions(&mut app.get_root());
+    app.shortcuts().register(KeyChord::new(VirtualKeyCode::S).ctrl(), ShortcutScope::Global, Action("save")).unwrap();
+    app.shortcuts().register(vec![
+        KeyChord::new(VirtualKeyCode::K).ctrl(),
+        KeyChord::new(VirtualKeyCode::O).ctrl(),
+    ], ShortcutScope::Global, Action("open folder")).unwrap();
+    app.set_root(Widget::new("root"));
+
+    app.key_press_with(VirtualKeyCode::S, ctrl());
+    app.key_press(VirtualKeyCode::S);
+    assert_eq!(*actions.borrow(), vec!["save"]);
+
+    app.key_press_with(VirtualKeyCode::K, ctrl());
+    assert_eq!(*actions.borrow(), vec!["save"]);
+    app.key_press_with(VirtualKeyCode::O, ctrl());
+    assert_eq!(*actions.borrow(), vec!["save", "open folder"]);
+
+    // a broken sequence is abandoned, the chord that broke it can still trigger a shortcut
+    app.key_press_with(VirtualKeyCode::K, ctrl());
+    app.key_press_with(VirtualKeyCode::S, ctrl());
+    assert_eq!(*actions.borrow(), vec!["save", "open folder", "save"]);
+}
+
+#[test]
+fn shortcut_conflicts() {
+    let mut app = App::new_headless(Size::new(300.0, 300.0));
+    let ctrl_k = KeyChord::new(VirtualKeyCode::K).ctrl();
+    let ctrl_s = KeyChord::new(VirtualKeyCode::S).ctrl();
+    app.shortcuts().register(vec![ctrl_k, ctrl_s], ShortcutScope::Global, Action("save all")).unwrap();
+
+    let error = app.shortcuts().register(ctrl_k, ShortcutScope::Global, Action("kill")).unwrap_err();
+    assert_eq!(error.to_string(), "shortcut Ctrl+K conflicts with existing shortcut Ctrl+K Ctrl+S");
+    assert!(app.shortcuts().register(vec![ctrl_k, ctrl_s], ShortcutScope::Global, Action("save")).is_err());
+    assert!(app.shortcuts().register(ctrl_s, ShortcutScope::Global, Action("save")).is_ok());
+
+    // shortcuts in different scopes don't conflict
+    let widget = Widget::new("widget");
+    assert!(app.shortcuts().register(ctrl_k, ShortcutScope::Focused(widget), Action("kill")).is_ok());
+}
+
+#[test]
+fn focused_shortcuts() {
+    let mut app = App::new_headless(Size::new(300.0, 300.0));
+    let global_actions = record_actions(&mut app.get_root());
+    let mut root = Widget::new("root");
+    let mut panel = Widget::new("panel");
+    panel.layout().add(constraints![
+        align_top(&root),
+        align_left(&root),
+        size(Size::new(100.0, 100.0)),
+    ]);
+    let mut input = Widget::new("input");
+    input.make_focusable();
+    input.layout().add(match_layout(&panel));
+    let keys = Rc::new(RefCell::new(Vec::new()));
+    {
+        let keys = keys.clone();
+        input.add_handler(move |event: &WidgetKeyboardInput, _: EventArgs| {
+            keys.borrow_mut().push(event.0.virtual_keycode);
+        });
+    }
+    let panel_actions = record_actions(&mut panel);
+    let ctrl_f = KeyChord::new(VirtualKeyCode::F).ctrl();
+    app.shortcuts().register(ctrl_f, ShortcutScope::Global, Action("find")).unwrap();
+    app.shortcuts().register(ctrl_f, ShortcutScope::Focused(panel.clone()), Action("find in panel")).unwrap();
+    panel.add_child(input);
+    root.add_child(panel);
+    app.set_root(root);
+
+    // without focus in the panel, only the global shortcut is active
+    app.key_press_with(VirtualKeyCode::F, ctrl());
+    assert_eq!(*global_actions.borrow(), vec!["find"]);
+
+    let input = app.find_widget("input").unwrap();
+    let bounds = input.bounds();
+    app.click(Point::new(bounds.origin.x + 50.0, bounds.origin.y + 50.0));
+    app.key_press_with(VirtualKeyCode::F, ctrl());
+    assert_eq!(*panel_actions.borrow(), vec!["find in panel"]);
+    assert_eq!(*global_actions.borrow(), vec!["find"]);
+
+    // keys that aren't shortcuts are still sent to the focused widget
+    keys.borrow_mut().clear();
+    app.key_press(VirtualKeyCode::A);
+    assert_eq!(*keys.borrow(), vec![Some(VirtualKeyCode::A), Some(VirtualKeyCode::A)]);
+}
+
+#[test]
+fn scoped_sequence_precedence() {
+    let mut app = App::new_headless(Size::new(300.0, 300.0));
+    let global_actions = record_actions(&mut app.get_root());
+    let mut root = Widget::new("root");
+    let mut panel = Widget::new("panel");
+    panel.make_focusable();
+    panel.layout().add(constraints![
+        align_top(&root),
+        align_left(&root),
+        size(Size::new(100.0, 100.0)),
+    ]);
+    let panel_actions = record_actions(&mut panel);
+    let ctrl_k = KeyChord::new(VirtualKeyCode::K).ctrl();
+    let ctrl_s = KeyChord::new(VirtualKeyCode::S).ctrl();
+    app.shortcuts().register(ctrl_k, ShortcutScope::Global, Action("kill")).unwrap();
+    app.shortcuts().register(vec![ctrl_k, ctrl_s], ShortcutScope::Focused(panel.clone()), Action("save panel")).unwrap();
+    root.add_child(panel);
+    app.set_root(root);
+
+    app.key_press_with(VirtualKeyCode::K, ctrl());
+    assert_eq!(*global_actions.borrow(), vec!["kill"]);
+
+    // with focus in the panel, the panel's sequence takes precedence over the global shortcut it starts with
+    app.click(Point::new(50.0, 50.0));
+    app.key_press_with(VirtualKeyCode::K, ctrl());
+    assert_eq!(*global_actions.borrow(), vec!["kill"]);
+    app.key_press_with(VirtualKeyCode::S, ctrl());
+    assert_eq!(*panel_actions.borrow(), vec!["save panel"]);
+    assert_eq!(*global_actions.borrow(), vec!["kill"]);
+}
+
+/// Presses `key`, receiving `char` before it's released, like typing on a keyboard
+fn type_key(app: &mut App, key: VirtualKeyCode, char: char) {
+    app.keyboard_input(ElementState::Pressed, key, ModifiersState::default());
+    app.received_character(char);
+    app.keyboard_input(ElementState::Released, key, ModifiersState::default());
+}
+
+#[test]
+fn shortcut_character_suppressed() {
+    let mut app = setup();
+    let actions = record_actions(&mut app.get_root());
+    app.shortcuts().register(KeyChord::new(VirtualKeyCode::Space), ShortcutScope::Global, Action("play")).unwrap();
+    let mut root = Widget::new("root");
+    let mut edit_text = Widget::from_modifier_style(EditTextStyle::default());
+    edit_text.set_name("edit_text");
+    edit_text.layout().add(constraints![
+        align_top(&root),
+        align_left(&root),
+        width(200.0),
+    ]);
+    let text = Rc::new(RefCell::new(String::new()));
+    {
+        let text = text.clone();
+        edit_text.add_handler(move |event: &TextUpdated, _: EventArgs| {
+            *text.borrow_mut() = event.0.clone();
+        });
+    }
+    root.add_child(edit_text.clone());
+    app.set_root(root);
+    assert!(edit_text.props().contains(&Property::Focused));
+
+    // the shortcut consumes the key press, and the character it produces
+    type_key(&mut app, VirtualKeyCode::Space, ' ');
+    assert_eq!(*actions.borrow(), vec!["play"]);
+    assert_eq!(*text.borrow(), "");
+
+    type_key(&mut app, VirtualKeyCode::A, 'a');
+    type_key(&mut app, VirtualKeyCode::B, 'b');
+    assert_eq!(*text.borrow(), "ab");
+    assert_eq!(*actions.borrow(), vec!["play"]);
+}
@@ -0,0 +1,822 @@
+/// These tests are not parallelizable (resources/theme access not thread safe yet), use RUST_TEST_THREADS=1 cargo test for now
+
+#[macro_use]
+extern crate limn;
+
+use std::rc::Rc;
+use std::cell::{Cell, RefCell};
+use std::time::Duration;
+
+use limn::prelude::*;
+use limn::glutin::{VirtualKeyCode, ModifiersState, ElementState, MouseButton};
+use limn::input::compose::Composition;
+use limn::clipboard::{Clipboard, MemoryClipboard};
+use limn::draw::text::{TextState, TextStyle};
+use limn::widgets::button::{ToggleButtonStyle, ToggleEvent};
+use limn::widgets::checkbox::{CheckboxStyle, CheckboxChanged, CheckState, SetCheckState};
+use limn::widgets::radio::{RadioGroupStyle, RadioButtonStyle, RadioChanged, SelectRadio};
+use limn::widgets::dropdown::{DropdownStyle, DropdownChanged};
+use limn::widgets::menu::{Menu, MenuItem, MenuBar};
+use limn::widgets::tooltip::TooltipStyle;
+use limn::input::shortcuts::KeyChord;
+use limn::widgets::edit_text::{EditTextStyle, TextUpdated};
+use limn::widgets::text::StaticTextStyle;
+use limn::widgets::text_area::TextAreaStyle;
+use limn::widgets::scroll::ScrollContainer;
+use limn::widgets::text_filter::TextFilter;
+use limn::widgets::slider::{SliderStyle, SliderEvent};
+use limn::widgets::list::{ListStyle, default_text_adapter};
+
+mod common;
+use common::{setup, center_of};
+
+#[test]
+fn toggle_button() {
+    let mut app = setup();
+    let mut root = Widget::new("root");
+    let mut style = ToggleButtonStyle::default();
+    style.text("toggle");
+    let mut button = Widget::from_modifier_style(style);
+    button.set_name("button");
+    let toggled = Rc::new(Cell::new(false));
+    {
+        let toggled = toggled.clone();
+        button.add_handler(move |event: &ToggleEvent, _: EventArgs| {
+            toggled.set(match *event {
+                ToggleEvent::On => true,
+                ToggleEvent::Off => false,
+            });
+        });
+    }
+    root.add_child(button);
+    app.set_root(root);
+
+    let button = app.find_widget("button").unwrap();
+    assert!(button.bounds().size.width >= 70.0);
+    assert!(!button.props().contains(&Property::Activated));
+
+    app.click(center_of(&button));
+    assert!(button.props().contains(&Property::Activated));
+    assert!(toggled.get());
+
+    app.click(center_of(&button));
+    assert!(!button.props().contains(&Property::Activated));
+    assert!(!toggled.get());
+}
+
+fn add_edit_text(root: &mut Widget) -> Rc<RefCell<String>> {
+    add_edit_text_with_style(root, EditTextStyle::default())
+}
+
+fn add_edit_text_with_style(root: &mut Widget, style: EditTextStyle) -> Rc<RefCell<String>> {
+    let mut edit_text = Widget::from_modifier_style(style);
+    edit_text.set_name("edit_text");
+    edit_text.layout().add(constraints![
+        align_top(root),
+        align_left(root),
+        width(200.0),
+    ]);
+    let text = Rc::new(RefCell::new(String::new()));
+    {
+        let text = text.clone();
+        edit_text.add_handler(move |event: &TextUpdated, _: EventArgs| {
+            *text.borrow_mut() = event.0.clone();
+        });
+    }
+    root.add_child(edit_text);
+    text
+}
+
+#[test]
+fn edit_text_typing() {
+    let mut app = setup();
+    let mut root = Widget::new("root");
+    let text = add_edit_text(&mut root);
+    app.set_root(root);
+
+    let edit_text = app.find_widget("edit_text").unwrap();
+    app.click(center_of(&edit_text));
+    assert!(edit_text.props().contains(&Property::Focused));
+
+    app.type_text("hello");
+    assert_eq!(*text.borrow(), "hello");
+    app.received_character('\u{8}');
+    assert_eq!(*text.borrow(), "hell");
+
+    let mut text_widget = app.find_widget("edit_text_text").unwrap();
+    assert_eq!(text_widget.draw_state().downcast_ref::<TextState>().unwrap().text, "hell");
+}
+
+#[test]
+fn edit_text_editing() {
+    let mut app = setup();
+    let mut root = Widget::new("root");
+    let text = add_edit_text(&mut root);
+    app.set_root(root);
+
+    let edit_text = app.find_widget("edit_text").unwrap();
+    app.click(center_of(&edit_text));
+    app.type_text("hello world");
+
+    app.key_press(VirtualKeyCode::Home);
+    app.key_press(VirtualKeyCode::Right);
+    app.key_press(VirtualKeyCode::Right);
+    app.type_text("X");
+    assert_eq!(*text.borrow(), "heXllo world");
+    app.key_press(VirtualKeyCode::Delete);
+    assert_eq!(*text.borrow(), "heXlo world");
+
+    // select the last word and replace it
+    let ctrl = ModifiersState { ctrl: true, ..ModifiersState::default() };
+    let shift = ModifiersState { shift: true, ..ModifiersState::default() };
+    app.key_press(VirtualKeyCode::End);
+    app.key_press_with(VirtualKeyCode::Left, ctrl);
+    app.key_press_with(VirtualKeyCode::End, shift);
+    let mut text_widget = app.find_widget("edit_text_text").unwrap();
+    assert_eq!(text_widget.draw_state().downcast_ref::<TextState>().unwrap().selection, Some(6..11));
+    app.type_text("there");
+    assert_eq!(*text.borrow(), "heXlo there");
+
+    // clicking at the start of the text moves the cursor there
+    let bounds = text_widget.bounds();
+    app.click(Point::new(bounds.origin.x + 1.0, bounds.origin.y + bounds.size.height / 2.0));
+    app.type_text("A");
+    assert_eq!(*text.borrow(), "AheXlo there");
+    assert_eq!(text_widget.draw_state().downcast_ref::<TextState>().unwrap().cursor, Some(1));
+}
+
+#[test]
+fn edit_text_clipboard() {
+    let mut app = setup();
+    let mut clipboard = MemoryClipboard::new();
+    clipboard.set_contents("pasted".to_owned());
+    app.set_clipboard(clipboard);
+    let mut root = Widget::new("root");
+    let text = add_edit_text(&mut root);
+    app.set_root(root);
+
+    let edit_text = app.find_widget("edit_text").unwrap();
+    app.click(center_of(&edit_text));
+    let ctrl = ModifiersState { ctrl: true, ..ModifiersState::default() };
+    app.key_press_with(VirtualKeyCode::V, ctrl);
+    assert_eq!(*text.borrow(), "pasted");
+
+    app.type_text("!");
+    app.key_press_with(VirtualKeyCode::A, ctrl);
+    app.key_press_with(VirtualKeyCode::X, ctrl);
+    assert_eq!(*text.borrow(), "");
+    app.key_press_with(VirtualKeyCode::V, ctrl);
+    app.key_press_with(VirtualKeyCode::V, ctrl);
+    assert_eq!(*text.borrow(), "pasted!pasted!");
+}
+
+#[test]
+fn edit_text_undo() {
+    let mut app = setup();
+    let mut root = Widget::new("root");
+    let text = add_edit_text(&mut root);
+    app.set_root(root);
+
+    let edit_text = app.find_widget("edit_text").unwrap();
+    app.click(center_of(&edit_text));
+    let ctrl = ModifiersState { ctrl: true, ..ModifiersState::default() };
+    let ctrl_shift = ModifiersState { ctrl: true, shift: true, ..ModifiersState::default() };
+    app.type_text("hello world");
+
+    // typing is undone a word at a time
+    app.key_press_with(VirtualKeyCode::Z, ctrl);
+    assert_eq!(*text.borrow(), "hello");
+    app.key_press_with(VirtualKeyCode::Z, ctrl);
+    assert_eq!(*text.borrow(), "");
+    app.key_press_with(VirtualKeyCode::Y, ctrl);
+    assert_eq!(*text.borrow(), "hello");
+    app.key_press_with(VirtualKeyCode::Z, ctrl_shift);
+    assert_eq!(*text.borrow(), "hello world");
+
+    // consecutive deletes are undone together, and a new edit discards the redo branch
+    app.received_character('\u{8}');
+    app.received_character('\u{8}');
+    assert_eq!(*text.borrow(), "hello wor");
+    app.key_press_with(VirtualKeyCode::Z, ctrl);
+    assert_eq!(*text.borrow(), "hello world");
+    app.type_text("!");
+    app.key_press_with(VirtualKeyCode::Y, ctrl);
+    assert_eq!(*text.borrow(), "hello world!");
+}
+
+#[test]
+fn edit_text_obscured() {
+    let mut app = setup();
+    let mut root = Widget::new("root");
+    let text = add_edit_text_with_style(&mut root, style!(EditTextStyle {
+        obscured: true,
+    }));
+    app.set_root(root);
+
+    let edit_text = app.find_widget("edit_text").unwrap();
+    app.click(center_of(&edit_text));
+    app.type_text("secret");
+    assert_eq!(*text.borrow(), "secret");
+
+    let mut text_widget = app.find_widget("edit_text_text").unwrap();
+    assert_eq!(text_widget.draw_state().downcast_ref::<TextState>().unwrap().text, "\u{2022}".repeat(6));
+}
+
+#[test]
+fn edit_text_filters() {
+    let mut app = setup();
+    let mut root = Widget::new("root");
+    let number = add_edit_text_with_style(&mut root, style!(EditTextStyle {
+        filters: vec![TextFilter::Numeric],
+    }));
+    app.set_root(root);
+
+    let edit_text = app.find_widget("edit_text").unwrap();
+    app.click(center_of(&edit_text));
+    app.type_text("-1a2.5b");
+    assert_eq!(*number.borrow(), "-12.5");
+
+    let mut app = setup();
+    let mut root = Widget::new("root");
+    let date = add_edit_text_with_style(&mut root, style!(EditTextStyle {
+        filters: vec![TextFilter::Mask("99/99/9999".to_owned())],
+        validators: vec![TextFilter::Mask("99/99/9999".to_owned())],
+    }));
+    app.set_root(root);
+
+    let edit_text = app.find_widget("edit_text").unwrap();
+    app.click(center_of(&edit_text));
+    // literal chars in the mask are inserted while typing
+    app.type_text("1231");
+    assert_eq!(*date.borrow(), "12/31/");
+    assert!(edit_text.props().contains(&Property::Invalid));
+    app.type_text("x2018");
+    assert_eq!(*date.borrow(), "12/31/2018");
+    assert!(!edit_text.props().contains(&Property::Invalid));
+}
+
+#[test]
+fn edit_text_composition() {
+    let mut app = setup();
+    let mut root = Widget::new("root");
+    let text = add_edit_text(&mut root);
+    app.set_root(root);
+
+    let edit_text = app.find_widget("edit_text").unwrap();
+    app.click(center_of(&edit_text));
+    app.type_text("ab");
+    app.key_press(VirtualKeyCode::Left);
+
+    // the pre-edit text is drawn underlined at the cursor, but isn't part of the text
+    app.composition(Composition::Start);
+    app.composition(Composition::Update("nihon".to_owned()));
+    assert_eq!(*text.borrow(), "ab");
+    {
+        let mut text_widget = app.find_widget("edit_text_text").unwrap();
+        let draw_state = text_widget.draw_state();
+        let text_state = draw_state.downcast_ref::<TextState>().unwrap();
+        assert_eq!(text_state.text, "anihonb");
+        assert_eq!(text_state.underline, Some(1..6));
+    }
+    app.composition(Composition::Commit("日本".to_owned()));
+    assert_eq!(*text.borrow(), "a日本b");
+
+    // composing over a selection previews the selection replaced, as it is on commit
+    let shift = ModifiersState { shift: true, ..ModifiersState::default() };
+    app.key_press_with(VirtualKeyCode::Left, shift);
+    app.key_press_with(VirtualKeyCode::Left, shift);
+    app.composition(Composition::Start);
+    app.composition(Composition::Update("go".to_owned()));
+    {
+        let mut text_widget = app.find_widget("edit_text_text").unwrap();
+        let draw_state = text_widget.draw_state();
+        let text_state = draw_state.downcast_ref::<TextState>().unwrap();
+        assert_eq!(text_state.text, "agob");
+        assert_eq!(text_state.selection, None);
+        assert_eq!(text_state.underline, Some(1..3));
+    }
+    app.composition(Composition::Commit("語".to_owned()));
+    assert_eq!(*text.borrow(), "a語b");
+
+    // dead keys compose accented chars
+    app.enable_dead_keys();
+    app.key_press(VirtualKeyCode::End);
+    app.type_text("'e\"x");
+    assert_eq!(*text.borrow(), "a語bé\"x");
+}
+
+#[test]
+fn text_area_multiline() {
+    let mut app = setup();
+    let mut root = Widget::new("root");
+    let mut text_area = Widget::from_modifier_style(TextAreaStyle::default());
+    text_area.set_name("text_area");
+    text_area.layout().add(constraints![
+        align_top(&root),
+        align_left(&root),
+        width(200.0),
+    ]);
+    let text = Rc::new(RefCell::new(String::new()));
+    {
+        let text = text.clone();
+        text_area.add_handler(move |event: &TextUpdated, _: EventArgs| {
+            *text.borrow_mut() = event.0.clone();
+        });
+    }
+    root.add_child(text_area);
+    app.set_root(root);
+
+    let text_area = app.find_widget("text_area").unwrap();
+    let single_line_height = text_area.bounds().size.height;
+    app.click(center_of(&text_area));
+    app.type_text("first\rsecond");
+    assert_eq!(*text.borrow(), "first\nsecond");
+    assert!(text_area.bounds().size.height > single_line_height);
+
+    // move up to the end of the first line
+    app.key_press(VirtualKeyCode::Up);
+    app.type_text("!");
+    assert_eq!(*text.borrow(), "first!\nsecond");
+    app.key_press(VirtualKeyCode::Down);
+    app.key_press(VirtualKeyCode::Home);
+    app.type_text("the ");
+    assert_eq!(*text.borrow(), "first!\nthe second");
+
+    // long lines are wrapped
+    let height = text_area.bounds().size.height;
+    app.key_press_with(VirtualKeyCode::End, ModifiersState { ctrl: true, ..ModifiersState::default() });
+    app.type_text(" line of text that is too long to fit");
+    assert!(text_area.bounds().size.height > height);
+}
+
+#[test]
+fn text_area_scroll_into_view() {
+    let mut app = setup();
+    let mut root = Widget::new("root");
+    let mut text_area = Widget::from_modifier_style(TextAreaStyle::default());
+    text_area.set_name("text_area");
+    text_area.layout().add(width(200.0));
+    let mut scroll = ScrollContainer::default();
+    scroll.add_content(text_area);
+    let mut scroll = Widget::from_modifier(scroll);
+    scroll.set_name("scroll");
+    scroll.layout().add(constraints![
+        align_top(&root),
+        align_left(&root),
+        size(Size::new(200.0, 80.0)),
+    ]);
+    root.add_child(scroll);
+    app.set_root(root);
+
+    let scroll = app.find_widget("scroll").unwrap();
+    let text_area = app.find_widget("text_area").unwrap();
+    let scroll_offset = || text_area.bounds().origin.y - scroll.bounds().origin.y;
+    assert_eq!(scroll_offset(), 0.0);
+
+    // typing moves the caret below the visible area, the content is scrolled up to keep it visible
+    app.click(center_of(&text_area));
+    app.type_text("one\rtwo\rthree\rfour\rfive\rsix");
+    assert!(text_area.bounds().size.height > scroll.bounds().size.height);
+    assert!(scroll_offset() < 0.0);
+
+    // moving the caret back to the start scrolls back to the top
+    app.key_press_with(VirtualKeyCode::Home, ModifiersState { ctrl: true, ..ModifiersState::default() });
+    assert_eq!(scroll_offset(), 0.0);
+}
+
+#[test]
+fn slider_click() {
+    let mut app = setup();
+    let mut root = Widget::new("root");
+    let mut slider = Widget::from_modifier_style(SliderStyle::default());
+    slider.set_name("slider");
+    slider.layout().add(constraints![
+        align_top(&root),
+        align_left(&root),
+        width(200.0),
+    ]);
+    let value = Rc::new(Cell::new(0.0));
+    {
+        let value = value.clone();
+        slider.add_handler(move |event: &SliderEvent, _: EventArgs| {
+            value.set(event.value);
+        });
+    }
+    root.add_child(slider);
+    app.set_root(root);
+
+    let slider = app.find_widget("slider").unwrap();
+    assert_eq!(slider.bounds().size, Size::new(200.0, 30.0));
+
+    // click on the bar, to the right of the handle
+    app.click(Point::new(150.0, 15.0));
+    assert!(value.get() > 0.5 && value.get() < 1.0);
+    let handle = app.find_widget("slider_handle").unwrap();
+    assert!(handle.bounds().origin.x > 100.0);
+}
+
+#[test]
+fn list_select() {
+    let mut app = setup();
+    let mut root = Widget::new("root");
+    let mut list = Widget::from_modifier_style(ListStyle::default());
+    list.set_name("list");
+    list.layout().add(constraints![
+        align_top(&root),
+        align_left(&root),
+        width(200.0),
+    ]);
+    let items = vec!["first", "second", "third"];
+    list.set_contents(items.iter().map(|item| item.to_string()), default_text_adapter);
+    root.add_child(list);
+    app.set_root(root);
+
+    let list = app.find_widget("list").unwrap();
+    let list_items = list.children();
+    assert_eq!(list_items.len(), 3);
+    assert!(list_items[0].bounds().origin.y < list_items[1].bounds().origin.y);
+
+    app.click(center_of(&list_items[1]));
+    assert!(list_items[1].props().contains(&Property::Selected));
+
+    app.click(center_of(&list_items[2]));
+    assert!(!list_items[1].props().contains(&Property::Selected));
+    assert!(list_items[2].props().contains(&Property::Selected));
+}
+
+#[test]
+fn static_text_size_to_content() {
+    let mut app = setup();
+    let mut root = Widget::new("root");
+    let mut text = Widget::from_modifier_style(StaticTextStyle::from_text("wrapped to a narrow width"));
+    root.add_child(text.clone());
+    app.set_root(root);
+    let (size, line_height, wrapped_height) = {
+        let draw_state = text.draw_state();
+        let state = draw_state.downcast_ref::<TextState>().unwrap();
+        (state.measure(), state.line_height(), state.text_height(100.0))
+    };
+    assert_eq!(text.bounds().size, size);
+    assert_eq!(size.height, line_height);
+
+    // wrapped text is as tall as the text wrapped to the width of the widget
+    text.layout().add(width(100.0));
+    app.handle_events();
+    assert!(wrapped_height > line_height);
+    assert_eq!(text.bounds().size, Size::new(100.0, wrapped_height));
+}
+
+#[test]
+fn static_text_no_wrap_exact_size() {
+    let mut app = setup();
+    let mut root = Widget::new("root");
+    let mut text = Widget::from_modifier_style(StaticTextStyle::from_style(style!(TextStyle {
+        text: String::from("short"),
+        wrap: Wrap::NoWrap,
+    })));
+    // text that doesn't wrap can't be stretched
+    text.layout().add(width(250.0).strength(STRONG));
+    root.add_child(text.clone());
+    app.set_root(root);
+    let size = {
+        let draw_state = text.draw_state();
+        draw_state.downcast_ref::<TextState>().unwrap().measure()
+    };
+    assert!(size.width < 250.0);
+    assert_eq!(text.bounds().size, size);
+}
+
+#[test]
+fn checkbox() {
+    let mut app = setup();
+    let mut root = Widget::new("root");
+    let mut style = CheckboxStyle::from_text("check");
+    style.tri_state = Some(true);
+    let mut checkbox = Widget::from_modifier_style(style);
+    checkbox.set_name("checkbox");
+    let states = Rc::new(RefCell::new(Vec::new()));
+    {
+        let states = states.clone();
+        checkbox.add_handler(move |event: &CheckboxChanged, _: EventArgs| {
+            states.borrow_mut().push(event.0);
+        });
+    }
+    root.add_child(checkbox);
+    app.set_root(root);
+
+    let checkbox = app.find_widget("checkbox").unwrap();
+    let check_box = app.find_widget("checkbox_box").unwrap();
+    assert_eq!(check_box.bounds().size, Size::new(20.0, 20.0));
+
+    app.click(center_of(&checkbox));
+    assert!(checkbox.props().contains(&Property::Activated));
+    app.click(center_of(&checkbox));
+    assert!(!checkbox.props().contains(&Property::Activated));
+    assert!(checkbox.props().contains(&Property::Indeterminate));
+    // the only focusable widget has focus, so space toggles it
+    app.key_press(VirtualKeyCode::Space);
+    assert!(!checkbox.props().contains(&Property::Indeterminate));
+    assert_eq!(*states.borrow(), vec![CheckState::Checked, CheckState::Indeterminate, CheckState::Unchecked]);
+
+    checkbox.event(SetCheckState(CheckState::Checked));
+    app.handle_events();
+    assert!(checkbox.props().contains(&Property::Activated));
+}
+
+#[test]
+fn radio_group() {
+    let mut app = setup();
+    let mut root = Widget::new("root");
+    let mut group = Widget::from_modifier_style(RadioGroupStyle::default());
+    group.set_name("group");
+    let values = Rc::new(RefCell::new(Vec::new()));
+    {
+        let values = values.clone();
+        group.add_handler(move |event: &RadioChanged, _: EventArgs| {
+            values.borrow_mut().push(event.0.clone());
+        });
+    }
+    let mut small = RadioButtonStyle::from_text("Small", "small");
+    small.selected = Some(true);
+    let small = Widget::from_modifier_style(small);
+    let large = Widget::from_modifier_style(RadioButtonStyle::from_text("Large", "large"));
+    group.add_child(small.clone());
+    group.add_child(large.clone());
+    root.add_child(group);
+    app.set_root(root);
+
+    assert!(small.props().contains(&Property::Activated));
+    assert!(small.bounds().origin.y < large.bounds().origin.y);
+    assert!(small.children().iter().any(|child| child.name() == "radio_button_text"));
+
+    app.click(center_of(&large));
+    assert!(large.props().contains(&Property::Activated));
+    assert!(!small.props().contains(&Property::Activated));
+
+    small.event(SelectRadio);
+    app.handle_events();
+    assert!(small.props().contains(&Property::Activated));
+    assert!(!large.props().contains(&Property::Activated));
+    assert_eq!(*values.borrow(), vec!["small", "large", "small"]);
+}
+
+fn add_dropdown(root: &mut Widget, style: DropdownStyle) -> Rc<RefCell<Vec<String>>> {
+    let mut dropdown = Widget::from_modifier_style(style);
+    dropdown.set_name("dropdown");
+    dropdown.layout().add(constraints![
+        align_top(root),
+        align_left(root),
+        width(150.0),
+    ]);
+    let values = Rc::new(RefCell::new(Vec::new()));
+    {
+        let values = values.clone();
+        dropdown.add_handler(move |event: &DropdownChanged, _: EventArgs| {
+            values.borrow_mut().push(event.0.clone());
+        });
+    }
+    root.add_child(dropdown);
+    values
+}
+
+#[test]
+fn dropdown() {
+    let mut app = setup();
+    let mut root = Widget::new("root");
+    let values = add_dropdown(&mut root, DropdownStyle::from_items(&["apple", "banana", "cherry"]));
+    app.set_root(root);
+
+    let dropdown = app.find_widget("dropdown").unwrap();
+    app.click(center_of(&dropdown));
+    let popup = app.find_widget("dropdown_popup").unwrap();
+    assert!(dropdown.props().contains(&Property::Activated));
+    assert_eq!(popup.bounds().origin.y, dropdown.bounds().origin.y + dropdown.bounds().size.height);
+    assert_eq!(popup.bounds().size.width, 150.0);
+
+    let items = popup.children();
+    assert_eq!(items.len(), 3);
+    app.click(center_of(&items[1]));
+    assert!(app.find_widget("dropdown_popup").is_none());
+    assert!(!dropdown.props().contains(&Property::Activated));
+    let text = app.find_widget("dropdown_text").unwrap();
+    assert_eq!(text.draw_state().downcast_ref::<TextState>().unwrap().text, "banana");
+
+    // clicking outside closes the popup, without reaching the widget under the mouse
+    app.click(center_of(&dropdown));
+    assert!(app.find_widget("dropdown_popup").is_some());
+    app.click(Point::new(250.0, 250.0));
+    assert!(app.find_widget("dropdown_popup").is_none());
+
+    app.click(center_of(&dropdown));
+    app.key_press(VirtualKeyCode::Escape);
+    assert!(app.find_widget("dropdown_popup").is_none());
+    assert_eq!(*values.borrow(), vec!["banana"]);
+}
+
+#[test]
+fn combobox() {
+    let mut app = setup();
+    let mut root = Widget::new("root");
+    let values = add_dropdown(&mut root, DropdownStyle::combobox(&["apple", "banana", "cherry"]));
+    app.set_root(root);
+
+    let edit = app.find_widget("dropdown_edit").unwrap();
+    app.click(center_of(&edit));
+    assert_eq!(app.find_widget("dropdown_popup").unwrap().children().len(), 3);
+    app.type_text("an");
+    let popup = app.find_widget("dropdown_popup").unwrap();
+    let items = popup.children();
+    assert_eq!(items.len(), 1);
+
+    app.click(center_of(&items[0]));
+    assert!(app.find_widget("dropdown_popup").is_none());
+    let text = app.find_widget("edit_text_text").unwrap();
+    assert_eq!(text.draw_state().downcast_ref::<TextState>().unwrap().text, "banana");
+    assert_eq!(*values.borrow(), vec!["banana"]);
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum MenuAction {
+    Copy,
+    ShowGrid(bool),
+    Paste,
+}
+
+fn popup_count(app: &App) -> usize {
+    app.find_widget("popup_layer").map_or(0, |layer| layer.children().len())
+}
+
+#[test]
+fn context_menu() {
+    let mut app = setup();
+    let mut root = Widget::new("root");
+    let mut canvas = Widget::new("canvas");
+    canvas.set_context_menu(Menu::new()
+        .item(MenuItem::new("Copy").event(MenuAction::Copy).shortcut(KeyChord::new(VirtualKeyCode::C).ctrl()))
+        .separator()
+        .item(MenuItem::check("Show grid", true).event_with(MenuAction::ShowGrid))
+        .submenu("More", Menu::new()
+            .item(MenuItem::new("Paste").event(MenuAction::Paste))));
+    let actions = Rc::new(RefCell::new(Vec::new()));
+    {
+        let actions = actions.clone();
+        canvas.add_handler(move |event: &MenuAction, _: EventArgs| {
+            actions.borrow_mut().push(event.clone());
+        });
+    }
+    root.add_child(canvas);
+    app.set_root(root);
+
+    let right_click = |app: &mut App, point: Point| {
+        app.mouse_moved(point);
+        app.mouse_button(ElementState::Pressed, MouseButton::Right);
+        app.mouse_button(ElementState::Released, MouseButton::Right);
+    };
+    right_click(&mut app, Point::new(50.0, 50.0));
+    let menu = app.find_widget("menu").unwrap();
+    assert_eq!(menu.bounds().origin, Point::new(50.0, 50.0));
+    // copy, separator, show grid, more
+    let items = menu.children();
+    assert_eq!(items.len(), 4);
+    assert!(items[2].props().contains(&Property::Activated));
+
+    // the separator is skipped
+    app.key_press(VirtualKeyCode::Down);
+    app.key_press(VirtualKeyCode::Down);
+    assert!(items[2].props().contains(&Property::Selected));
+    app.key_press(VirtualKeyCode::Return);
+    assert_eq!(popup_count(&app), 0);
+
+    right_click(&mut app, Point::new(50.0, 50.0));
+    let items = app.find_widget("menu").unwrap().children();
+    assert!(!items[2].props().contains(&Property::Activated));
+    app.click(center_of(&items[0]));
+    assert_eq!(popup_count(&app), 0);
+
+    right_click(&mut app, Point::new(50.0, 50.0));
+    let items = app.find_widget("menu").unwrap().children();
+    app.mouse_moved(center_of(&items[3]));
+    assert_eq!(popup_count(&app), 2);
+    app.key_press(VirtualKeyCode::Escape);
+    assert_eq!(popup_count(&app), 1);
+    app.mouse_moved(center_of(&items[0]));
+    app.mouse_moved(center_of(&items[3]));
+    let submenu = app.find_widget("popup_layer").unwrap().children()[1].clone();
+    assert!(submenu.bounds().origin.x >= items[3].bounds().origin.x + items[3].bounds().size.width);
+    app.click(center_of(&submenu.children()[0]));
+    assert_eq!(popup_count(&app), 0);
+
+    assert_eq!(*actions.borrow(), vec![MenuAction::ShowGrid(false), MenuAction::Copy, MenuAction::Paste]);
+}
+
+#[test]
+fn menu_bar() {
+    let mut app = setup();
+    let mut root = Widget::new("root");
+    let mut bar = Widget::from_modifier(MenuBar::new()
+        .menu("File", Menu::new().item(MenuItem::new("Copy").event(MenuAction::Copy)))
+        .menu("Edit", Menu::new().item(MenuItem::new("Paste").event(MenuAction::Paste))));
+    bar.layout().add(constraints![
+        align_top(&root),
+        align_left(&root),
+        align_right(&root),
+    ]);
+    let actions = Rc::new(RefCell::new(Vec::new()));
+    {
+        let actions = actions.clone();
+        root.add_handler(move |event: &MenuAction, _: EventArgs| {
+            actions.borrow_mut().push(event.clone());
+        });
+    }
+    root.add_child(bar.clone());
+    app.set_root(root);
+
+    let bar_items = bar.children();
+    assert!(bar_items[0].bounds().origin.x < bar_items[1].bounds().origin.x);
+    app.click(center_of(&bar_items[0]));
+    assert!(bar_items[0].props().contains(&Property::Activated));
+    let menu = app.find_widget("menu").unwrap();
+    assert_eq!(menu.bounds().origin.y, bar_items[0].bounds().origin.y + bar_items[0].bounds().size.height);
+
+    // hovering over another menu of the bar opens it
+    app.mouse_moved(center_of(&bar_items[1]));
+    assert_eq!(popup_count(&app), 1);
+    assert!(bar_items[1].props().contains(&Property::Activated));
+    assert!(!bar_items[0].props().contains(&Property::Activated));
+
+    app.key_press(VirtualKeyCode::Right);
+    assert!(bar_items[0].props().contains(&Property::Activated));
+    app.key_press(VirtualKeyCode::Return);
+    assert_eq!(popup_count(&app), 0);
+    assert!(!bar_items[0].props().contains(&Property::Activated));
+    assert_eq!(*actions.borrow(), vec![MenuAction::Copy]);
+}
+
+#[test]
+fn tooltip() {
+    let mut app = setup();
+    let delay = Duration::from_millis(500);
+    resources().theme.register_modifier_type_style(style!(TooltipStyle {
+        delay: delay,
+    }));
+    let mut root = Widget::new("root");
+    let mut save = Widget::new("save");
+    save.set_tooltip("Saves the file");
+    save.layout().add(constraints![
+        top_left(Point::new(0.0, 0.0)),
+        size(Size::new(100.0, 30.0)),
+    ]);
+    let mut corner = Widget::new("corner");
+    corner.set_tooltip("A tooltip that doesn't fit");
+    corner.layout().add(constraints![
+        top_left(Point::new(250.0, 250.0)),
+        size(Size::new(50.0, 50.0)),
+    ]);
+    let mut menu_button = Widget::new("menu_button");
+    menu_button.set_context_menu(Menu::new().item(MenuItem::new("Open")));
+    menu_button.layout().add(constraints![
+        top_left(Point::new(0.0, 100.0)),
+        size(Size::new(100.0, 30.0)),
+    ]);
+    root.add_child(save.clone());
+    root.add_child(corner.clone());
+    root.add_child(menu_button.clone());
+    app.set_root(root);
+
+    // the tooltip is shown once the mouse has hovered over the widget for the delay
+    app.mouse_moved(center_of(&save));
+    app.advance_tooltips(delay / 2);
+    assert!(app.find_widget("tooltip").is_none());
+    app.advance_tooltips(delay / 2);
+    let tooltip = app.find_widget("tooltip").unwrap();
+    assert_eq!(tooltip.bounds().origin, Point::new(50.0, 35.0));
+    assert_eq!(app.find_widget("tooltip_text").unwrap().draw_state().downcast_ref::<TextState>().unwrap().text, "Saves the file");
+
+    app.mouse_moved(Point::new(150.0, 150.0));
+    assert!(app.find_widget("tooltip").is_none());
+
+    // pressing a button hides the tooltip until the mouse leaves the widget
+    app.mouse_moved(center_of(&save));
+    app.advance_tooltips(delay);
+    app.mouse_button(ElementState::Pressed, MouseButton::Left);
+    app.mouse_button(ElementState::Released, MouseButton::Left);
+    assert!(app.find_widget("tooltip").is_none());
+    app.advance_tooltips(delay);
+    assert!(app.find_widget("tooltip").is_none());
+
+    app.mouse_moved(Point::new(290.0, 290.0));
+    app.advance_tooltips(delay);
+    let bounds = app.find_widget("tooltip").unwrap().bounds();
+    assert!(bounds.origin.x >= 0.0 && bounds.origin.x + bounds.size.width <= 300.0);
+    assert!(bounds.origin.y >= 0.0 && bounds.origin.y + bounds.size.height <= 300.0);
+
+    // tooltips are drawn over popups
+    app.mouse_moved(center_of(&menu_button));
+    app.mouse_button(ElementState::Pressed, MouseButton::Right);
+    app.mouse_button(ElementState::Released, MouseButton::Right);
+    let popup_layer = app.find_widget("popup_layer").unwrap();
+    app.mouse_moved(Point::new(290.0, 290.0));
+    app.advance_tooltips(delay);
+    let tooltip_layer = app.find_widget("tooltip_layer").unwrap();
+    assert!(tooltip_layer.z_index() > popup_layer.z_index());
+}
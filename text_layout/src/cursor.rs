@@ -369,9 +369,9 @@ pub fn closest_line<'a, I>(y_pos: f32, xys_per_line: I) -> Option<(usize, Xs<'a,
 pub fn closest_cursor_index_and_xy<'a, I>(point: Point, xys_per_line: I) -> Option<(Index, Point)>
     where I: Iterator<Item = (Xs<'a, 'a>, Range)>
 {
-    closest_line(point.x, xys_per_line)
+    closest_line(point.y, xys_per_line)
         .and_then(|(closest_line_idx, closest_line_xs, closest_line_y)| {
-            let (closest_char_idx, closest_x) = closest_cursor_index_on_line(point.y,
+            let (closest_char_idx, closest_x) = closest_cursor_index_on_line(point.x,
                                                                              closest_line_xs);
             let index = Index {
                 line: closest_line_idx,
@@ -325,8 +325,7 @@ impl<'a, I> Iterator for SelectedLineRects<'a, I>
         while let Some(mut rects) = self.selected_glyph_rects_per_line.next() {
             if let Some(first_rect) = rects.next() {
                 let total_selected_rect = rects.fold(first_rect, |mut total, next| {
-                    // TODO ?
-                    total.size.width = next.width();
+                    total.size.width = next.right() - total.left();
                     total
                 });
                 return Some(total_selected_rect);